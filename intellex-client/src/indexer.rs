@@ -0,0 +1,915 @@
+//! Reference indexer for the registry's NEP-297 events, behind the
+//! `indexer` feature.
+//!
+//! Like the rest of this crate it leaves transport to the caller. Blocks
+//! come from a `BlockSource`: `RpcPoller` over an `RpcTransport` you
+//! implement with your RPC library, or `ChannelSource` fed by a NEAR Lake
+//! streamer task. Events go to an `EventSink` such as `StdoutSink` or
+//! `PostgresSink`. Call `Indexer::run_once` in a loop to drive it.
+
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::mpsc::{Receiver, TryRecvError};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use intellex_types::gas_config::GasConfig;
+use intellex_types::registration_access::RegistrationMode;
+use intellex_types::registration_limits::RegistrationLimits;
+use intellex_types::registry_config::RegistryConfig;
+use intellex_types::reputation_engine::ReputationEngineConfig;
+use intellex_types::staking::u128_string;
+use intellex_types::{AccountId, AgentStatus};
+
+/// Prefix of NEP-297 event logs.
+pub const EVENT_LOG_PREFIX: &str = "EVENT_JSON:";
+/// `standard` field of the registry's events.
+pub const EVENT_STANDARD: &str = "iaip";
+
+/// Logs from executing one receipt.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReceiptOutcome {
+    pub block_height: u64,
+    pub block_timestamp: u64,
+    pub receipt_id: String,
+    pub executor_id: AccountId,
+    pub logs: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ChangedBy {
+    pub by: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ReputationUpdatesResumed {
+    pub by: AccountId,
+    pub held_updates: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ReputationContractChanged {
+    /// `None` when the registry was computing reputation locally.
+    pub old: Option<AccountId>,
+    pub new: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ReputationEngineConfigChanged {
+    pub config: ReputationEngineConfig,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RegistryConfigChanged {
+    pub config: RegistryConfig,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RegistrationLimitsChanged {
+    pub limits: RegistrationLimits,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct GasConfigChanged {
+    pub config: GasConfig,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TaskHistoryWindowChanged {
+    pub window: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RegistrationModeChanged {
+    pub mode: RegistrationMode,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AllowlistUpdated {
+    pub added: Vec<AccountId>,
+    pub removed: Vec<AccountId>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AccountBanned {
+    pub account_id: AccountId,
+    pub reason: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AccountUnbanned {
+    pub account_id: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RegistrationBondLocked {
+    pub account_id: AccountId,
+    #[serde(with = "u128_string")]
+    pub amount: u128,
+    pub unlocks_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RegistrationBondClaimed {
+    pub account_id: AccountId,
+    #[serde(with = "u128_string")]
+    pub amount: u128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct OwnershipProposed {
+    pub owner_id: AccountId,
+    pub pending_owner_id: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct OwnershipProposalCancelled {
+    pub owner_id: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct OwnershipTransferred {
+    pub old_owner_id: AccountId,
+    pub new_owner_id: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AgentSlashed {
+    pub agent_id: AccountId,
+    #[serde(with = "u128_string")]
+    pub amount: u128,
+    #[serde(with = "u128_string")]
+    pub requested_amount: u128,
+    pub reason: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SocialProfileLinked {
+    pub agent_id: AccountId,
+    pub social_account_id: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SocialProfileUnlinked {
+    pub agent_id: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StakeDeposited {
+    pub account_id: AccountId,
+    #[serde(with = "u128_string")]
+    pub amount: u128,
+    #[serde(with = "u128_string")]
+    pub staked_balance: u128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StakeUnstaked {
+    pub account_id: AccountId,
+    #[serde(with = "u128_string")]
+    pub amount: u128,
+    pub available_at: u64,
+}
+
+/// Payload of `stake_withdrawn` and the storage deposit events.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AmountMoved {
+    pub account_id: AccountId,
+    #[serde(with = "u128_string")]
+    pub amount: u128,
+}
+
+/// A call to the reputation contract that failed, such as
+/// `initialize_agent`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ReputationCallFailed {
+    pub method: String,
+    pub agent_id: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AgentStatusChanged {
+    pub agent_id: AccountId,
+    pub old_status: AgentStatus,
+    pub new_status: AgentStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AgentTransferProposed {
+    pub agent_id: AccountId,
+    pub new_owner: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AgentTransferCancelled {
+    pub agent_id: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AgentTransferred {
+    pub old_agent_id: AccountId,
+    pub new_agent_id: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TaskCreated {
+    pub task_id: u64,
+    pub creator: AccountId,
+    #[serde(with = "u128_string")]
+    pub reward: u128,
+}
+
+/// Payload of `task_assigned` and `task_submitted`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TaskAgent {
+    pub task_id: u64,
+    pub agent_id: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TaskFunded {
+    pub task_id: u64,
+    #[serde(with = "u128_string")]
+    pub amount: u128,
+    #[serde(with = "u128_string")]
+    pub itlx_reward: u128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TaskCompleted {
+    pub task_id: u64,
+    pub agent_id: AccountId,
+    #[serde(with = "u128_string")]
+    pub reward: u128,
+    #[serde(with = "u128_string")]
+    pub itlx_reward: u128,
+    pub fee_bps: u16,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ProtocolFeeChanged {
+    pub fee_bps: u16,
+    pub treasury_id: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TaskCancelled {
+    pub task_id: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TasksCompacted {
+    pub task_ids: Vec<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TaskDisputed {
+    pub task_id: u64,
+    pub raised_by: AccountId,
+    pub deadline: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DisputeResolved {
+    pub task_id: u64,
+    pub agent_id: AccountId,
+    pub agent_share_bps: u16,
+    pub report_failure: bool,
+    /// Settled with the fallback ruling after the arbiter's deadline.
+    pub expired: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ArbiterChanged {
+    pub arbiter_id: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ListingFlagged {
+    pub flag_id: u64,
+    pub agent_id: AccountId,
+    pub flagger: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FlagConfirmed {
+    pub flag_id: u64,
+    pub agent_id: AccountId,
+    pub flagger: AccountId,
+    #[serde(with = "u128_string")]
+    pub bounty_paid: u128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FlagRejected {
+    pub flag_id: u64,
+    pub agent_id: AccountId,
+    pub flagger: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RankingsSnapshotted {
+    pub epoch: u64,
+    pub taken_by: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RankedSkillsChanged {
+    pub skills: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CurationBountyChanged {
+    #[serde(with = "u128_string")]
+    pub bounty: u128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct InviteCodesMinted {
+    pub count: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct InviteCodeRevoked {
+    pub code_hash: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct InviteRedeemed {
+    pub agent_id: AccountId,
+    pub code_hash: String,
+    pub cohort: String,
+    /// The agent whose referral code was redeemed.
+    #[serde(default)]
+    pub inviter: Option<AccountId>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct InviteCreated {
+    pub inviter: AccountId,
+    pub code_hash: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ReviewSubmitted {
+    pub agent_id: AccountId,
+    pub task_id: u64,
+    pub reviewer: AccountId,
+    pub rating: u8,
+}
+
+/// Payload of `skill_endorsed` and `endorsement_revoked`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SkillEndorsementChanged {
+    pub agent_id: AccountId,
+    pub skill: String,
+    pub endorser: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MirroredMethodsChanged {
+    pub methods: Vec<String>,
+}
+
+/// A mirrored call for a canary relayer to replay.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CallMirrored {
+    pub method: String,
+    pub caller: AccountId,
+    #[serde(with = "u128_string")]
+    pub deposit: u128,
+    /// `Null` when `args_omitted`; read them from the transaction instead.
+    pub args: Value,
+    pub args_omitted: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CanonicalSkillSet {
+    pub skill: String,
+    pub aliases: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CanonicalSkillRemoved {
+    pub skill: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StrictSkillTaxonomyChanged {
+    pub strict: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SkillIndexMigrated {
+    pub skills: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ReputationRankingMigrated {
+    pub ranked: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AgentNftTransferableChanged {
+    pub transferable: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CredentialIssued {
+    pub agent_id: AccountId,
+    pub name: String,
+    pub issuer: AccountId,
+    pub expires_at: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CredentialRevoked {
+    pub agent_id: AccountId,
+    pub name: String,
+    pub revoked_by: AccountId,
+}
+
+/// Payload of `credential_verifier_added` and `credential_verifier_removed`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CredentialVerifierChanged {
+    pub account_id: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ServiceDescriptorUpdated {
+    pub agent_id: AccountId,
+    pub endpoint_url: Option<String>,
+    pub protocols: Vec<String>,
+}
+
+/// Payload of `operator_added` and `operator_removed`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct OperatorChanged {
+    pub agent_id: AccountId,
+    pub operator_id: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TeamCreated {
+    pub team_id: u64,
+    pub name: String,
+    pub lead: AccountId,
+}
+
+/// Payload of `team_member_invited`, `team_member_joined` and
+/// `team_member_removed`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TeamMemberChanged {
+    pub team_id: u64,
+    pub agent_id: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TeamDisbanded {
+    pub team_id: u64,
+    pub lead: AccountId,
+}
+
+/// Payload of `skill_added` and `skill_removed`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SkillChanged {
+    pub agent_id: AccountId,
+    pub skill: String,
+}
+
+/// Payload of `system_skill_granted` and `system_skill_revoked`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SystemSkillChanged {
+    pub agent_id: AccountId,
+    pub skill: String,
+}
+
+/// One event emitted by the registry. Keep in step with the contract's
+/// `events::emit` calls; events this build does not know become `Unknown`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum RegistryEvent {
+    RegistryPaused(ChangedBy),
+    RegistryUnpaused(ChangedBy),
+    RegistrationPaused(ChangedBy),
+    RegistrationResumed(ChangedBy),
+    ReputationUpdatesPaused(ChangedBy),
+    ReputationUpdatesResumed(ReputationUpdatesResumed),
+    ReputationContractChanged(ReputationContractChanged),
+    ReputationEngineConfigChanged(ReputationEngineConfigChanged),
+    RegistryConfigChanged(RegistryConfigChanged),
+    RegistrationLimitsChanged(RegistrationLimitsChanged),
+    RegistrationBondLocked(RegistrationBondLocked),
+    RegistrationBondClaimed(RegistrationBondClaimed),
+    RegistrationModeChanged(RegistrationModeChanged),
+    AllowlistUpdated(AllowlistUpdated),
+    AccountBanned(AccountBanned),
+    AccountUnbanned(AccountUnbanned),
+    ReputationCallFailed(ReputationCallFailed),
+    GasConfigChanged(GasConfigChanged),
+    TaskHistoryWindowChanged(TaskHistoryWindowChanged),
+    ProtocolFeeChanged(ProtocolFeeChanged),
+    ArbiterChanged(ArbiterChanged),
+    OwnershipProposed(OwnershipProposed),
+    OwnershipProposalCancelled(OwnershipProposalCancelled),
+    OwnershipTransferred(OwnershipTransferred),
+    AgentSlashed(AgentSlashed),
+    AgentStatusChanged(AgentStatusChanged),
+    AgentTransferProposed(AgentTransferProposed),
+    AgentTransferCancelled(AgentTransferCancelled),
+    AgentTransferred(AgentTransferred),
+    SocialProfileLinked(SocialProfileLinked),
+    SocialProfileUnlinked(SocialProfileUnlinked),
+    StakeDeposited(StakeDeposited),
+    StakeUnstaked(StakeUnstaked),
+    StakeWithdrawn(AmountMoved),
+    SkillAdded(SkillChanged),
+    SkillRemoved(SkillChanged),
+    SystemSkillGranted(SystemSkillChanged),
+    SystemSkillRevoked(SystemSkillChanged),
+    StorageDeposit(AmountMoved),
+    StorageWithdraw(AmountMoved),
+    StorageRefund(AmountMoved),
+    TaskCreated(TaskCreated),
+    TaskFunded(TaskFunded),
+    TaskAssigned(TaskAgent),
+    TaskSubmitted(TaskAgent),
+    TaskCompleted(TaskCompleted),
+    TaskCancelled(TaskCancelled),
+    TasksCompacted(TasksCompacted),
+    TaskDisputed(TaskDisputed),
+    DisputeResolved(DisputeResolved),
+    ListingFlagged(ListingFlagged),
+    FlagConfirmed(FlagConfirmed),
+    FlagRejected(FlagRejected),
+    CurationBountyChanged(CurationBountyChanged),
+    CurationPoolFunded(AmountMoved),
+    RankingsSnapshotted(RankingsSnapshotted),
+    RankedSkillsChanged(RankedSkillsChanged),
+    InviteOnlyEnabled(ChangedBy),
+    InviteOnlyDisabled(ChangedBy),
+    InviteCodesMinted(InviteCodesMinted),
+    InviteCodeRevoked(InviteCodeRevoked),
+    InviteRedeemed(InviteRedeemed),
+    InviteCreated(InviteCreated),
+    ReviewSubmitted(ReviewSubmitted),
+    SkillEndorsed(SkillEndorsementChanged),
+    EndorsementRevoked(SkillEndorsementChanged),
+    MirroredMethodsChanged(MirroredMethodsChanged),
+    CallMirrored(CallMirrored),
+    CanonicalSkillSet(CanonicalSkillSet),
+    CanonicalSkillRemoved(CanonicalSkillRemoved),
+    StrictSkillTaxonomyChanged(StrictSkillTaxonomyChanged),
+    SkillIndexMigrated(SkillIndexMigrated),
+    ReputationRankingMigrated(ReputationRankingMigrated),
+    AgentNftTransferableChanged(AgentNftTransferableChanged),
+    CredentialIssued(CredentialIssued),
+    CredentialRevoked(CredentialRevoked),
+    CredentialVerifierAdded(CredentialVerifierChanged),
+    CredentialVerifierRemoved(CredentialVerifierChanged),
+    ServiceDescriptorUpdated(ServiceDescriptorUpdated),
+    OperatorAdded(OperatorChanged),
+    OperatorRemoved(OperatorChanged),
+    TeamCreated(TeamCreated),
+    TeamMemberInvited(TeamMemberChanged),
+    TeamMemberJoined(TeamMemberChanged),
+    TeamMemberRemoved(TeamMemberChanged),
+    TeamDisbanded(TeamDisbanded),
+    #[serde(other)]
+    Unknown,
+}
+
+/// A registry event with the position it was found at.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct IndexedEvent {
+    pub block_height: u64,
+    pub block_timestamp: u64,
+    pub receipt_id: String,
+    /// Position among the events of the receipt; with `receipt_id`, a
+    /// unique key for the event.
+    pub index: u32,
+    /// Event name as logged, kept for `Unknown` events.
+    pub name: String,
+    /// `version` of the event standard the contract logged.
+    pub version: String,
+    #[serde(flatten)]
+    pub event: RegistryEvent,
+}
+
+#[derive(Debug)]
+pub enum IndexerError {
+    /// An `iaip` event log that does not match this build's schema.
+    Json(serde_json::Error),
+    Source(String),
+    Sink(String),
+}
+
+impl fmt::Display for IndexerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IndexerError::Json(err) => write!(f, "malformed registry event: {}", err),
+            IndexerError::Source(msg) => write!(f, "block source failed: {}", msg),
+            IndexerError::Sink(msg) => write!(f, "event sink failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for IndexerError {}
+
+impl From<serde_json::Error> for IndexerError {
+    fn from(err: serde_json::Error) -> Self {
+        IndexerError::Json(err)
+    }
+}
+
+#[derive(Deserialize)]
+struct EventLog {
+    standard: String,
+    version: String,
+    event: String,
+    data: Vec<Value>,
+}
+
+/// One entry of an event log's `data`, decoded.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParsedEvent {
+    pub name: String,
+    pub version: String,
+    pub event: RegistryEvent,
+}
+
+/// Decodes each entry of an event log line's `data`. Returns `Ok(None)`
+/// for logs that are not `iaip` events.
+pub fn parse_event_log(log: &str) -> Result<Option<Vec<ParsedEvent>>, IndexerError> {
+    let Some(payload) = log.strip_prefix(EVENT_LOG_PREFIX) else {
+        return Ok(None);
+    };
+    let log: EventLog = serde_json::from_str(payload)?;
+    if log.standard != EVENT_STANDARD {
+        return Ok(None);
+    }
+    // Only a name this build does not know decodes without its data
+    let unknown = matches!(
+        serde_json::from_value(json!({ "event": log.event })),
+        Ok(RegistryEvent::Unknown)
+    );
+    log.data
+        .into_iter()
+        .map(|data| {
+            let event = if unknown {
+                RegistryEvent::Unknown
+            } else {
+                serde_json::from_value(json!({ "event": log.event, "data": data }))?
+            };
+            Ok(ParsedEvent { name: log.event.clone(), version: log.version.clone(), event })
+        })
+        .collect::<Result<Vec<_>, IndexerError>>()
+        .map(Some)
+}
+
+/// Yields the receipt outcomes of one block at a time.
+pub trait BlockSource {
+    /// Outcomes of the next block, or `None` once caught up.
+    fn next_block(&mut self) -> Result<Option<Vec<ReceiptOutcome>>, IndexerError>;
+}
+
+/// The RPC queries `RpcPoller` needs, implemented over your RPC client
+/// (`block` plus `EXPERIMENTAL_tx_status` or `chunk`, for instance).
+pub trait RpcTransport {
+    fn final_block_height(&mut self) -> Result<u64, IndexerError>;
+    /// Outcomes executed in the block at `height`, `None` if it was skipped.
+    fn block_outcomes(&mut self, height: u64) -> Result<Option<Vec<ReceiptOutcome>>, IndexerError>;
+}
+
+/// Walks final blocks one by one from `start_height` over RPC.
+pub struct RpcPoller<T> {
+    transport: T,
+    next_height: u64,
+}
+
+impl<T: RpcTransport> RpcPoller<T> {
+    pub fn new(transport: T, start_height: u64) -> Self {
+        Self { transport, next_height: start_height }
+    }
+
+    /// Height of the next block to fetch; persist it to resume later.
+    pub fn next_height(&self) -> u64 {
+        self.next_height
+    }
+}
+
+impl<T: RpcTransport> BlockSource for RpcPoller<T> {
+    fn next_block(&mut self) -> Result<Option<Vec<ReceiptOutcome>>, IndexerError> {
+        if self.next_height > self.transport.final_block_height()? {
+            return Ok(None);
+        }
+        let outcomes = self.transport.block_outcomes(self.next_height)?.unwrap_or_default();
+        self.next_height += 1;
+        Ok(Some(outcomes))
+    }
+}
+
+/// Blocks handed over by another task, typically one reading
+/// `near-lake-framework`'s streamer and converting each message's receipt
+/// execution outcomes into `ReceiptOutcome`s.
+pub struct ChannelSource {
+    receiver: Receiver<Vec<ReceiptOutcome>>,
+}
+
+impl ChannelSource {
+    pub fn new(receiver: Receiver<Vec<ReceiptOutcome>>) -> Self {
+        Self { receiver }
+    }
+}
+
+impl BlockSource for ChannelSource {
+    fn next_block(&mut self) -> Result<Option<Vec<ReceiptOutcome>>, IndexerError> {
+        match self.receiver.try_recv() {
+            Ok(outcomes) => Ok(Some(outcomes)),
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(TryRecvError::Disconnected) => Err(IndexerError::Source("streamer disconnected".to_string())),
+        }
+    }
+}
+
+/// Destination for decoded events.
+pub trait EventSink {
+    fn handle(&mut self, event: &IndexedEvent) -> Result<(), IndexerError>;
+}
+
+/// Writes each event as a JSON line.
+pub struct StdoutSink<W = io::Stdout> {
+    out: W,
+}
+
+impl StdoutSink {
+    pub fn new() -> Self {
+        Self { out: io::stdout() }
+    }
+}
+
+impl Default for StdoutSink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: Write> StdoutSink<W> {
+    pub fn with_writer(out: W) -> Self {
+        Self { out }
+    }
+}
+
+impl<W: Write> EventSink for StdoutSink<W> {
+    fn handle(&mut self, event: &IndexedEvent) -> Result<(), IndexerError> {
+        let line = serde_json::to_string(event)?;
+        writeln!(self.out, "{}", line).map_err(|err| IndexerError::Sink(err.to_string()))
+    }
+}
+
+/// Runs parameterized SQL; implement over `postgres` or `tokio-postgres`.
+/// Parameters are passed as text.
+pub trait SqlExecutor {
+    fn execute(&mut self, statement: &str, params: &[&str]) -> Result<(), IndexerError>;
+}
+
+/// Table `PostgresSink` writes to; run it once before indexing.
+pub const POSTGRES_SCHEMA: &str = "CREATE TABLE IF NOT EXISTS registry_events (
+    block_height BIGINT NOT NULL,
+    block_timestamp BIGINT NOT NULL,
+    receipt_id TEXT NOT NULL,
+    event_index INT NOT NULL,
+    event TEXT NOT NULL,
+    version TEXT NOT NULL,
+    data JSONB NOT NULL,
+    PRIMARY KEY (receipt_id, event_index)
+)";
+
+const POSTGRES_INSERT: &str = "INSERT INTO registry_events
+    (block_height, block_timestamp, receipt_id, event_index, event, version, data)
+    VALUES ($1::text::bigint, $2::text::bigint, $3, $4::text::int, $5, $6, $7::text::jsonb)
+    ON CONFLICT DO NOTHING";
+
+/// Inserts one row per event into `registry_events`. Re-indexing a block
+/// is harmless; rows already present are skipped.
+pub struct PostgresSink<E> {
+    executor: E,
+}
+
+impl<E: SqlExecutor> PostgresSink<E> {
+    pub fn new(executor: E) -> Self {
+        Self { executor }
+    }
+}
+
+impl<E: SqlExecutor> EventSink for PostgresSink<E> {
+    fn handle(&mut self, event: &IndexedEvent) -> Result<(), IndexerError> {
+        let data = serde_json::to_value(&event.event)?
+            .get("data")
+            .cloned()
+            .unwrap_or(Value::Null);
+        self.executor.execute(
+            POSTGRES_INSERT,
+            &[
+                &event.block_height.to_string(),
+                &event.block_timestamp.to_string(),
+                &event.receipt_id,
+                &event.index.to_string(),
+                &event.name,
+                &event.version,
+                &data.to_string(),
+            ],
+        )
+    }
+}
+
+/// Feeds the registry's events from `source` into `sink`.
+pub struct Indexer<S, K> {
+    pub contract_id: AccountId,
+    pub source: S,
+    pub sink: K,
+}
+
+impl<S: BlockSource, K: EventSink> Indexer<S, K> {
+    pub fn new(contract_id: AccountId, source: S, sink: K) -> Self {
+        Self { contract_id, source, sink }
+    }
+
+    /// Indexes one block. Returns `false` when the source is caught up, so
+    /// the caller can sleep before polling again.
+    pub fn run_once(&mut self) -> Result<bool, IndexerError> {
+        let Some(outcomes) = self.source.next_block()? else {
+            return Ok(false);
+        };
+        for outcome in outcomes.iter().filter(|outcome| outcome.executor_id == self.contract_id) {
+            let mut index = 0;
+            for log in &outcome.logs {
+                for parsed in parse_event_log(log)?.unwrap_or_default() {
+                    self.sink.handle(&IndexedEvent {
+                        block_height: outcome.block_height,
+                        block_timestamp: outcome.block_timestamp,
+                        receipt_id: outcome.receipt_id.clone(),
+                        index,
+                        name: parsed.name,
+                        version: parsed.version,
+                        event: parsed.event,
+                    })?;
+                    index += 1;
+                }
+            }
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    fn outcome(executor_id: &str, logs: &[&str]) -> ReceiptOutcome {
+        ReceiptOutcome {
+            block_height: 7,
+            block_timestamp: 1_000,
+            receipt_id: "receipt".to_string(),
+            executor_id: executor_id.parse().unwrap(),
+            logs: logs.iter().map(|log| log.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_event_log() {
+        let log = r#"EVENT_JSON:{"standard":"iaip","version":"1.0.0","event":"stake_withdrawn","data":[{"account_id":"bob.near","amount":"50"}]}"#;
+        let events = parse_event_log(log).unwrap().unwrap();
+        assert_eq!(
+            events[0].event,
+            RegistryEvent::StakeWithdrawn(AmountMoved { account_id: "bob.near".parse().unwrap(), amount: 50 })
+        );
+
+        let unknown = r#"EVENT_JSON:{"standard":"iaip","version":"1.0.0","event":"future_event","data":[{"x":1}]}"#;
+        assert_eq!(parse_event_log(unknown).unwrap().unwrap()[0].event, RegistryEvent::Unknown);
+        assert!(parse_event_log("Refunding 10 to bob.near").unwrap().is_none());
+        assert!(parse_event_log(r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_transfer","data":[]}"#)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_indexer_keeps_registry_events_only() {
+        let (sender, receiver) = mpsc::channel();
+        let paused = r#"EVENT_JSON:{"standard":"iaip","version":"1.0.0","event":"registry_paused","data":[{"by":"owner.near"}]}"#;
+        sender.send(vec![outcome("registry.near", &[paused, "plain log", paused]), outcome("other.near", &[paused])]).unwrap();
+
+        let mut indexer = Indexer::new("registry.near".parse().unwrap(), ChannelSource::new(receiver), StdoutSink::with_writer(Vec::new()));
+        assert!(indexer.run_once().unwrap());
+        assert!(!indexer.run_once().unwrap());
+
+        let written = String::from_utf8(indexer.sink.out).unwrap();
+        let lines: Vec<Value> = written.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1]["index"], 1);
+        assert_eq!(lines[1]["event"], "registry_paused");
+        assert_eq!(lines[1]["data"]["by"], "owner.near");
+    }
+}
@@ -0,0 +1,827 @@
+//! Transport-agnostic client helpers for the IAIP agent registry.
+//!
+//! Builders here produce the method name and JSON arguments for each call,
+//! and `parse_view` decodes view results, so services can use whichever RPC
+//! library they already depend on to actually send them.
+
+#[cfg(feature = "indexer")]
+pub mod indexer;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::json;
+
+pub use intellex_types as types;
+use intellex_types::batch::RegistryAction;
+use intellex_types::curation::FLAG_BOND;
+use intellex_types::disputes::DisputeRuling;
+use intellex_types::social::SocialLinkProof;
+use intellex_types::teams::TeamMetadata;
+use intellex_types::trending::TrendWindow;
+use intellex_types::{AccountId, AgentMetadata, AgentStatus, PricingModel, SortOrder, PROTOCOL_VERSION};
+
+/// Default gas attached to change calls (30 TGas).
+pub const DEFAULT_CALL_GAS: u64 = 30_000_000_000_000;
+
+/// Gas for `ft_transfer_call` deposits (stakes and task funding), which run
+/// the registry's `ft_on_transfer` and the token's resolve step (100 TGas).
+pub const STAKE_CALL_GAS: u64 = 100_000_000_000_000;
+
+/// Gas for `sync_reputation_batch`, enough for the largest batch (300 TGas).
+pub const SYNC_BATCH_CALL_GAS: u64 = 300_000_000_000_000;
+
+/// A read-only call against the registry.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ViewCall {
+    pub contract_id: AccountId,
+    pub method_name: String,
+    pub args: Vec<u8>,
+}
+
+/// A state-changing call against the registry, or against the ITLX token
+/// for `stake`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FunctionCall {
+    pub contract_id: AccountId,
+    pub method_name: String,
+    pub args: Vec<u8>,
+    pub gas: u64,
+    pub deposit: u128,
+}
+
+#[derive(Clone, Debug)]
+pub struct RegistryClient {
+    pub contract_id: AccountId,
+}
+
+/// Decodes the raw bytes returned by a view call.
+pub fn parse_view<T: DeserializeOwned>(result: &[u8]) -> Result<T, serde_json::Error> {
+    serde_json::from_slice(result)
+}
+
+impl RegistryClient {
+    pub fn new(contract_id: AccountId) -> Self {
+        Self { contract_id }
+    }
+
+    fn view(&self, method_name: &str, args: impl Serialize) -> ViewCall {
+        ViewCall {
+            contract_id: self.contract_id.clone(),
+            method_name: method_name.to_string(),
+            args: serde_json::to_vec(&args).expect("view args serialize"),
+        }
+    }
+
+    fn call(&self, method_name: &str, args: impl Serialize) -> FunctionCall {
+        FunctionCall {
+            contract_id: self.contract_id.clone(),
+            method_name: method_name.to_string(),
+            args: serde_json::to_vec(&args).expect("call args serialize"),
+            gas: DEFAULT_CALL_GAS,
+            deposit: 0,
+        }
+    }
+
+    /// Registration must pay for its storage: set `deposit` on the returned
+    /// call, or fund the account with `storage_deposit` beforehand.
+    pub fn register_agent(&self, metadata: &AgentMetadata) -> FunctionCall {
+        self.call("register_agent", json!({ "metadata": metadata }))
+    }
+
+    /// `register_agent` redeeming an invite code, required while the
+    /// registry is invite-only. Another agent's referral code also waives
+    /// the ITLX stake minimum.
+    pub fn register_agent_with_invite(&self, metadata: &AgentMetadata, invite_code: &str) -> FunctionCall {
+        self.call("register_agent", json!({ "metadata": metadata, "invite_code": invite_code }))
+    }
+
+    /// Adds a referral code by its hex SHA-256; hand the code itself to the
+    /// invitee.
+    pub fn create_invite(
+        &self,
+        code_hash: &str,
+        expires_at: Option<u64>,
+        bound_to: Option<&AccountId>,
+        storage_deposit: u128,
+    ) -> FunctionCall {
+        let args = json!({ "code_hash": code_hash, "expires_at": expires_at, "bound_to": bound_to });
+        FunctionCall { deposit: storage_deposit, ..self.call("create_invite", args) }
+    }
+
+    pub fn revoke_invite_code(&self, code_hash: &str) -> FunctionCall {
+        self.call("revoke_invite_code", json!({ "code_hash": code_hash }))
+    }
+
+    /// `ft_transfer_call` on the ITLX token that stakes `amount` yocto-ITLX
+    /// with the registry. Sent to `token_contract_id` with 1 yoctoNEAR.
+    pub fn stake(&self, token_contract_id: &AccountId, amount: u128) -> FunctionCall {
+        FunctionCall {
+            contract_id: token_contract_id.clone(),
+            method_name: "ft_transfer_call".to_string(),
+            args: serde_json::to_vec(&json!({
+                "receiver_id": self.contract_id,
+                "amount": amount.to_string(),
+                "msg": "stake",
+            }))
+            .expect("call args serialize"),
+            gas: STAKE_CALL_GAS,
+            deposit: 1,
+        }
+    }
+
+    /// Adds `amount` yoctoNEAR to the caller's storage balance.
+    pub fn storage_deposit(&self, amount: u128) -> FunctionCall {
+        FunctionCall { deposit: amount, ..self.call("storage_deposit", json!({})) }
+    }
+
+    /// Withdraws `amount` of the available storage balance, or all of it.
+    pub fn storage_withdraw(&self, amount: Option<u128>) -> FunctionCall {
+        let args = json!({ "amount": amount.map(|amount| amount.to_string()) });
+        FunctionCall { deposit: 1, ..self.call("storage_withdraw", args) }
+    }
+
+    /// `ft_transfer_call` on the ITLX token that adds `amount` yocto-ITLX to
+    /// the escrowed reward of open task `task_id`. Creator only.
+    pub fn fund_task(&self, token_contract_id: &AccountId, task_id: u64, amount: u128) -> FunctionCall {
+        FunctionCall {
+            contract_id: token_contract_id.clone(),
+            method_name: "ft_transfer_call".to_string(),
+            args: serde_json::to_vec(&json!({
+                "receiver_id": self.contract_id,
+                "amount": amount.to_string(),
+                "msg": format!("fund_task:{}", task_id),
+            }))
+            .expect("call args serialize"),
+            gas: STAKE_CALL_GAS,
+            deposit: 1,
+        }
+    }
+
+    pub fn unstake(&self, amount: u128) -> FunctionCall {
+        self.call("unstake", json!({ "amount": amount.to_string() }))
+    }
+
+    pub fn withdraw(&self) -> FunctionCall {
+        self.call("withdraw", json!({}))
+    }
+
+    pub fn update_agent_metadata(&self, metadata: &AgentMetadata) -> FunctionCall {
+        self.call("update_agent_metadata", json!({ "metadata": metadata }))
+    }
+
+    /// `update_agent_metadata` on behalf of `agent_id`; the caller must be
+    /// one of its operators.
+    pub fn update_agent_metadata_as_operator(&self, agent_id: &AccountId, metadata: &AgentMetadata) -> FunctionCall {
+        let args = json!({ "agent_id": agent_id, "metadata": metadata });
+        self.call("update_agent_metadata_as_operator", args)
+    }
+
+    pub fn add_operator(&self, account_id: &AccountId, storage_deposit: u128) -> FunctionCall {
+        FunctionCall { deposit: storage_deposit, ..self.call("add_operator", json!({ "account_id": account_id })) }
+    }
+
+    pub fn remove_operator(&self, account_id: &AccountId) -> FunctionCall {
+        self.call("remove_operator", json!({ "account_id": account_id }))
+    }
+
+    /// `agent_id` is the agent an operator acts for; `None` for the
+    /// caller's own agent. Likewise for `remove_skill`,
+    /// `set_service_descriptor` and `batch_execute`.
+    pub fn add_skill(&self, skill: &str, agent_id: Option<&AccountId>, storage_deposit: u128) -> FunctionCall {
+        let args = json!({ "skill": skill, "agent_id": agent_id });
+        FunctionCall { deposit: storage_deposit, ..self.call("add_skill", args) }
+    }
+
+    pub fn remove_skill(&self, skill: &str, agent_id: Option<&AccountId>) -> FunctionCall {
+        self.call("remove_skill", json!({ "skill": skill, "agent_id": agent_id }))
+    }
+
+    pub fn set_service_descriptor(
+        &self,
+        endpoint_url: Option<&str>,
+        protocols: &[&str],
+        pricing: Option<&PricingModel>,
+        public_key: Option<&str>,
+        agent_id: Option<&AccountId>,
+        storage_deposit: u128,
+    ) -> FunctionCall {
+        let args = json!({
+            "endpoint_url": endpoint_url,
+            "protocols": protocols,
+            "pricing": pricing,
+            "public_key": public_key,
+            "agent_id": agent_id,
+        });
+        FunctionCall { deposit: storage_deposit, ..self.call("set_service_descriptor", args) }
+    }
+
+    pub fn set_status(&self, status: AgentStatus) -> FunctionCall {
+        self.call("set_status", json!({ "status": status }))
+    }
+
+    pub fn transfer_agent_ownership(&self, new_owner: &AccountId) -> FunctionCall {
+        self.call("transfer_agent_ownership", json!({ "new_owner": new_owner }))
+    }
+
+    /// Takes over `agent_id`. The caller needs the minimum stake, and
+    /// `deposit` should cover the agent's storage unless already deposited.
+    pub fn accept_agent_transfer(&self, agent_id: &AccountId) -> FunctionCall {
+        self.call("accept_agent_transfer", json!({ "agent_id": agent_id }))
+    }
+
+    /// Posts a task, attaching `reward` plus `storage_deposit` yoctoNEAR.
+    pub fn create_task(&self, description: &str, required_skills: &[String], reward: u128, storage_deposit: u128) -> FunctionCall {
+        let args = json!({
+            "description": description,
+            "required_skills": required_skills,
+            "reward": reward.to_string(),
+        });
+        FunctionCall { deposit: reward + storage_deposit, ..self.call("create_task", args) }
+    }
+
+    pub fn assign_task(&self, task_id: u64, agent_id: &AccountId) -> FunctionCall {
+        self.call("assign_task", json!({ "task_id": task_id, "agent_id": agent_id }))
+    }
+
+    pub fn assign_task_to_team(&self, task_id: u64, team_id: u64) -> FunctionCall {
+        self.call("assign_task_to_team", json!({ "task_id": task_id, "team_id": team_id }))
+    }
+
+    pub fn create_team(&self, name: &str, metadata: &TeamMetadata, storage_deposit: u128) -> FunctionCall {
+        let args = json!({ "name": name, "metadata": metadata });
+        FunctionCall { deposit: storage_deposit, ..self.call("create_team", args) }
+    }
+
+    pub fn add_member(&self, team_id: u64, agent_id: &AccountId, storage_deposit: u128) -> FunctionCall {
+        let args = json!({ "team_id": team_id, "agent_id": agent_id });
+        FunctionCall { deposit: storage_deposit, ..self.call("add_member", args) }
+    }
+
+    pub fn accept_team_invite(&self, team_id: u64, storage_deposit: u128) -> FunctionCall {
+        FunctionCall { deposit: storage_deposit, ..self.call("accept_team_invite", json!({ "team_id": team_id })) }
+    }
+
+    pub fn remove_member(&self, team_id: u64, agent_id: &AccountId) -> FunctionCall {
+        self.call("remove_member", json!({ "team_id": team_id, "agent_id": agent_id }))
+    }
+
+    pub fn submit_result(&self, task_id: u64, details: &str) -> FunctionCall {
+        self.call("submit_result", json!({ "task_id": task_id, "details": details }))
+    }
+
+    pub fn approve_task(&self, task_id: u64) -> FunctionCall {
+        self.call("approve_task", json!({ "task_id": task_id }))
+    }
+
+    pub fn compact_settled_tasks(&self, limit: u64) -> FunctionCall {
+        self.call("compact_settled_tasks", json!({ "limit": limit }))
+    }
+
+    pub fn dispute_task(&self, task_id: u64, evidence: &str) -> FunctionCall {
+        self.call("dispute_task", json!({ "task_id": task_id, "evidence": evidence }))
+    }
+
+    /// Arbiter only; for a DAO arbiter, use these as the proposal's action.
+    pub fn resolve_dispute(&self, task_id: u64, ruling: &DisputeRuling) -> FunctionCall {
+        self.call("resolve_dispute", json!({ "task_id": task_id, "ruling": ruling }))
+    }
+
+    pub fn settle_expired_dispute(&self, task_id: u64) -> FunctionCall {
+        self.call("settle_expired_dispute", json!({ "task_id": task_id }))
+    }
+
+    /// Attaches `FLAG_BOND` plus `storage_deposit` toward the flag's storage.
+    pub fn flag_listing(&self, agent_id: &AccountId, reason: &str, storage_deposit: u128) -> FunctionCall {
+        FunctionCall {
+            deposit: FLAG_BOND + storage_deposit,
+            ..self.call("flag_listing", json!({ "agent_id": agent_id, "reason": reason }))
+        }
+    }
+
+    /// Arbiter or owner only.
+    pub fn confirm_flag(&self, flag_id: u64) -> FunctionCall {
+        self.call("confirm_flag", json!({ "flag_id": flag_id }))
+    }
+
+    /// Arbiter or owner only.
+    pub fn reject_flag(&self, flag_id: u64) -> FunctionCall {
+        self.call("reject_flag", json!({ "flag_id": flag_id }))
+    }
+
+    /// `ft_transfer_call` on the ITLX token that adds `amount` yocto-ITLX to
+    /// the pool listing flag bounties are paid from.
+    pub fn fund_curation_pool(&self, token_contract_id: &AccountId, amount: u128) -> FunctionCall {
+        FunctionCall {
+            contract_id: token_contract_id.clone(),
+            method_name: "ft_transfer_call".to_string(),
+            args: serde_json::to_vec(&json!({
+                "receiver_id": self.contract_id,
+                "amount": amount.to_string(),
+                "msg": "fund_curation",
+            }))
+            .expect("call args serialize"),
+            gas: STAKE_CALL_GAS,
+            deposit: 1,
+        }
+    }
+
+    /// Attaches `storage_deposit` toward the review's storage.
+    pub fn submit_review(
+        &self,
+        agent_id: &AccountId,
+        task_id: u64,
+        rating: u8,
+        comment: &str,
+        storage_deposit: u128,
+    ) -> FunctionCall {
+        let args = json!({ "agent_id": agent_id, "task_id": task_id, "rating": rating, "comment": comment });
+        FunctionCall { deposit: storage_deposit, ..self.call("submit_review", args) }
+    }
+
+    /// Attaches `storage_deposit` toward the endorsement's storage.
+    pub fn endorse_skill(&self, agent_id: &AccountId, skill: &str, storage_deposit: u128) -> FunctionCall {
+        FunctionCall {
+            deposit: storage_deposit,
+            ..self.call("endorse_skill", json!({ "agent_id": agent_id, "skill": skill }))
+        }
+    }
+
+    pub fn revoke_endorsement(&self, agent_id: &AccountId, skill: &str) -> FunctionCall {
+        self.call("revoke_endorsement", json!({ "agent_id": agent_id, "skill": skill }))
+    }
+
+    /// Attaches `storage_deposit` toward the snapshot's storage.
+    pub fn snapshot_rankings(&self, storage_deposit: u128) -> FunctionCall {
+        FunctionCall { deposit: storage_deposit, ..self.call("snapshot_rankings", json!({})) }
+    }
+
+    /// Profile changes applied atomically; attach `storage_deposit` toward
+    /// any growth.
+    pub fn batch_execute(&self, actions: &[RegistryAction], agent_id: Option<&AccountId>, storage_deposit: u128) -> FunctionCall {
+        let args = json!({ "actions": actions, "agent_id": agent_id });
+        FunctionCall { deposit: storage_deposit, ..self.call("batch_execute", args) }
+    }
+
+    /// Moves the caller's agent and its token to `receiver_id`; only while
+    /// agent tokens are transferable.
+    pub fn nft_transfer(&self, receiver_id: &AccountId, token_id: &str, memo: Option<&str>) -> FunctionCall {
+        let args = json!({ "receiver_id": receiver_id, "token_id": token_id, "memo": memo });
+        FunctionCall { deposit: 1, ..self.call("nft_transfer", args) }
+    }
+
+    /// Issues credential `name` to `agent_id`; the caller must be the
+    /// registry owner or an approved verifier.
+    pub fn issue_credential(&self, agent_id: &AccountId, name: &str, expires_at: Option<u64>, storage_deposit: u128) -> FunctionCall {
+        let args = json!({ "agent_id": agent_id, "name": name, "expires_at": expires_at });
+        FunctionCall { deposit: storage_deposit, ..self.call("issue_credential", args) }
+    }
+
+    pub fn revoke_credential(&self, agent_id: &AccountId, name: &str) -> FunctionCall {
+        self.call("revoke_credential", json!({ "agent_id": agent_id, "name": name }))
+    }
+
+    pub fn deregister_agent(&self) -> FunctionCall {
+        self.call("deregister_agent", json!({}))
+    }
+
+    pub fn reserve_handle(&self, handle: &str) -> FunctionCall {
+        self.call("reserve_handle", json!({ "handle": handle }))
+    }
+
+    pub fn claim_handle(&self, handle: &str) -> FunctionCall {
+        self.call("claim_handle", json!({ "handle": handle }))
+    }
+
+    pub fn link_social_profile(&self, proof: &SocialLinkProof) -> FunctionCall {
+        self.call("link_social_profile", json!({ "proof": proof }))
+    }
+
+    /// `entry_hash` is hex(sha256("{requester}:{agent_id}")); see
+    /// `types::requester_lists::blocklist_entry_preimage`.
+    pub fn block_agent(&self, entry_hash: &str) -> FunctionCall {
+        self.call("block_agent", json!({ "entry_hash": entry_hash }))
+    }
+
+    pub fn unblock_agent(&self, entry_hash: &str) -> FunctionCall {
+        self.call("unblock_agent", json!({ "entry_hash": entry_hash }))
+    }
+
+    pub fn watch_agent(&self, agent_id: &AccountId) -> FunctionCall {
+        self.call("watch_agent", json!({ "agent_id": agent_id }))
+    }
+
+    pub fn unwatch_agent(&self, agent_id: &AccountId) -> FunctionCall {
+        self.call("unwatch_agent", json!({ "agent_id": agent_id }))
+    }
+
+    pub fn anchor_audit_log(&self, period_id: u64, merkle_root: &str) -> FunctionCall {
+        self.call(
+            "anchor_audit_log",
+            json!({ "period_id": period_id, "merkle_root": merkle_root }),
+        )
+    }
+
+    /// Refreshes up to 20 agents' reputation from the reputation contract.
+    pub fn sync_reputation_batch(&self, agent_ids: &[AccountId]) -> FunctionCall {
+        FunctionCall {
+            gas: SYNC_BATCH_CALL_GAS,
+            ..self.call("sync_reputation_batch", json!({ "agent_ids": agent_ids }))
+        }
+    }
+
+    /// Re-sends `initialize_agent` for an agent whose first one failed.
+    pub fn retry_initialization(&self, agent_id: &AccountId) -> FunctionCall {
+        self.call("retry_initialization", json!({ "agent_id": agent_id }))
+    }
+
+    pub fn get_pending_initializations(&self, cursor: Option<&str>, limit: Option<u64>) -> ViewCall {
+        self.view("get_pending_initializations", json!({ "cursor": cursor, "limit": limit }))
+    }
+
+    /// Re-sends an agent's failed `transfer_agent` or `remove_agent`.
+    pub fn retry_reputation_call(&self, agent_id: &AccountId) -> FunctionCall {
+        self.call("retry_reputation_call", json!({ "agent_id": agent_id }))
+    }
+
+    pub fn get_pending_reputation_call(&self, agent_id: &AccountId) -> ViewCall {
+        self.view("get_pending_reputation_call", json!({ "agent_id": agent_id }))
+    }
+
+    pub fn get_agent(&self, agent_id: &AccountId) -> ViewCall {
+        self.view("get_agent", json!({ "agent_id": agent_id }))
+    }
+
+    pub fn get_stake(&self, account_id: &AccountId) -> ViewCall {
+        self.view("get_stake", json!({ "account_id": account_id }))
+    }
+
+    pub fn get_team(&self, team_id: u64) -> ViewCall {
+        self.view("get_team", json!({ "team_id": team_id }))
+    }
+
+    pub fn get_team_agents(&self, team_id: u64) -> ViewCall {
+        self.view("get_team_agents", json!({ "team_id": team_id }))
+    }
+
+    pub fn get_agent_teams(&self, agent_id: &AccountId) -> ViewCall {
+        self.view("get_agent_teams", json!({ "agent_id": agent_id }))
+    }
+
+    pub fn get_task(&self, task_id: u64) -> ViewCall {
+        self.view("get_task", json!({ "task_id": task_id }))
+    }
+
+    pub fn get_archived_task(&self, task_id: u64) -> ViewCall {
+        self.view("get_archived_task", json!({ "task_id": task_id }))
+    }
+
+    pub fn get_compaction_backlog(&self) -> ViewCall {
+        self.view("get_compaction_backlog", json!({}))
+    }
+
+    pub fn get_dispute(&self, task_id: u64) -> ViewCall {
+        self.view("get_dispute", json!({ "task_id": task_id }))
+    }
+
+    pub fn get_ranking_snapshot(&self, epoch: u64) -> ViewCall {
+        self.view("get_ranking_snapshot", json!({ "epoch": epoch }))
+    }
+
+    pub fn get_pending_flag(&self, agent_id: &AccountId) -> ViewCall {
+        self.view("get_pending_flag", json!({ "agent_id": agent_id }))
+    }
+
+    pub fn storage_balance_of(&self, account_id: &AccountId) -> ViewCall {
+        self.view("storage_balance_of", json!({ "account_id": account_id }))
+    }
+
+    /// Bytes and deposit `update_agent_metadata(metadata)` would need; attach
+    /// `required_deposit` to the update.
+    pub fn estimate_update_cost(&self, agent_id: &AccountId, metadata: &AgentMetadata) -> ViewCall {
+        self.view("estimate_update_cost", json!({ "agent_id": agent_id, "metadata": metadata }))
+    }
+
+    pub fn get_referrals(&self, agent_id: &AccountId, cursor: Option<&str>, limit: Option<u64>) -> ViewCall {
+        self.view("get_referrals", json!({ "agent_id": agent_id, "cursor": cursor, "limit": limit }))
+    }
+
+    pub fn get_agent_reviews(&self, agent_id: &AccountId, cursor: Option<&str>, limit: Option<u64>) -> ViewCall {
+        self.view(
+            "get_agent_reviews",
+            json!({ "agent_id": agent_id, "cursor": cursor, "limit": limit }),
+        )
+    }
+
+    pub fn get_skill_endorsements(&self, agent_id: &AccountId) -> ViewCall {
+        self.view("get_skill_endorsements", json!({ "agent_id": agent_id }))
+    }
+
+    pub fn get_slash_history(&self, agent_id: &AccountId) -> ViewCall {
+        self.view("get_slash_history", json!({ "agent_id": agent_id }))
+    }
+
+    pub fn get_social_link_nonce(&self, agent_id: &AccountId, social_account_id: &AccountId) -> ViewCall {
+        self.view(
+            "get_social_link_nonce",
+            json!({ "agent_id": agent_id, "social_account_id": social_account_id }),
+        )
+    }
+
+    pub fn get_all_skills(&self, from_index: u64, limit: Option<u64>) -> ViewCall {
+        self.view("get_all_skills", json!({ "from_index": from_index, "limit": limit }))
+    }
+
+    pub fn normalize_skill(&self, skill: &str) -> ViewCall {
+        self.view("normalize_skill", json!({ "skill": skill }))
+    }
+
+    pub fn get_mirrored_methods(&self) -> ViewCall {
+        self.view("get_mirrored_methods", json!({}))
+    }
+
+    pub fn get_trending_skills(&self, window: TrendWindow, limit: Option<u64>) -> ViewCall {
+        self.view("get_trending_skills", json!({ "window": window, "limit": limit }))
+    }
+
+    pub fn get_agents_with_stale_reputation(&self, max_age_ns: u64, limit: Option<u64>) -> ViewCall {
+        self.view(
+            "get_agents_with_stale_reputation",
+            json!({ "max_age_ns": max_age_ns, "limit": limit }),
+        )
+    }
+
+    pub fn get_agent_reputation_breakdown(&self, agent_id: &AccountId) -> ViewCall {
+        self.view("get_agent_reputation_breakdown", json!({ "agent_id": agent_id }))
+    }
+
+    pub fn get_reputation_with_ttl(&self, agent_id: &AccountId) -> ViewCall {
+        self.view("get_reputation_with_ttl", json!({ "agent_id": agent_id }))
+    }
+
+    pub fn get_agent_reputation_with_sync_time(&self, agent_id: &AccountId) -> ViewCall {
+        self.view("get_agent_reputation_with_sync_time", json!({ "agent_id": agent_id }))
+    }
+
+    /// Totals, success rate and latest task time over the agent's whole
+    /// history.
+    pub fn get_agent_task_stats(&self, agent_id: &AccountId) -> ViewCall {
+        self.view("get_agent_task_stats", json!({ "agent_id": agent_id }))
+    }
+
+    /// Reputation points between two timestamps, inclusive; `None` leaves
+    /// that end open.
+    pub fn get_agent_reputation_history(
+        &self,
+        agent_id: &AccountId,
+        from_timestamp: Option<u64>,
+        to_timestamp: Option<u64>,
+        cursor: Option<&str>,
+        limit: Option<u64>,
+    ) -> ViewCall {
+        self.view(
+            "get_agent_reputation_history",
+            json!({
+                "agent_id": agent_id,
+                "from_timestamp": from_timestamp,
+                "to_timestamp": to_timestamp,
+                "cursor": cursor,
+                "limit": limit,
+            }),
+        )
+    }
+
+    pub fn get_agent_version(&self, agent_id: &AccountId) -> ViewCall {
+        self.view("get_agent_version", json!({ "agent_id": agent_id }))
+    }
+
+    pub fn get_agents_by_skill(&self, skill: &str) -> ViewCall {
+        self.view("get_agents_by_skill", json!({ "skill": skill }))
+    }
+
+    /// `get_agents_by_skill` without inactive, suspended or retired agents.
+    pub fn get_active_agents_by_skill(&self, skill: &str) -> ViewCall {
+        self.view("get_agents_by_skill", json!({ "skill": skill, "active_only": true }))
+    }
+
+    /// Ranked candidates for a task; see `types::matching::match_score`.
+    pub fn match_agents(&self, required_skills: &[String], min_reputation: u64, limit: u64) -> ViewCall {
+        self.view(
+            "match_agents",
+            json!({ "required_skills": required_skills, "min_reputation": min_reputation, "limit": limit }),
+        )
+    }
+
+    /// Pass `None` for the first page, then each page's `next_cursor`.
+    pub fn search_agents_by_skill(
+        &self,
+        skill: &str,
+        cursor: Option<&str>,
+        limit: u64,
+        sort: Option<SortOrder>,
+        require_system_skills: &[String],
+    ) -> ViewCall {
+        self.view(
+            "search_agents_by_skill",
+            json!({
+                "skill": skill,
+                "cursor": cursor,
+                "limit": limit,
+                "sort": sort,
+                "require_system_skills": require_system_skills,
+            }),
+        )
+    }
+
+    pub fn search_agents_for_requester(
+        &self,
+        requester: &AccountId,
+        skill: &str,
+        cursor: Option<&str>,
+        limit: u64,
+        sort: Option<SortOrder>,
+        require_system_skills: &[String],
+    ) -> ViewCall {
+        self.view(
+            "search_agents_for_requester",
+            json!({
+                "requester": requester,
+                "skill": skill,
+                "cursor": cursor,
+                "limit": limit,
+                "sort": sort,
+                "require_system_skills": require_system_skills,
+            }),
+        )
+    }
+
+    pub fn get_agents_by_skill_version(&self, skill: &str, semver_req: &str) -> ViewCall {
+        self.view(
+            "get_agents_by_skill_version",
+            json!({ "skill": skill, "semver_req": semver_req }),
+        )
+    }
+
+    pub fn get_agent_by_handle(&self, handle: &str) -> ViewCall {
+        self.view("get_agent_by_handle", json!({ "handle": handle }))
+    }
+
+    pub fn get_top_agents(&self, limit: Option<u64>) -> ViewCall {
+        self.view("get_top_agents", json!({ "limit": limit }))
+    }
+
+    pub fn get_top_agents_by_skill(&self, skill: &str, limit: Option<u64>) -> ViewCall {
+        self.view("get_top_agents_by_skill", json!({ "skill": skill, "limit": limit }))
+    }
+
+    pub fn get_agent_credentials(&self, agent_id: &AccountId, include_inactive: bool) -> ViewCall {
+        self.view("get_agent_credentials", json!({ "agent_id": agent_id, "include_inactive": include_inactive }))
+    }
+
+    pub fn has_credential(&self, agent_id: &AccountId, name: &str) -> ViewCall {
+        self.view("has_credential", json!({ "agent_id": agent_id, "name": name }))
+    }
+
+    pub fn nft_token(&self, token_id: &str) -> ViewCall {
+        self.view("nft_token", json!({ "token_id": token_id }))
+    }
+
+    pub fn nft_tokens_for_owner(&self, account_id: &AccountId) -> ViewCall {
+        self.view("nft_tokens_for_owner", json!({ "account_id": account_id }))
+    }
+
+    pub fn get_operators(&self, agent_id: &AccountId) -> ViewCall {
+        self.view("get_operators", json!({ "agent_id": agent_id }))
+    }
+
+    pub fn verify_metadata_hash(&self, agent_id: &AccountId, payload: &[u8]) -> ViewCall {
+        self.view("verify_metadata_hash", json!({ "agent_id": agent_id, "payload": payload }))
+    }
+
+    /// Limits `register_agent` and metadata updates check against.
+    pub fn get_registry_config(&self) -> ViewCall {
+        self.view("get_registry_config", json!({}))
+    }
+
+    /// Anti-sybil limits `register_agent` enforces.
+    pub fn get_registration_limits(&self) -> ViewCall {
+        self.view("get_registration_limits", json!({}))
+    }
+
+    /// Gas the registry attaches to its outbound calls.
+    pub fn get_gas_config(&self) -> ViewCall {
+        self.view("get_gas_config", json!({}))
+    }
+
+    pub fn get_task_history_window(&self) -> ViewCall {
+        self.view("get_task_history_window", json!({}))
+    }
+
+    pub fn get_registration_mode(&self) -> ViewCall {
+        self.view("get_registration_mode", json!({}))
+    }
+
+    /// Whether `account_id` may register while the registry is in
+    /// `Allowlist` mode.
+    pub fn is_allowlisted(&self, account_id: &AccountId) -> ViewCall {
+        self.view("is_allowlisted", json!({ "account_id": account_id }))
+    }
+
+    pub fn get_ban(&self, account_id: &AccountId) -> ViewCall {
+        self.view("get_ban", json!({ "account_id": account_id }))
+    }
+
+    pub fn get_registration_bond(&self, account_id: &AccountId) -> ViewCall {
+        self.view("get_registration_bond", json!({ "account_id": account_id }))
+    }
+
+    pub fn claim_registration_bond(&self) -> FunctionCall {
+        self.call("claim_registration_bond", json!({}))
+    }
+
+    pub fn get_registry_stats(&self) -> ViewCall {
+        self.view("get_registry_stats", json!({}))
+    }
+
+    pub fn get_skill_counts(&self, from_index: u64, limit: Option<u64>) -> ViewCall {
+        self.view("get_skill_counts", json!({ "from_index": from_index, "limit": limit }))
+    }
+
+    pub fn get_total_agents(&self) -> ViewCall {
+        self.view("get_total_agents", json!({}))
+    }
+
+    pub fn get_reputation_mode(&self) -> ViewCall {
+        self.view("get_reputation_mode", json!({}))
+    }
+
+    pub fn health_check(&self) -> ViewCall {
+        self.view("health_check", json!({}))
+    }
+
+    pub fn protocol_version(&self) -> ViewCall {
+        self.view("protocol_version", json!({}))
+    }
+
+    /// View that fails with "Client too old for method ..." if this client
+    /// build is too old to call `method_name` on the deployed contract.
+    pub fn check_client_version(&self, method_name: &str) -> ViewCall {
+        self.view(
+            "check_client_version",
+            json!({ "method_name": method_name, "client_version": PROTOCOL_VERSION }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use intellex_types::Agent;
+
+    fn client() -> RegistryClient {
+        RegistryClient::new("registry.near".parse().unwrap())
+    }
+
+    #[test]
+    fn test_call_args_are_named_json_objects() {
+        let call = client().get_agent(&"alice.near".parse().unwrap());
+        assert_eq!(call.method_name, "get_agent");
+        assert_eq!(call.args, br#"{"agent_id":"alice.near"}"#.to_vec());
+
+        let call = client().register_agent(&AgentMetadata {
+            name: "Agent".to_string(),
+            description: String::new(),
+            skills: vec!["rust".to_string()],
+            purpose: String::new(),
+            ..Default::default()
+        });
+        let args: serde_json::Value = serde_json::from_slice(&call.args).unwrap();
+        assert_eq!(args["metadata"]["skills"][0], "rust");
+        assert_eq!(call.gas, DEFAULT_CALL_GAS);
+    }
+
+    #[test]
+    fn test_stake_targets_token_contract() {
+        let call = client().stake(&"itlx.token.near".parse().unwrap(), 5);
+        assert_eq!(call.contract_id.as_str(), "itlx.token.near");
+        assert_eq!(call.method_name, "ft_transfer_call");
+        assert_eq!(call.deposit, 1);
+        let args: serde_json::Value = serde_json::from_slice(&call.args).unwrap();
+        assert_eq!(args["receiver_id"], "registry.near");
+        assert_eq!(args["amount"], "5");
+    }
+
+    #[test]
+    fn test_parse_view_result() {
+        let raw = br#"{
+            "owner_id": "alice.near",
+            "metadata": {"name": "A", "description": "", "skills": [], "purpose": ""},
+            "registered_at": 1,
+            "updated_at": 1,
+            "reputation_info": {"reputation": 7, "task_history": [], "reputation_history": [[1, 7]]}
+        }"#;
+        let agent: Option<Agent> = parse_view(raw).unwrap();
+        assert_eq!(agent.unwrap().reputation_info.reputation, 7);
+        let missing: Option<Agent> = parse_view(b"null").unwrap();
+        assert!(missing.is_none());
+    }
+}
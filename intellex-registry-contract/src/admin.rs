@@ -0,0 +1,277 @@
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, require, AccountId};
+use serde_json::json;
+
+use crate::events;
+use crate::tasks::{ProtocolFee, MAX_PROTOCOL_FEE_BPS};
+use crate::{AgentRegistration, AgentRegistrationExt, ReputationMode};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Points the registry at a new reputation contract, switching a local
+    /// reputation registry to `ReputationMode::External`. Owner only.
+    pub fn set_reputation_contract(&mut self, reputation_contract_id: AccountId) {
+        self.assert_owner();
+        let old_reputation_contract_id = self.reputation_contract().cloned();
+        self.reputation_mode = ReputationMode::External(reputation_contract_id.clone());
+        events::emit(
+            "reputation_contract_changed",
+            json!({ "old": old_reputation_contract_id, "new": reputation_contract_id }),
+        );
+    }
+
+    /// Sets the share of task rewards sent to `treasury_id` on approval.
+    /// Applies to tasks created afterwards. Owner only.
+    pub fn set_protocol_fee(&mut self, fee_bps: u16, treasury_id: AccountId) {
+        self.assert_owner();
+        require!(fee_bps <= MAX_PROTOCOL_FEE_BPS, "Protocol fee cannot exceed 1000 basis points");
+        self.protocol_fee_bps = fee_bps;
+        self.treasury_id = treasury_id;
+        events::emit(
+            "protocol_fee_changed",
+            json!({ "fee_bps": fee_bps, "treasury_id": self.treasury_id }),
+        );
+    }
+
+    /// Hands dispute resolution to `arbiter_id`, a single account or a DAO
+    /// contract. Owner only.
+    pub fn set_arbiter(&mut self, arbiter_id: AccountId) {
+        self.assert_owner();
+        self.arbiter_id = arbiter_id;
+        events::emit("arbiter_changed", json!({ "arbiter_id": self.arbiter_id }));
+    }
+
+    /// Sets the ITLX bounty paid for each confirmed listing flag. Owner only.
+    pub fn set_curation_bounty(&mut self, bounty: U128) {
+        self.assert_owner();
+        self.curation_bounty = bounty.0;
+        events::emit("curation_bounty_changed", json!({ "bounty": bounty }));
+    }
+
+    /// Circuit breaker: freezes every mutating endpoint except these admin
+    /// methods. Reputation pushes are held for replay. Owner only.
+    pub fn pause(&mut self) {
+        self.assert_owner();
+        require!(!self.paused, "Registry is already paused");
+        self.paused = true;
+        events::emit("registry_paused", json!({ "by": self.owner_id }));
+    }
+
+    pub fn unpause(&mut self) {
+        self.assert_owner();
+        require!(self.paused, "Registry is not paused");
+        self.paused = false;
+        events::emit("registry_unpaused", json!({ "by": self.owner_id }));
+    }
+
+    /// Stops new registrations; existing agents are unaffected. Owner only.
+    pub fn pause_registration(&mut self) {
+        self.assert_owner();
+        require!(!self.registration_paused, "Registration is already paused");
+        self.registration_paused = true;
+        events::emit("registration_paused", json!({ "by": self.owner_id }));
+    }
+
+    pub fn resume_registration(&mut self) {
+        self.assert_owner();
+        require!(self.registration_paused, "Registration is not paused");
+        self.registration_paused = false;
+        events::emit("registration_resumed", json!({ "by": self.owner_id }));
+    }
+
+    /// Holds incoming reputation pushes instead of applying them; see
+    /// `replay_held_reputation_updates`. Owner only.
+    pub fn pause_reputation_updates(&mut self) {
+        self.assert_owner();
+        require!(!self.reputation_updates_paused, "Reputation updates are already paused");
+        self.reputation_updates_paused = true;
+        events::emit("reputation_updates_paused", json!({ "by": self.owner_id }));
+    }
+
+    pub fn resume_reputation_updates(&mut self) {
+        self.assert_owner();
+        require!(self.reputation_updates_paused, "Reputation updates are not paused");
+        self.reputation_updates_paused = false;
+        events::emit(
+            "reputation_updates_resumed",
+            json!({ "by": self.owner_id, "held_updates": self.get_held_reputation_update_count() }),
+        );
+    }
+
+    /// First step of an ownership transfer; `new_owner_id` must call
+    /// `accept_ownership`. Proposing again replaces the pending owner.
+    pub fn propose_owner(&mut self, new_owner_id: AccountId) {
+        self.assert_owner();
+        require!(new_owner_id != self.owner_id, "Account is already the owner");
+        self.pending_owner_id = Some(new_owner_id.clone());
+        events::emit(
+            "ownership_proposed",
+            json!({ "owner_id": self.owner_id, "pending_owner_id": new_owner_id }),
+        );
+    }
+
+    pub fn cancel_owner_proposal(&mut self) {
+        self.assert_owner();
+        require!(self.pending_owner_id.take().is_some(), "No ownership transfer pending");
+        events::emit("ownership_proposal_cancelled", json!({ "owner_id": self.owner_id }));
+    }
+
+    pub fn accept_ownership(&mut self) {
+        let caller = env::predecessor_account_id();
+        require!(
+            self.pending_owner_id.as_ref() == Some(&caller),
+            "Only the proposed owner can accept ownership"
+        );
+        self.pending_owner_id = None;
+        let old_owner_id = std::mem::replace(&mut self.owner_id, caller);
+        events::emit(
+            "ownership_transferred",
+            json!({ "old_owner_id": old_owner_id, "new_owner_id": self.owner_id }),
+        );
+    }
+
+    pub fn get_owner(&self) -> AccountId {
+        self.owner_id.clone()
+    }
+
+    pub fn get_pending_owner(&self) -> Option<AccountId> {
+        self.pending_owner_id.clone()
+    }
+
+    /// `None` when reputation is computed locally.
+    pub fn get_reputation_contract(&self) -> Option<AccountId> {
+        self.reputation_contract().cloned()
+    }
+
+    pub fn get_reputation_mode(&self) -> ReputationMode {
+        self.reputation_mode.clone()
+    }
+
+    pub fn get_protocol_fee(&self) -> ProtocolFee {
+        ProtocolFee { fee_bps: self.protocol_fee_bps, treasury_id: self.treasury_id.clone() }
+    }
+
+    pub fn get_arbiter(&self) -> AccountId {
+        self.arbiter_id.clone()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn is_registration_paused(&self) -> bool {
+        self.registration_paused
+    }
+
+    pub fn are_reputation_updates_paused(&self) -> bool {
+        self.reputation_updates_paused
+    }
+}
+
+impl AgentRegistration {
+    /// Who may slash agents: the reputation contract, or the owner when
+    /// reputation is computed locally.
+    pub(crate) fn is_reputation_authority(&self, account_id: &AccountId) -> bool {
+        match &self.reputation_mode {
+            ReputationMode::External(reputation_contract_id) => account_id == reputation_contract_id,
+            ReputationMode::Local => account_id == &self.owner_id,
+        }
+    }
+
+    pub(crate) fn assert_owner(&self) {
+        require!(
+            env::predecessor_account_id() == self.owner_id,
+            "Only the contract owner can call this method"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use crate::AgentInfo;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn test_two_step_ownership_transfer() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.propose_owner(accounts(2));
+        assert_eq!(contract.get_owner(), accounts(0));
+        assert_eq!(contract.get_pending_owner(), Some(accounts(2)));
+
+        testing_env!(get_context(accounts(2)).build());
+        contract.accept_ownership();
+        assert_eq!(contract.get_owner(), accounts(2));
+        assert_eq!(contract.get_pending_owner(), None);
+
+        contract.set_reputation_contract(accounts(3));
+        assert_eq!(contract.get_reputation_contract(), Some(accounts(3)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the proposed owner can accept ownership")]
+    fn test_accept_ownership_requires_proposal() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.propose_owner(accounts(2));
+
+        testing_env!(get_context(accounts(3)).build());
+        contract.accept_ownership();
+    }
+
+    #[test]
+    #[should_panic(expected = "Registration is paused")]
+    fn test_paused_registration_rejects_agents() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.pause_registration();
+        assert!(contract.is_registration_paused());
+
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        register(&mut contract, &mut context, sample_metadata());
+    }
+
+    #[test]
+    fn test_pause_freezes_mutations_and_holds_reputation() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+
+        testing_env!(get_context(accounts(0)).build());
+        contract.pause();
+        let info = AgentInfo { reputation: 7, task_history: Vec::new(), reputation_history: Vec::new() };
+        contract.update_agent_reputation(accounts(1), info, String::new(), None);
+        assert_eq!(contract.get_held_reputation_update_count(), 1);
+
+        contract.unpause();
+        contract.replay_held_reputation_updates(None);
+        assert_eq!(contract.get_agent_reputation(&accounts(1)), Some(7));
+    }
+
+    #[test]
+    #[should_panic(expected = "Registry is paused")]
+    fn test_paused_registry_rejects_mutations() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+
+        testing_env!(get_context(accounts(0)).build());
+        contract.pause();
+        testing_env!(context.build());
+        contract.update_agent_metadata(sample_metadata());
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the contract owner can call this method")]
+    fn test_governance_setters_are_owner_only() {
+        testing_env!(get_context(accounts(1)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.set_reputation_contract(accounts(1));
+    }
+}
@@ -0,0 +1,223 @@
+use near_sdk::base64::engine::general_purpose::STANDARD;
+use near_sdk::base64::Engine;
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, require, AccountId};
+use serde_json::json;
+
+pub use intellex_types::nft::{
+    NFTContractMetadata, Token, TokenMetadata, NFT_METADATA_SPEC, NFT_NAME, NFT_SYMBOL,
+};
+
+use crate::events;
+use crate::storage::assert_one_yocto;
+use crate::{Agent, AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    pub fn nft_metadata(&self) -> NFTContractMetadata {
+        NFTContractMetadata {
+            spec: NFT_METADATA_SPEC.to_string(),
+            name: NFT_NAME.to_string(),
+            symbol: NFT_SYMBOL.to_string(),
+            icon: None,
+            base_uri: None,
+            reference: None,
+            reference_hash: None,
+        }
+    }
+
+    pub fn nft_token(&self, token_id: String) -> Option<Token> {
+        let agent_id = self.token_agents.get(&token_id.parse::<u64>().ok()?)?;
+        self.agent_token(&agent_id)
+    }
+
+    /// An account holds at most one agent token: the one of its agent.
+    pub fn nft_tokens_for_owner(&self, account_id: AccountId, from_index: Option<U128>, limit: Option<u64>) -> Vec<Token> {
+        if from_index.map_or(0, |index| index.0) > 0 || limit == Some(0) {
+            return Vec::new();
+        }
+        self.agent_token(&account_id).into_iter().collect()
+    }
+
+    pub fn nft_supply_for_owner(&self, account_id: AccountId) -> U128 {
+        U128(self.agent_tokens.contains_key(&account_id) as u128)
+    }
+
+    /// Moves the caller's agent, with its token, to `receiver_id`, like an
+    /// accepted `transfer_agent_ownership`. Only while the owner has made
+    /// agent tokens transferable. The receiver must not be an agent, must
+    /// hold the minimum stake and must have a storage balance covering the
+    /// agent. Requires exactly 1 yoctoNEAR.
+    #[payable]
+    pub fn nft_transfer(&mut self, receiver_id: AccountId, token_id: String, approval_id: Option<u64>, memo: Option<String>) {
+        assert_one_yocto();
+        when_not_paused!(self);
+        require!(self.agent_nft_transferable, "Agent tokens are not transferable");
+        require!(approval_id.is_none(), "Agent tokens have no approvals");
+        let sender_id = env::predecessor_account_id();
+        require!(
+            self.agent_tokens.get(&sender_id).map(|id| id.to_string()) == Some(token_id),
+            "Token is not owned by the caller"
+        );
+        self.pending_agent_transfers.remove(&sender_id);
+        self.move_agent(&sender_id, &receiver_id, memo);
+    }
+
+    /// Lets agent tokens move with `nft_transfer`. Off by default, so tokens
+    /// stay bound to their agent's account. Owner only.
+    pub fn set_agent_nft_transferable(&mut self, transferable: bool) {
+        self.assert_owner();
+        self.agent_nft_transferable = transferable;
+        events::emit("agent_nft_transferable_changed", json!({ "transferable": transferable }));
+    }
+
+    pub fn is_agent_nft_transferable(&self) -> bool {
+        self.agent_nft_transferable
+    }
+
+    /// Mints tokens for agents registered before agent tokens existed.
+    /// Agents that have one are skipped. Owner only.
+    pub fn migrate_agent_tokens(&mut self, agent_ids: Vec<AccountId>) -> u64 {
+        self.assert_owner();
+        let mut minted = 0;
+        for agent_id in agent_ids {
+            if self.agents.contains_key(&agent_id) && !self.agent_tokens.contains_key(&agent_id) {
+                self.mint_agent_token(&agent_id);
+                minted += 1;
+            }
+        }
+        minted
+    }
+}
+
+impl AgentRegistration {
+    /// Storage prefix for a collection of `agent_id`'s that moves to a new
+    /// owner by re-keying its header only. Derived from the agent's token,
+    /// which follows the agent, so an account registering again after
+    /// handing its agent away never reuses the moved collection's keys.
+    /// Agents migrated without a token fall back to their account id.
+    pub(crate) fn agent_collection_prefix(&self, tag: char, agent_id: &AccountId) -> Vec<u8> {
+        match self.agent_tokens.get(agent_id) {
+            Some(token_id) => format!("{}#{}", tag, token_id).into_bytes(),
+            None => format!("{}_{}", tag, agent_id).into_bytes(),
+        }
+    }
+
+    pub(crate) fn mint_agent_token(&mut self, agent_id: &AccountId) {
+        let token_id = self.next_agent_token_id;
+        self.next_agent_token_id += 1;
+        self.agent_tokens.insert(agent_id, &token_id);
+        self.token_agents.insert(&token_id, agent_id);
+        events::emit_nft("nft_mint", json!({ "owner_id": agent_id, "token_ids": [token_id.to_string()] }));
+    }
+
+    pub(crate) fn burn_agent_token(&mut self, agent_id: &AccountId) {
+        let Some(token_id) = self.agent_tokens.remove(agent_id) else {
+            return;
+        };
+        self.token_agents.remove(&token_id);
+        events::emit_nft("nft_burn", json!({ "owner_id": agent_id, "token_ids": [token_id.to_string()] }));
+    }
+
+    pub(crate) fn move_agent_token(&mut self, agent_id: &AccountId, new_owner: &AccountId, memo: Option<String>) {
+        let Some(token_id) = self.agent_tokens.remove(agent_id) else {
+            return;
+        };
+        self.agent_tokens.insert(new_owner, &token_id);
+        self.token_agents.insert(&token_id, new_owner);
+        let mut data = json!({
+            "old_owner_id": agent_id,
+            "new_owner_id": new_owner,
+            "token_ids": [token_id.to_string()],
+        });
+        if let Some(memo) = memo {
+            data["memo"] = json!(memo);
+        }
+        events::emit_nft("nft_transfer", data);
+    }
+
+    fn agent_token(&self, agent_id: &AccountId) -> Option<Token> {
+        let token_id = self.agent_tokens.get(agent_id)?;
+        let agent = self.agents.get(agent_id)?;
+        Some(Token {
+            token_id: token_id.to_string(),
+            owner_id: agent_id.clone(),
+            metadata: Some(token_metadata(&agent)),
+            approved_account_ids: Some(Default::default()),
+        })
+    }
+}
+
+fn token_metadata(agent: &Agent) -> TokenMetadata {
+    TokenMetadata {
+        title: Some(agent.metadata.name.clone()),
+        description: Some(agent.metadata.description.clone()),
+        copies: Some(1),
+        issued_at: Some((agent.registered_at / 1_000_000).to_string()),
+        updated_at: Some((agent.updated_at / 1_000_000).to_string()),
+        extra: Some(json!({ "skills": agent.metadata.skills, "purpose": agent.metadata.purpose }).to_string()),
+        reference: agent.metadata.metadata_uri.clone(),
+        reference_hash: agent.metadata.metadata_hash.map(|hash| STANDARD.encode(hash)),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::staking::MIN_REGISTRATION_STAKE;
+    use crate::tests::{deposit_stake, get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+    use near_sdk::NearToken;
+
+    #[test]
+    fn test_registration_mints_agent_token() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+
+        let token = contract.nft_token("0".to_string()).unwrap();
+        assert_eq!(token.owner_id, accounts(1));
+        let metadata = token.metadata.unwrap();
+        assert_eq!(metadata.title, Some("Test Agent".to_string()));
+        assert_eq!(metadata.extra, Some(r#"{"purpose":"Testing","skills":["Rust"]}"#.to_string()));
+        assert_eq!(contract.nft_tokens_for_owner(accounts(1), None, None).len(), 1);
+
+        contract.deregister_agent();
+        assert_eq!(contract.nft_token("0".to_string()), None);
+        assert_eq!(contract.nft_supply_for_owner(accounts(1)), U128(0));
+    }
+
+    #[test]
+    fn test_transferable_token_moves_agent() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        deposit_stake(&mut contract, accounts(2), MIN_REGISTRATION_STAKE);
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        contract.storage_deposit(None, None);
+
+        testing_env!(get_context(accounts(0)).build());
+        contract.set_agent_nft_transferable(true);
+        testing_env!(get_context(accounts(1)).attached_deposit(NearToken::from_yoctonear(1)).build());
+        contract.nft_transfer(accounts(2), "0".to_string(), None, Some("rotation".to_string()));
+
+        assert_eq!(contract.nft_token("0".to_string()).unwrap().owner_id, accounts(2));
+        assert!(contract.get_agent(&accounts(2)).is_some());
+        assert!(contract.get_agent(&accounts(1)).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Agent tokens are not transferable")]
+    fn test_tokens_bound_by_default() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        testing_env!(context.attached_deposit(NearToken::from_yoctonear(1)).build());
+        contract.nft_transfer(accounts(2), "0".to_string(), None, None);
+    }
+}
@@ -0,0 +1,122 @@
+use near_sdk::{env, near_bindgen, AccountId};
+use serde_json::json;
+
+pub use intellex_types::agent::{PricingModel, AGENT_PROTOCOLS, MAX_ENDPOINT_URL_LENGTH};
+
+use crate::events;
+use crate::{AgentMetadata, AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Replaces the service descriptor of the caller's agent, leaving its
+    /// name, skills and the rest of its metadata as they are. Lets agents
+    /// registered before service descriptors existed fill theirs in. The
+    /// attached deposit, plus any storage balance, pays for growth.
+    /// Operators pass the `agent_id` they act for.
+    #[payable]
+    pub fn set_service_descriptor(
+        &mut self,
+        endpoint_url: Option<String>,
+        protocols: Vec<String>,
+        pricing: Option<PricingModel>,
+        public_key: Option<String>,
+        agent_id: Option<AccountId>,
+    ) {
+        when_not_paused!(self);
+        self.mirror_call("set_service_descriptor", || {
+            json!({
+                "endpoint_url": endpoint_url,
+                "protocols": protocols,
+                "pricing": pricing,
+                "public_key": public_key,
+                "agent_id": agent_id,
+            })
+        });
+        let account_id = self.managed_agent_id(agent_id);
+        let mut agent = self.owned_agent(&account_id);
+        agent.metadata = normalize_service(AgentMetadata { endpoint_url, protocols, pricing, public_key, ..agent.metadata });
+        self.credit_storage_deposit(&account_id);
+        let initial_usage = env::storage_usage();
+
+        agent.updated_at = env::block_timestamp();
+        agent.version += 1;
+        self.agents.insert(&account_id, &agent);
+        self.settle_storage(&account_id, initial_usage);
+
+        events::emit(
+            "service_descriptor_updated",
+            json!({
+                "agent_id": account_id,
+                "endpoint_url": agent.metadata.endpoint_url,
+                "protocols": agent.metadata.protocols,
+            }),
+        );
+    }
+}
+
+impl AgentRegistration {
+    /// `metadata` as stored: skills normalized as in `normalize_skills`,
+    /// then checked against `registry_config` along with its off-chain
+    /// reference and service descriptor. Panics on the first problem found.
+    pub(crate) fn normalize_metadata(&self, metadata: AgentMetadata) -> AgentMetadata {
+        let metadata = AgentMetadata { skills: self.normalize_skills(metadata.skills), ..metadata };
+        self.assert_metadata_within_limits(&metadata);
+        if let Err(message) = metadata.validate_reference() {
+            env::panic_str(message);
+        }
+        normalize_service(metadata)
+    }
+}
+
+/// Lowercases protocols, then validates the service descriptor.
+fn normalize_service(metadata: AgentMetadata) -> AgentMetadata {
+    let protocols = metadata.protocols.iter().map(|protocol| protocol.trim().to_lowercase()).collect();
+    let metadata = AgentMetadata { protocols, ..metadata };
+    if let Err(message) = metadata.validate_service() {
+        env::panic_str(message);
+    }
+    metadata
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    const PUBLIC_KEY: &str = "ed25519:DcA2MzgpJbrUATQLLceocVckhhAqrkingax4oJ9kZ847";
+
+    #[test]
+    fn test_register_with_service_descriptor() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        let metadata = AgentMetadata {
+            endpoint_url: Some("https://agent.example.com/v1".to_string()),
+            protocols: vec!["HTTP".to_string(), "near".to_string()],
+            pricing: Some(PricingModel::PerTask { amount: 10u128.pow(24) }),
+            public_key: Some(PUBLIC_KEY.to_string()),
+            ..sample_metadata()
+        };
+        register(&mut contract, &mut context, metadata);
+
+        let stored = contract.get_agent(&accounts(1)).unwrap().metadata;
+        assert_eq!(stored.protocols, vec!["http".to_string(), "near".to_string()]);
+        assert_eq!(stored.pricing, Some(PricingModel::PerTask { amount: 10u128.pow(24) }));
+
+        contract.set_service_descriptor(None, vec!["near".to_string()], Some(PricingModel::Free), None, None);
+        let agent = contract.get_agent(&accounts(1)).unwrap();
+        assert_eq!((agent.metadata.endpoint_url, agent.metadata.skills), (None, vec!["Rust".to_string()]));
+        assert_eq!(agent.version, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "http and grpc protocols need an endpoint URL")]
+    fn test_http_needs_endpoint() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, AgentMetadata { protocols: vec!["grpc".to_string()], ..sample_metadata() });
+    }
+}
@@ -0,0 +1,137 @@
+use near_sdk::{env, near_bindgen, require, AccountId};
+use serde_json::json;
+
+use crate::events;
+use crate::skill_history::SkillChangeKind;
+use crate::skills_registry::{lists_skill, skill_lookup_key};
+use crate::system_skills::is_system_skill;
+use crate::{Agent, AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Adds one skill to the caller's agent without rewriting the rest of
+    /// its metadata. The skill is normalized like in `register_agent`. The
+    /// attached deposit, plus any storage balance, pays for the index entry.
+    /// Operators pass the `agent_id` they act for.
+    #[payable]
+    pub fn add_skill(&mut self, skill: String, agent_id: Option<AccountId>) {
+        when_not_paused!(self);
+        self.mirror_call("add_skill", || json!({ "skill": skill, "agent_id": agent_id }));
+        let account_id = self.managed_agent_id(agent_id);
+        self.credit_storage_deposit(&account_id);
+        let initial_usage = env::storage_usage();
+        self.add_agent_skill(&account_id, skill);
+        self.settle_storage(&account_id, initial_usage);
+    }
+
+    /// Drops one skill, matched ignoring case and spacing, from the caller's
+    /// agent and its index bucket, unlocking the storage it took. Operators
+    /// pass the `agent_id` they act for.
+    pub fn remove_skill(&mut self, skill: String, agent_id: Option<AccountId>) {
+        when_not_paused!(self);
+        self.mirror_call("remove_skill", || json!({ "skill": skill, "agent_id": agent_id }));
+        let account_id = self.managed_agent_id(agent_id);
+        let initial_usage = env::storage_usage();
+        self.remove_agent_skill(&account_id, skill);
+        self.settle_storage(&account_id, initial_usage);
+    }
+}
+
+impl AgentRegistration {
+    /// `add_skill` without the storage settlement, shared with
+    /// `batch_execute`.
+    pub(crate) fn add_agent_skill(&mut self, account_id: &AccountId, skill: String) {
+        let mut agent = self.owned_agent(account_id);
+        require!(!skill_lookup_key(&skill).is_empty(), "Skill must not be empty");
+        require!(!is_system_skill(&skill), "system: skills can only be changed by the registry owner");
+        let skill = self.normalize_skills(vec![skill]).remove(0);
+        require!(!lists_skill(&agent.metadata.skills, &skill), "Skill already listed");
+        if let Err(message) = self.registry_config.validate_skill(&skill) {
+            env::panic_str(&message);
+        }
+        require!(
+            agent.metadata.skills.len() < self.registry_config.max_skills as usize,
+            format!("An agent can list at most {} skills", self.registry_config.max_skills)
+        );
+
+        self.index_agent_skill(&skill, account_id);
+        self.record_skill_change(account_id, &skill, SkillChangeKind::Added);
+        agent.metadata.skills.push(skill.clone());
+        self.save_skill_change(account_id, agent);
+
+        events::emit("skill_added", json!({ "agent_id": account_id, "skill": skill }));
+    }
+
+    /// `remove_skill` without the storage settlement, shared with
+    /// `batch_execute`.
+    pub(crate) fn remove_agent_skill(&mut self, account_id: &AccountId, skill: String) {
+        let mut agent = self.owned_agent(account_id);
+        require!(!is_system_skill(&skill), "system: skills can only be changed by the registry owner");
+        let key = skill_lookup_key(&skill);
+        let position = agent
+            .metadata
+            .skills
+            .iter()
+            .position(|listed| skill_lookup_key(listed) == key)
+            .unwrap_or_else(|| env::panic_str("Skill not listed"));
+
+        let skill = agent.metadata.skills.remove(position);
+        self.unindex_agent_skill(&skill, account_id);
+        self.record_skill_change(account_id, &skill, SkillChangeKind::Removed);
+        self.save_skill_change(account_id, agent);
+
+        events::emit("skill_removed", json!({ "agent_id": account_id, "skill": skill }));
+    }
+
+    pub(crate) fn owned_agent(&self, account_id: &AccountId) -> Agent {
+        let agent = self
+            .agents
+            .get(account_id)
+            .unwrap_or_else(|| env::panic_str("Agent not registered"));
+        require!(&agent.owner_id == account_id, "Only the agent owner can update metadata");
+        agent
+    }
+
+    fn save_skill_change(&mut self, account_id: &AccountId, mut agent: Agent) {
+        agent.updated_at = env::block_timestamp();
+        agent.version += 1;
+        self.agents.insert(account_id, &agent);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+    use near_sdk::NearToken;
+
+    #[test]
+    fn test_add_and_remove_single_skills() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+
+        testing_env!(context.attached_deposit(NearToken::from_near(1)).build());
+        contract.add_skill("Go".to_string(), None);
+        testing_env!(context.attached_deposit(NearToken::from_near(0)).build());
+        contract.remove_skill("RUST".to_string(), None);
+
+        let agent = contract.get_agent(&accounts(1)).unwrap();
+        assert_eq!(agent.metadata.skills, vec!["Go".to_string()]);
+        assert_eq!(contract.get_agents_by_skill("go".to_string(), None), vec![accounts(1)]);
+        assert!(contract.get_agents_by_skill("Rust".to_string(), None).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Skill already listed")]
+    fn test_add_listed_skill_fails() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        contract.add_skill(" rust".to_string(), None);
+    }
+}
@@ -0,0 +1,233 @@
+use near_sdk::{env, near_bindgen, require, AccountId};
+use serde_json::json;
+
+use crate::events;
+use crate::reputation_calls::PendingReputationCall;
+use crate::skill_history::SkillChangeKind;
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// First step of moving the caller's agent to another account, e.g.
+    /// after a key rotation. `new_owner` must call `accept_agent_transfer`.
+    /// Proposing again replaces the pending receiver.
+    pub fn transfer_agent_ownership(&mut self, new_owner: AccountId) {
+        when_not_paused!(self);
+        let agent_id = env::predecessor_account_id();
+        require!(self.agents.contains_key(&agent_id), "Agent not registered");
+        require!(!self.is_banned(&agent_id), "A banned account cannot transfer its agent");
+        require!(new_owner != agent_id, "Cannot transfer an agent to itself");
+        require!(!self.agents.contains_key(&new_owner), "Receiver is already a registered agent");
+
+        self.pending_agent_transfers.insert(&agent_id, &new_owner);
+        events::emit(
+            "agent_transfer_proposed",
+            json!({ "agent_id": agent_id, "new_owner": new_owner }),
+        );
+    }
+
+    pub fn cancel_agent_transfer(&mut self) {
+        when_not_paused!(self);
+        let agent_id = env::predecessor_account_id();
+        require!(self.pending_agent_transfers.remove(&agent_id).is_some(), "No agent transfer pending");
+        events::emit("agent_transfer_cancelled", json!({ "agent_id": agent_id }));
+    }
+
+    /// Re-keys `agent_id` under the caller. The record, reputation, status,
+    /// handle, availability and invite cohort move with it; skill history,
+    /// audit anchors and slash history stay with the old account. The caller
+    /// needs the minimum ITLX stake and takes over the storage the agent
+    /// locks, while the old account is refunded. A near.social link is
+    /// dropped, since it was proven for the old account.
+    #[payable]
+    pub fn accept_agent_transfer(&mut self, agent_id: AccountId) {
+        when_not_paused!(self);
+        let new_owner = env::predecessor_account_id();
+        require!(
+            self.pending_agent_transfers.get(&agent_id).as_ref() == Some(&new_owner),
+            "No transfer of this agent is pending for the caller"
+        );
+        self.pending_agent_transfers.remove(&agent_id);
+        self.credit_storage_deposit(&new_owner);
+        self.move_agent(&agent_id, &new_owner, None);
+    }
+
+    pub fn get_pending_agent_transfer(&self, agent_id: AccountId) -> Option<AccountId> {
+        self.pending_agent_transfers.get(&agent_id)
+    }
+}
+
+impl AgentRegistration {
+    /// Re-keys `agent_id` under `new_owner`, which must not be an agent yet,
+    /// must hold the minimum stake, must be allowed to register, and takes
+    /// over the agent's storage. The agent must have no task in progress,
+    /// as those stay assigned to, and pay, the account that took them.
+    /// Shared by `accept_agent_transfer` and `nft_transfer`.
+    pub(crate) fn move_agent(&mut self, agent_id: &AccountId, new_owner: &AccountId, memo: Option<String>) {
+        let (agent_id, new_owner) = (agent_id.clone(), new_owner.clone());
+        require!(!self.agents.contains_key(&new_owner), "Account is already a registered agent");
+        require!(
+            !self.agent_open_tasks.contains_key(&agent_id),
+            "Agent has tasks in progress; finish them before transferring it"
+        );
+        // Or a suspension for a ban could be shed by moving the agent away
+        require!(!self.is_banned(&agent_id), "A banned account cannot transfer its agent");
+        self.assert_min_stake(&new_owner);
+        self.assert_registration_permitted(&new_owner);
+        // Taking over an agent counts as registering one
+        self.assert_registration_allowed(&new_owner);
+        // Memberships stay with the old owner's agent: it leaves its teams
+        // and settles that storage before the rest moves over
+        self.leave_all_teams(&agent_id);
+        // The registry pays for task history, so it moves unmeasured too
+        self.move_task_results(&agent_id, &new_owner);
+        let initial_usage = env::storage_usage();
+
+        let mut agent = self.agents.remove(&agent_id).unwrap();
+        for skill in &agent.metadata.skills {
+            self.unindex_agent_skill(skill, &agent_id);
+            self.record_skill_change(&agent_id, skill, SkillChangeKind::Removed);
+            self.index_agent_skill(skill, &new_owner);
+            self.record_skill_change(&new_owner, skill, SkillChangeKind::Added);
+        }
+        let reputation = agent.reputation_info.reputation;
+        self.rerank_agent(&agent_id, Some(reputation), None);
+        self.rerank_agent(&new_owner, None, Some(reputation));
+        agent.owner_id = new_owner.clone();
+        agent.updated_at = env::block_timestamp();
+        agent.version += 1;
+        self.agents.insert(&new_owner, &agent);
+
+        if let Some(handle) = self.handle_holders.remove(&agent_id) {
+            let mut record = self.handles.get(&handle).unwrap();
+            record.holder = new_owner.clone();
+            record.updated_at = env::block_timestamp();
+            self.handles.insert(&handle, &record);
+            self.handle_holders.insert(&new_owner, &handle);
+        }
+        if let Some(tip) = self.history_tips.remove(&agent_id) {
+            self.history_tips.insert(&new_owner, &tip);
+        }
+        if let Some(nonce) = self.reputation_push_nonces.remove(&agent_id) {
+            self.reputation_push_nonces.insert(&new_owner, &nonce);
+        }
+        if let Some(mut item) = self.reconciliation_queue.remove(&agent_id) {
+            item.agent_id = new_owner.clone();
+            self.reconciliation_queue.insert(new_owner.clone(), item);
+        }
+        if let Some(breakdown) = self.reputation_breakdowns.remove(&agent_id) {
+            self.reputation_breakdowns.insert(&new_owner, &breakdown);
+        }
+        if let Some(invite) = self.agent_invites.remove(&agent_id) {
+            self.agent_invites.insert(&new_owner, &invite);
+        }
+        if let Some(synced_at) = self.reputation_synced_at.remove(&agent_id) {
+            self.reputation_synced_at.insert(new_owner.clone(), synced_at);
+        }
+        if let Some(availability) = self.availability.remove(&agent_id) {
+            self.availability.insert(&new_owner, &availability);
+        }
+        if let Some(last_ping) = self.last_pings.remove(&agent_id) {
+            self.last_pings.insert(&new_owner, &last_ping);
+        }
+        self.move_reviews(&agent_id, &new_owner);
+        self.move_endorsements(&agent_id, &new_owner);
+        self.move_credentials(&agent_id, &new_owner);
+        self.social_links.remove(&agent_id);
+        self.clear_operators(&agent_id);
+        self.move_agent_token(&agent_id, &new_owner, memo);
+
+        self.transfer_locked_storage(&agent_id, &new_owner);
+        self.settle_storage(&new_owner, initial_usage);
+
+        // Let the reputation contract re-key its record as well. Without a
+        // record there, the new account gets a fresh one instead
+        if self.pending_initializations.remove(&agent_id) {
+            self.call_reputation_contract("initialize_agent", &new_owner, None, |reputation| {
+                reputation.initialize_agent(new_owner.clone())
+            });
+        } else {
+            let from = self.reputation_record_id(&agent_id);
+            self.send_reputation_call(&new_owner, PendingReputationCall::Transfer { from });
+        }
+        events::emit(
+            "agent_transferred",
+            json!({ "old_agent_id": agent_id, "new_agent_id": new_owner }),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{deposit_stake, get_context, register, sample_metadata};
+    use crate::staking::MIN_REGISTRATION_STAKE;
+    use crate::AgentInfo;
+    use near_sdk::json_types::U128;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+    use near_sdk::NearToken;
+
+    #[test]
+    fn test_transfer_rekeys_agent() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        contract.reserve_handle("bob-agent".to_string());
+        contract.claim_handle("bob-agent".to_string());
+        let info = AgentInfo { reputation: 40, task_history: Vec::new(), reputation_history: vec![(0, 40)] };
+        testing_env!(get_context(accounts(0)).build());
+        contract.update_agent_reputation(accounts(1), info, String::new(), None);
+
+        testing_env!(context.build());
+        contract.transfer_agent_ownership(accounts(2));
+        assert_eq!(contract.get_pending_agent_transfer(accounts(1)), Some(accounts(2)));
+
+        deposit_stake(&mut contract, accounts(2), MIN_REGISTRATION_STAKE);
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        contract.accept_agent_transfer(accounts(1));
+
+        assert!(contract.get_agent(&accounts(1)).is_none());
+        let agent = contract.get_agent(&accounts(2)).unwrap();
+        assert_eq!(agent.owner_id, accounts(2));
+        assert_eq!(agent.reputation_info.reputation_history, vec![(0, 40)]);
+        assert_eq!(contract.get_agents_by_skill("Rust".to_string(), None), vec![accounts(2)]);
+        assert_eq!(contract.get_agent_by_handle("bob-agent".to_string()), Some(accounts(2)));
+        assert_eq!(contract.get_total_agents(), 1);
+        assert_eq!(contract.storage_balance_of(accounts(1)), None);
+        assert_eq!(contract.get_pending_agent_transfer(accounts(1)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Agent has tasks in progress; finish them before transferring it")]
+    fn test_agent_with_a_task_in_progress_cannot_move() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        testing_env!(get_context(accounts(3)).attached_deposit(NearToken::from_near(2)).build());
+        let task = contract.create_task("Summarize a paper".to_string(), Vec::new(), U128(10u128.pow(24)));
+        contract.assign_task(task.task_id, accounts(1));
+        testing_env!(context.build());
+        contract.submit_result(task.task_id, "Done".to_string());
+        contract.transfer_agent_ownership(accounts(2));
+
+        deposit_stake(&mut contract, accounts(2), MIN_REGISTRATION_STAKE);
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        contract.accept_agent_transfer(accounts(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "No transfer of this agent is pending for the caller")]
+    fn test_accept_requires_matching_proposal() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        contract.transfer_agent_ownership(accounts(2));
+
+        testing_env!(get_context(accounts(3)).build());
+        contract.accept_agent_transfer(accounts(1));
+    }
+}
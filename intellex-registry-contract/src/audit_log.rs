@@ -0,0 +1,153 @@
+use near_sdk::store::Vector;
+use near_sdk::{env, near_bindgen, require, AccountId};
+
+pub use intellex_types::audit_log::{AuditAnchor, AuditGap};
+
+use crate::pagination::{page_limit, paginate, sequence_start, CursorKey, Page};
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub(crate) fn from_hex(input: &str) -> Option<Vec<u8>> {
+    if !input.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..input.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(input.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn chain_hash(previous: Option<&str>, period_id: u64, merkle_root: &[u8]) -> String {
+    let mut preimage = previous.and_then(from_hex).unwrap_or_default();
+    preimage.extend_from_slice(&period_id.to_le_bytes());
+    preimage.extend_from_slice(merkle_root);
+    to_hex(&env::sha256(&preimage))
+}
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Commits the merkle root of the caller's off-chain action log for
+    /// `period_id`. Periods must increase; skipped periods are recorded as gaps.
+    pub fn anchor_audit_log(&mut self, period_id: u64, merkle_root: String) -> AuditAnchor {
+        when_not_paused!(self);
+        let agent_id = env::predecessor_account_id();
+        require!(self.agents.contains_key(&agent_id), "Agent not registered");
+        let root = from_hex(&merkle_root)
+            .filter(|root| root.len() == 32)
+            .unwrap_or_else(|| env::panic_str("Merkle root must be 32 bytes of hex"));
+
+        let mut anchors = match self.audit_logs.get(&agent_id) {
+            Some(existing) => existing,
+            None => Vector::new(format!("l_{}", agent_id).into_bytes()),
+        };
+        let previous = anchors.len().checked_sub(1).and_then(|i| anchors.get(i));
+        if let Some(previous) = previous {
+            require!(
+                period_id > previous.period_id,
+                "Period must be later than the last anchored period"
+            );
+        }
+
+        let anchor = AuditAnchor {
+            period_id,
+            merkle_root: to_hex(&root),
+            anchored_at: env::block_timestamp(),
+            chain_hash: chain_hash(previous.map(|p| p.chain_hash.as_str()), period_id, &root),
+            follows_gap: previous.is_some_and(|p| period_id != p.period_id + 1),
+        };
+        anchors.push(anchor.clone());
+        self.audit_logs.insert(&agent_id, &anchors);
+        anchor
+    }
+
+    pub fn get_audit_anchors(&self, agent_id: AccountId, cursor: Option<String>, limit: Option<u64>) -> Page<AuditAnchor> {
+        let start = sequence_start("get_audit_anchors", cursor);
+
+        self.audit_logs
+            .get(&agent_id)
+            .map(|anchors| {
+                let anchors = anchors
+                    .iter()
+                    .enumerate()
+                    .skip(start as usize)
+                    .map(|(i, anchor)| (CursorKey::Sequence(i as u64), anchor.clone()));
+                paginate("get_audit_anchors", anchors, page_limit(limit))
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn get_latest_audit_anchor(&self, agent_id: AccountId) -> Option<AuditAnchor> {
+        let anchors = self.audit_logs.get(&agent_id)?;
+        anchors.len().checked_sub(1).and_then(|i| anchors.get(i)).cloned()
+    }
+
+    /// Lists the period ranges missing from an agent's anchor chain.
+    pub fn get_audit_gaps(&self, agent_id: AccountId) -> Vec<AuditGap> {
+        let Some(anchors) = self.audit_logs.get(&agent_id) else {
+            return Vec::new();
+        };
+        let mut gaps = Vec::new();
+        let mut previous: Option<u64> = None;
+        for anchor in anchors.iter() {
+            if let (Some(prev), true) = (previous, anchor.follows_gap) {
+                gaps.push(AuditGap {
+                    from_period: prev + 1,
+                    to_period: anchor.period_id - 1,
+                });
+            }
+            previous = Some(anchor.period_id);
+        }
+        gaps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    fn root(byte: u8) -> String {
+        to_hex(&[byte; 32])
+    }
+
+    #[test]
+    fn test_anchor_chain_and_gaps() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+
+        let first = contract.anchor_audit_log(1, root(1));
+        let second = contract.anchor_audit_log(2, root(2));
+        let fifth = contract.anchor_audit_log(5, root(5));
+
+        assert_ne!(first.chain_hash, second.chain_hash);
+        assert!(!second.follows_gap);
+        assert!(fifth.follows_gap);
+        assert_eq!(
+            contract.get_audit_gaps(accounts(1)),
+            vec![AuditGap { from_period: 3, to_period: 4 }]
+        );
+        let page = contract.get_audit_anchors(accounts(1), None, Some(1));
+        let page = contract.get_audit_anchors(accounts(1), page.next_cursor, Some(1));
+        assert_eq!(page.items[0].period_id, 2);
+        assert_eq!(contract.get_latest_audit_anchor(accounts(1)).unwrap().period_id, 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "Period must be later than the last anchored period")]
+    fn test_anchor_rejects_replayed_period() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+
+        contract.anchor_audit_log(3, root(1));
+        contract.anchor_audit_log(3, root(2));
+    }
+}
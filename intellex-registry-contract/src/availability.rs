@@ -0,0 +1,117 @@
+use near_sdk::{env, near_bindgen, require, AccountId};
+
+pub use intellex_types::availability::{is_online, Availability, WorkingWindow, ONLINE_PING_WINDOW_NS};
+
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Declares the caller's timezone and working hours.
+    pub fn set_availability(&mut self, availability: Availability) {
+        when_not_paused!(self);
+        self.store_availability(&env::predecessor_account_id(), availability);
+    }
+
+    /// Liveness heartbeat used by the "online now" estimate.
+    pub fn ping(&mut self) {
+        when_not_paused!(self);
+        self.record_ping(&env::predecessor_account_id());
+    }
+
+    pub fn get_availability(&self, agent_id: AccountId) -> Option<Availability> {
+        self.availability.get(&agent_id)
+    }
+
+    pub fn get_last_ping(&self, agent_id: AccountId) -> Option<u64> {
+        self.last_pings.get(&agent_id)
+    }
+
+    /// True if the agent pinged within the last 15 minutes and the current
+    /// time falls inside its declared working hours.
+    pub fn is_online_now(&self, agent_id: AccountId) -> bool {
+        self.agents.contains_key(&agent_id) && self.online_at(&agent_id, env::block_timestamp())
+    }
+
+    /// `get_agents_by_skill`, restricted to active agents estimated to be
+    /// online.
+    pub fn get_online_agents_by_skill(&self, skill: String) -> Vec<AccountId> {
+        let now = env::block_timestamp();
+        self.get_agents_by_skill(skill, Some(true))
+            .into_iter()
+            .filter(|agent_id| self.online_at(agent_id, now))
+            .collect()
+    }
+}
+
+impl AgentRegistration {
+    pub(crate) fn store_availability(&mut self, account_id: &AccountId, availability: Availability) {
+        require!(self.agents.contains_key(account_id), "Agent not registered");
+        if let Err(message) = availability.validate() {
+            env::panic_str(message);
+        }
+        self.availability.insert(account_id, &availability);
+        self.bump_agent_version(account_id);
+    }
+
+    pub(crate) fn record_ping(&mut self, account_id: &AccountId) {
+        require!(self.agents.contains_key(account_id), "Agent not registered");
+        self.last_pings.insert(account_id, &env::block_timestamp());
+    }
+
+    pub(crate) fn online_at(&self, agent_id: &AccountId, now: u64) -> bool {
+        is_online(
+            self.availability.get(agent_id).as_ref(),
+            self.last_pings.get(agent_id),
+            now,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    const HOUR: u64 = 60 * 60 * 1_000_000_000;
+    // Monday 1970-01-05 10:00 UTC
+    const MONDAY_TEN_AM: u64 = 4 * 24 * HOUR + 10 * HOUR;
+
+    #[test]
+    fn test_online_filter() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.block_timestamp(MONDAY_TEN_AM).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        contract.set_availability(Availability {
+            utc_offset_minutes: 0,
+            working_hours: vec![WorkingWindow { days: 0b001_1111, start_minute: 9 * 60, end_minute: 17 * 60 }],
+        });
+
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        register(&mut contract, &mut context, sample_metadata());
+
+        assert!(contract.get_online_agents_by_skill("Rust".to_string()).is_empty());
+
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.ping();
+        assert!(contract.is_online_now(accounts(1)));
+        assert_eq!(contract.get_online_agents_by_skill("Rust".to_string()), vec![accounts(1)]);
+
+        // Still recently pinged, but outside working hours
+        testing_env!(context.block_timestamp(MONDAY_TEN_AM + 8 * HOUR).build());
+        contract.ping();
+        assert!(!contract.is_online_now(accounts(1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "UTC offset must be within +/-14 hours")]
+    fn test_set_availability_validates() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        contract.set_availability(Availability { utc_offset_minutes: 20 * 60, working_hours: Vec::new() });
+    }
+}
@@ -0,0 +1,25 @@
+use near_sdk::{near_bindgen, AccountId};
+
+pub use intellex_types::bands::{band_for_reputation, BandThreshold, ReputationBand, DEFAULT_BAND_THRESHOLDS};
+
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Thresholds every UI should use to label reputation scores.
+    pub fn get_band_thresholds(&self) -> Vec<BandThreshold> {
+        DEFAULT_BAND_THRESHOLDS
+            .iter()
+            .map(|(band, min_reputation)| BandThreshold {
+                band: *band,
+                min_reputation: *min_reputation,
+            })
+            .collect()
+    }
+
+    pub fn get_agent_band(&self, agent_id: AccountId) -> Option<ReputationBand> {
+        self.agents
+            .get(&agent_id)
+            .map(|agent| band_for_reputation(agent.reputation_info.reputation))
+    }
+}
@@ -0,0 +1,91 @@
+use near_sdk::{env, near_bindgen, require, AccountId};
+
+pub use intellex_types::batch::{RegistryAction, MAX_BATCH_ACTIONS};
+
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Applies up to 20 changes to the caller's agent in order, all or
+    /// nothing: if any action fails, none of them take effect. Storage for
+    /// the whole batch is settled once against the caller's balance,
+    /// topped up by the attached deposit. Operators pass the `agent_id`
+    /// they act for.
+    #[payable]
+    pub fn batch_execute(&mut self, actions: Vec<RegistryAction>, agent_id: Option<AccountId>) {
+        when_not_paused!(self);
+        self.mirror_call("batch_execute", || serde_json::json!({ "actions": actions, "agent_id": agent_id }));
+        require!(!actions.is_empty(), "No actions to execute");
+        require!(actions.len() <= MAX_BATCH_ACTIONS, "Cannot execute more than 20 actions at once");
+        let account_id = self.managed_agent_id(agent_id);
+        self.credit_storage_deposit(&account_id);
+        let initial_usage = env::storage_usage();
+
+        for action in actions {
+            match action {
+                RegistryAction::UpdateMetadata(metadata) => self.replace_agent_metadata(&account_id, *metadata),
+                RegistryAction::AddSkill(skill) => self.add_agent_skill(&account_id, skill),
+                RegistryAction::RemoveSkill(skill) => self.remove_agent_skill(&account_id, skill),
+                RegistryAction::SetAvailability(availability) => self.store_availability(&account_id, availability),
+                RegistryAction::Ping => self.record_ping(&account_id),
+            }
+        }
+        self.settle_storage(&account_id, initial_usage);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::availability::Availability;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+    use near_sdk::NearToken;
+
+    #[test]
+    fn test_batch_applies_actions_in_order() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+
+        let availability = Availability { utc_offset_minutes: 60, working_hours: Vec::new() };
+        testing_env!(context.attached_deposit(NearToken::from_near(1)).build());
+        contract.batch_execute(vec![
+            RegistryAction::AddSkill("Go".to_string()),
+            RegistryAction::RemoveSkill("rust".to_string()),
+            RegistryAction::SetAvailability(availability.clone()),
+            RegistryAction::Ping,
+        ], None);
+
+        assert_eq!(contract.get_agent(&accounts(1)).unwrap().metadata.skills, vec!["Go".to_string()]);
+        assert_eq!(contract.get_agents_by_skill("Rust".to_string(), None), Vec::<AccountId>::new());
+        assert_eq!(contract.get_availability(accounts(1)), Some(availability));
+        assert!(contract.get_last_ping(accounts(1)).is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "UTC offset must be within +/-14 hours")]
+    fn test_batch_fails_as_a_whole() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        contract.batch_execute(vec![
+            RegistryAction::AddSkill("Go".to_string()),
+            RegistryAction::SetAvailability(Availability { utc_offset_minutes: 15 * 60, working_hours: Vec::new() }),
+        ], None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Skill already listed")]
+    fn test_batch_skill_actions_check_like_add_skill() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        testing_env!(context.attached_deposit(NearToken::from_near(1)).build());
+        contract.batch_execute(vec![RegistryAction::AddSkill(" rust".to_string())], None);
+    }
+}
@@ -0,0 +1,190 @@
+use near_sdk::borsh;
+use near_sdk::{env, near_bindgen, require, AccountId};
+
+pub use intellex_types::continuity::{
+    HeldReputationUpdate, IncrementalChange, ReconciliationItem, ReputationPush, MAX_HELD_REPUTATION_UPDATES,
+};
+
+use crate::audit_log::to_hex;
+use crate::pagination::{account_after, page_limit, paginate, sequence_start, CursorKey, Page};
+use crate::{AgentInfo, AgentRegistration, AgentRegistrationExt};
+
+/// Hash identifying a reputation state: hex(sha256(borsh(AgentInfo))).
+/// The reputation contract sends the hash of the state it last delivered
+/// as `prev_tip_hash` with each push; before any delivery the tip is empty.
+pub fn history_tip_hash(reputation_info: &AgentInfo) -> String {
+    to_hex(&env::sha256(&borsh::to_vec(reputation_info).unwrap()))
+}
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Hash of the last reputation state applied for an agent, or an empty
+    /// string if none has been applied yet.
+    pub fn get_history_tip(&self, agent_id: AccountId) -> String {
+        self.history_tips.get(&agent_id).unwrap_or_default()
+    }
+
+    /// Pushes held back for a tip mismatch. A successful `sync_reputation`
+    /// for the agent clears its entry.
+    /// Ordered by agent id.
+    pub fn get_reconciliation_queue(&self, cursor: Option<String>, limit: Option<u64>) -> Page<ReconciliationItem> {
+        let after = account_after("get_reconciliation_queue", cursor);
+        let mut items: Vec<&ReconciliationItem> = self
+            .reconciliation_queue
+            .values()
+            .filter(|item| after.as_ref().is_none_or(|after| &item.agent_id > after))
+            .collect();
+        items.sort_by(|a, b| a.agent_id.cmp(&b.agent_id));
+
+        let items = items
+            .into_iter()
+            .map(|item| (CursorKey::Account(item.agent_id.clone()), item.clone()));
+        paginate("get_reconciliation_queue", items, page_limit(limit))
+    }
+
+    /// Applies up to `limit` held pushes (default 50, max 100) in arrival
+    /// order, exactly as if they had arrived now. An incremental push that
+    /// would have been rejected then, e.g. for a stale nonce, is logged and
+    /// dropped. Anyone may call it once reputation updates are resumed.
+    /// Returns how many were replayed.
+    pub fn replay_held_reputation_updates(&mut self, limit: Option<u64>) -> u64 {
+        when_not_paused!(self);
+        require!(!self.reputation_updates_paused, "Reputation updates are paused");
+        let limit = limit.unwrap_or(50).min(100);
+
+        let mut applied = 0;
+        while applied < limit && self.held_updates_head < self.held_updates_tail {
+            let held = self.held_reputation_updates.remove(&self.held_updates_head).unwrap();
+            self.held_updates_head += 1;
+            match held.push {
+                ReputationPush::Full { reputation_info, prev_tip_hash, breakdown } => {
+                    self.apply_reputation_update(&held.agent_id, reputation_info, &prev_tip_hash, breakdown)
+                }
+                ReputationPush::Incremental { change, nonce, tip_hash } => {
+                    if let Err(reason) = self.apply_incremental_push(&held.agent_id, change, nonce, tip_hash) {
+                        env::log_str(&format!("Held reputation push for {} dropped: {}", held.agent_id, reason));
+                    }
+                }
+            }
+            applied += 1;
+        }
+        applied
+    }
+
+    /// Held pushes in arrival order. A cursor stays valid while pushes are
+    /// replayed; ones replayed in the meantime are simply skipped.
+    pub fn get_held_reputation_updates(&self, cursor: Option<String>, limit: Option<u64>) -> Page<HeldReputationUpdate> {
+        let start = sequence_start("get_held_reputation_updates", cursor).max(self.held_updates_head);
+        let updates = (start..self.held_updates_tail)
+            .filter_map(|seq| self.held_reputation_updates.get(&seq).map(|update| (CursorKey::Sequence(seq), update)));
+        paginate("get_held_reputation_updates", updates, page_limit(limit))
+    }
+
+    pub fn get_held_reputation_update_count(&self) -> u64 {
+        self.held_updates_tail - self.held_updates_head
+    }
+}
+
+impl AgentRegistration {
+    /// Queues a push for replay, rejecting it once the queue is full.
+    pub(crate) fn hold_reputation_update(&mut self, agent_id: AccountId, push: ReputationPush) {
+        require!(
+            self.held_updates_tail - self.held_updates_head < MAX_HELD_REPUTATION_UPDATES,
+            "Held reputation update queue is full"
+        );
+        self.held_reputation_updates.insert(
+            &self.held_updates_tail,
+            &HeldReputationUpdate { agent_id, push, received_at: env::block_timestamp() },
+        );
+        self.held_updates_tail += 1;
+    }
+
+    /// Returns true if `prev_tip_hash` continues the agent's history.
+    /// Otherwise queues the push for reconciliation and returns false.
+    pub(crate) fn check_history_continuity(
+        &mut self,
+        agent_id: &AccountId,
+        prev_tip_hash: &str,
+        reputation_info: &AgentInfo,
+    ) -> bool {
+        let expected_tip = self.history_tips.get(agent_id).unwrap_or_default();
+        if expected_tip == prev_tip_hash {
+            return true;
+        }
+        env::log_str(&format!(
+            "Reputation history discontinuity for {}: expected tip {:?}, got {:?}",
+            agent_id, expected_tip, prev_tip_hash
+        ));
+        self.reconciliation_queue.insert(
+            agent_id.clone(),
+            ReconciliationItem {
+                agent_id: agent_id.clone(),
+                expected_tip,
+                provided_tip: prev_tip_hash.to_string(),
+                proposed_reputation: reputation_info.reputation,
+                flagged_at: env::block_timestamp(),
+            },
+        );
+        false
+    }
+
+    /// Records `tip_hash` as the agent's new tip, clears any pending
+    /// discontinuity and stamps the sync time.
+    pub(crate) fn advance_history_tip(&mut self, agent_id: &AccountId, tip_hash: String) {
+        self.history_tips.insert(agent_id, &tip_hash);
+        self.reconciliation_queue.remove(agent_id);
+        self.reputation_synced_at.insert(agent_id.clone(), env::block_timestamp());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    fn info(reputation: u64) -> AgentInfo {
+        AgentInfo { reputation, task_history: Vec::new(), reputation_history: vec![(reputation, reputation)] }
+    }
+
+    #[test]
+    fn test_paused_updates_are_held_and_replayed_in_order() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+
+        testing_env!(get_context(accounts(0)).build());
+        contract.pause_reputation_updates();
+        contract.update_agent_reputation(accounts(1), info(10), String::new(), None);
+        contract.update_agent_reputation(accounts(1), info(20), history_tip_hash(&info(10)), None);
+        assert_eq!(contract.get_agent_reputation(&accounts(1)), Some(0));
+        assert_eq!(contract.get_held_reputation_update_count(), 2);
+
+        contract.resume_reputation_updates();
+        // Still queued behind the held pushes rather than jumping ahead
+        contract.update_agent_reputation(accounts(1), info(30), history_tip_hash(&info(20)), None);
+        let page = contract.get_held_reputation_updates(None, Some(2));
+        let page = contract.get_held_reputation_updates(page.next_cursor, None);
+        assert!(matches!(&page.items[0].push, ReputationPush::Full { reputation_info, .. } if reputation_info.reputation == 30));
+
+        assert_eq!(contract.replay_held_reputation_updates(Some(2)), 2);
+        assert_eq!(contract.get_agent_reputation(&accounts(1)), Some(20));
+        assert_eq!(contract.replay_held_reputation_updates(None), 1);
+        assert_eq!(contract.get_agent_reputation(&accounts(1)), Some(30));
+        assert!(contract.get_reconciliation_queue(None, None).items.is_empty());
+        assert_eq!(contract.get_held_reputation_update_count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Held reputation update queue is full")]
+    fn test_held_queue_overflow_rejects_push() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.pause_reputation_updates();
+        for _ in 0..=MAX_HELD_REPUTATION_UPDATES {
+            contract.update_agent_reputation(accounts(1), info(1), String::new(), None);
+        }
+    }
+}
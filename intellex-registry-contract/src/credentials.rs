@@ -0,0 +1,232 @@
+use near_sdk::store::IterableMap;
+use near_sdk::{env, near_bindgen, require, AccountId};
+use serde_json::json;
+
+pub use intellex_types::credentials::{Credential, MAX_AGENT_CREDENTIALS, MAX_CREDENTIAL_NAME_LENGTH};
+
+use crate::events;
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Lets `account_id` issue credentials. Owner only.
+    pub fn add_credential_verifier(&mut self, account_id: AccountId) {
+        self.assert_owner();
+        require!(self.credential_verifiers.insert(account_id.clone()), "Already a verifier");
+        events::emit("credential_verifier_added", json!({ "account_id": account_id }));
+    }
+
+    /// Stops `account_id` from issuing credentials. Ones it already issued
+    /// stay valid until revoked or expired. Owner only.
+    pub fn remove_credential_verifier(&mut self, account_id: AccountId) {
+        self.assert_owner();
+        require!(self.credential_verifiers.remove(&account_id), "Not a verifier");
+        events::emit("credential_verifier_removed", json!({ "account_id": account_id }));
+    }
+
+    pub fn get_credential_verifiers(&self) -> Vec<AccountId> {
+        self.credential_verifiers.iter().cloned().collect()
+    }
+
+    /// Issues credential `name` to `agent_id`, replacing any earlier one of
+    /// that name. Owner or verifiers only. The attached deposit, plus any
+    /// storage balance, pays for the credential.
+    #[payable]
+    pub fn issue_credential(&mut self, agent_id: AccountId, name: String, expires_at: Option<u64>) -> Credential {
+        when_not_paused!(self);
+        let issuer = env::predecessor_account_id();
+        require!(
+            issuer == self.owner_id || self.credential_verifiers.contains(&issuer),
+            "Only the owner or a verifier can issue credentials"
+        );
+        require!(
+            !name.is_empty() && name.len() <= MAX_CREDENTIAL_NAME_LENGTH,
+            "Credential name must be between 1 and 64 bytes"
+        );
+        require!(self.agents.contains_key(&agent_id), "Agent not registered");
+        let now = env::block_timestamp();
+        require!(expires_at.is_none_or(|expires_at| expires_at > now), "Expiry must be in the future");
+        self.credit_storage_deposit(&issuer);
+        let initial_usage = env::storage_usage();
+
+        let mut credentials = self
+            .agent_credentials
+            .get(&agent_id)
+            .unwrap_or_else(|| IterableMap::new(self.agent_collection_prefix('I', &agent_id)));
+        require!(
+            credentials.contains_key(&name) || credentials.len() < MAX_AGENT_CREDENTIALS,
+            "Agent holds too many credentials"
+        );
+        let credential = Credential { name: name.clone(), issuer: issuer.clone(), issued_at: now, expires_at, revoked_at: None };
+        credentials.insert(name.clone(), credential.clone());
+        self.agent_credentials.insert(&agent_id, &credentials);
+        // Flush the credential before measuring what it took
+        drop(credentials);
+        self.settle_storage(&issuer, initial_usage);
+
+        events::emit(
+            "credential_issued",
+            json!({ "agent_id": agent_id, "name": name, "issuer": issuer, "expires_at": expires_at }),
+        );
+        credential
+    }
+
+    /// Revokes `agent_id`'s credential `name`. The record is kept, marked
+    /// revoked. Its issuer or the owner only; the caller pays for the
+    /// revocation mark.
+    #[payable]
+    pub fn revoke_credential(&mut self, agent_id: AccountId, name: String) {
+        when_not_paused!(self);
+        let caller = env::predecessor_account_id();
+        let mut credentials = self
+            .agent_credentials
+            .get(&agent_id)
+            .unwrap_or_else(|| env::panic_str("Credential not found"));
+        let credential = credentials
+            .get_mut(&name)
+            .unwrap_or_else(|| env::panic_str("Credential not found"));
+        require!(
+            caller == credential.issuer || caller == self.owner_id,
+            "Only the issuer or the owner can revoke a credential"
+        );
+        require!(credential.revoked_at.is_none(), "Credential already revoked");
+        self.credit_storage_deposit(&caller);
+        let initial_usage = env::storage_usage();
+
+        credential.revoked_at = Some(env::block_timestamp());
+        self.agent_credentials.insert(&agent_id, &credentials);
+        drop(credentials);
+        self.settle_storage(&caller, initial_usage);
+        events::emit(
+            "credential_revoked",
+            json!({ "agent_id": agent_id, "name": name, "revoked_by": caller }),
+        );
+    }
+
+    /// `agent_id`'s credentials. Revoked and expired ones are only included
+    /// with `include_inactive`.
+    pub fn get_agent_credentials(&self, agent_id: AccountId, include_inactive: Option<bool>) -> Vec<Credential> {
+        let now = env::block_timestamp();
+        self.agent_credentials
+            .get(&agent_id)
+            .map(|credentials| {
+                credentials
+                    .values()
+                    .filter(|credential| include_inactive.unwrap_or(false) || credential.is_valid(now))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn has_credential(&self, agent_id: AccountId, name: String) -> bool {
+        self.agent_credentials
+            .get(&agent_id)
+            .and_then(|credentials| credentials.get(&name).map(|credential| credential.is_valid(env::block_timestamp())))
+            .unwrap_or(false)
+    }
+}
+
+impl AgentRegistration {
+    /// Re-keys `agent_id`'s credentials to `new_owner`; they attest the
+    /// agent, which keeps its token and so its credentials' prefix.
+    pub(crate) fn move_credentials(&mut self, agent_id: &AccountId, new_owner: &AccountId) {
+        if let Some(credentials) = self.agent_credentials.remove(agent_id) {
+            self.agent_credentials.insert(new_owner, &credentials);
+        }
+    }
+
+    /// Deletes `agent_id`'s credentials, at most `MAX_AGENT_CREDENTIALS`.
+    pub(crate) fn clear_credentials(&mut self, agent_id: &AccountId) {
+        if let Some(mut credentials) = self.agent_credentials.remove(agent_id) {
+            credentials.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::staking::MIN_REGISTRATION_STAKE;
+    use crate::storage::storage_cost;
+    use crate::tests::{deposit_stake, get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+    use near_sdk::NearToken;
+
+    fn setup() -> AgentRegistration {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        testing_env!(get_context(accounts(0)).build());
+        contract.add_credential_verifier(accounts(2));
+        contract
+    }
+
+    #[test]
+    fn test_credentials_expire_and_revoke() {
+        let mut contract = setup();
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        contract.storage_deposit(None, None);
+        testing_env!(get_context(accounts(2)).build());
+        let initial_usage = env::storage_usage();
+        contract.issue_credential(accounts(1), "Audited".to_string(), None);
+        contract.issue_credential(accounts(1), "Benchmark >90%".to_string(), Some(100));
+        assert_eq!(contract.get_agent_credentials(accounts(1), None).len(), 2);
+        let balance = contract.storage_balance_of(accounts(2)).unwrap();
+        assert_eq!(balance.total - balance.available, storage_cost(env::storage_usage() - initial_usage));
+
+        testing_env!(get_context(accounts(2)).block_timestamp(100).build());
+        assert!(!contract.has_credential(accounts(1), "Benchmark >90%".to_string()));
+        contract.revoke_credential(accounts(1), "Audited".to_string());
+        assert!(contract.get_agent_credentials(accounts(1), None).is_empty());
+        let all = contract.get_agent_credentials(accounts(1), Some(true));
+        assert_eq!(all.len(), 2);
+        assert!(all.iter().any(|credential| credential.revoked_at == Some(100)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the owner or a verifier can issue credentials")]
+    fn test_only_verifiers_issue() {
+        let mut contract = setup();
+        testing_env!(get_context(accounts(3)).attached_deposit(NearToken::from_near(1)).build());
+        contract.issue_credential(accounts(1), "Audited".to_string(), None);
+    }
+
+    #[test]
+    fn test_credentials_follow_a_transferred_agent() {
+        let mut contract = setup();
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        contract.issue_credential(accounts(1), "Audited".to_string(), None);
+
+        testing_env!(get_context(accounts(1)).build());
+        contract.transfer_agent_ownership(accounts(3));
+        deposit_stake(&mut contract, accounts(3), MIN_REGISTRATION_STAKE);
+        testing_env!(get_context(accounts(3)).attached_deposit(NearToken::from_near(1)).build());
+        contract.accept_agent_transfer(accounts(1));
+        assert!(contract.has_credential(accounts(3), "Audited".to_string()));
+
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        register(&mut contract, &mut context, sample_metadata());
+        assert!(contract.get_agent_credentials(accounts(1), Some(true)).is_empty());
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        contract.issue_credential(accounts(1), "KYC-verified".to_string(), None);
+        assert_eq!(contract.get_agent_credentials(accounts(3), None).len(), 1);
+    }
+
+    #[test]
+    fn test_deregistration_clears_credentials() {
+        let mut contract = setup();
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        contract.issue_credential(accounts(1), "Audited".to_string(), None);
+
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        contract.deregister_agent();
+        register(&mut contract, &mut context, sample_metadata());
+        assert!(!contract.has_credential(accounts(1), "Audited".to_string()));
+        assert!(contract.get_agent_credentials(accounts(1), Some(true)).is_empty());
+    }
+}
@@ -0,0 +1,198 @@
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, require, AccountId};
+use serde_json::json;
+
+pub use intellex_types::curation::{CurationBounty, FlagStatus, ListingFlag, FLAG_BOND, MAX_FLAG_REASON_LENGTH};
+
+use crate::events;
+use crate::{AgentRegistration, AgentRegistrationExt, AgentStatus};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Reports `agent_id`'s listing as invalid, e.g. a dead endpoint or
+    /// fraudulent claims. Attach `FLAG_BOND`; anything above it tops up the
+    /// caller's storage balance, which pays for the flag. A listing has at
+    /// most one pending flag.
+    #[payable]
+    pub fn flag_listing(&mut self, agent_id: AccountId, reason: String) -> ListingFlag {
+        when_not_paused!(self);
+        require!(reason.len() <= MAX_FLAG_REASON_LENGTH, "Flag reason exceeds 512 bytes");
+        let flagger = env::predecessor_account_id();
+        require!(flagger != agent_id, "Cannot flag your own listing");
+        require!(self.agents.contains_key(&agent_id), "Agent not registered");
+        require!(!self.pending_flags.contains_key(&agent_id), "Listing already has a pending flag");
+        let attached = env::attached_deposit().as_yoctonear();
+        require!(attached >= FLAG_BOND, "Attach the 0.1 NEAR flag bond");
+
+        self.credit_storage(&flagger, attached - FLAG_BOND);
+        let initial_usage = env::storage_usage();
+        let flag = ListingFlag {
+            flag_id: self.next_flag_id,
+            agent_id: agent_id.clone(),
+            flagger: flagger.clone(),
+            reason,
+            bond: FLAG_BOND,
+            status: FlagStatus::Pending,
+            bounty_paid: 0,
+            flagged_at: env::block_timestamp(),
+            reviewed_at: None,
+        };
+        self.next_flag_id += 1;
+        self.listing_flags.insert(&flag.flag_id, &flag);
+        self.pending_flags.insert(&agent_id, &flag.flag_id);
+        self.settle_storage(&flagger, initial_usage);
+        events::emit(
+            "listing_flagged",
+            json!({ "flag_id": flag.flag_id, "agent_id": agent_id, "flagger": flagger }),
+        );
+        flag
+    }
+
+    /// Upholds a flag: suspends the agent, refunds the bond and pays the
+    /// flagger the bounty, or whatever is left of the pool. Arbiter or owner.
+    pub fn confirm_flag(&mut self, flag_id: u64) -> ListingFlag {
+        when_not_paused!(self);
+        let mut flag = self.review_flag(flag_id, FlagStatus::Confirmed);
+        flag.bounty_paid = self.curation_bounty.min(self.curation_pool);
+        self.curation_pool -= flag.bounty_paid;
+        self.listing_flags.insert(&flag_id, &flag);
+
+        if self
+            .agents
+            .get(&flag.agent_id)
+            .is_some_and(|agent| agent.status != AgentStatus::Suspended)
+        {
+            self.change_status(&flag.agent_id, AgentStatus::Suspended);
+        }
+        self.pay_near(&flag.flagger, flag.bond);
+        self.pay_itlx(&flag.flagger, flag.bounty_paid);
+        events::emit(
+            "flag_confirmed",
+            json!({
+                "flag_id": flag_id,
+                "agent_id": flag.agent_id,
+                "flagger": flag.flagger,
+                "bounty_paid": U128(flag.bounty_paid),
+            }),
+        );
+        flag
+    }
+
+    /// Dismisses a flag; its bond goes to the treasury. Arbiter or owner.
+    pub fn reject_flag(&mut self, flag_id: u64) -> ListingFlag {
+        when_not_paused!(self);
+        let flag = self.review_flag(flag_id, FlagStatus::Rejected);
+        self.pay_near(&self.treasury_id, flag.bond);
+        events::emit(
+            "flag_rejected",
+            json!({ "flag_id": flag_id, "agent_id": flag.agent_id, "flagger": flag.flagger }),
+        );
+        flag
+    }
+
+    pub fn get_listing_flag(&self, flag_id: u64) -> Option<ListingFlag> {
+        self.listing_flags.get(&flag_id)
+    }
+
+    pub fn get_pending_flag(&self, agent_id: AccountId) -> Option<ListingFlag> {
+        self.pending_flags
+            .get(&agent_id)
+            .and_then(|flag_id| self.listing_flags.get(&flag_id))
+    }
+
+    pub fn get_curation_bounty(&self) -> CurationBounty {
+        CurationBounty { bounty: self.curation_bounty, pool: self.curation_pool }
+    }
+}
+
+impl AgentRegistration {
+    /// Closes a pending flag with `status`, unlocking the flagger's storage
+    /// for its pending entry.
+    fn review_flag(&mut self, flag_id: u64, status: FlagStatus) -> ListingFlag {
+        let caller = env::predecessor_account_id();
+        require!(
+            caller == self.arbiter_id || caller == self.owner_id,
+            "Only the arbiter or owner can review flags"
+        );
+        let mut flag = self
+            .listing_flags
+            .get(&flag_id)
+            .unwrap_or_else(|| env::panic_str("Flag not found"));
+        require!(flag.status == FlagStatus::Pending, "Flag was already reviewed");
+
+        // Dropping the pending entry frees more than `reviewed_at` adds
+        let initial_usage = env::storage_usage();
+        self.pending_flags.remove(&flag.agent_id);
+        flag.status = status;
+        flag.reviewed_at = Some(env::block_timestamp());
+        self.listing_flags.insert(&flag_id, &flag);
+        self.settle_storage(&flag.flagger, initial_usage);
+        flag
+    }
+
+    pub(crate) fn fund_curation_pool(&mut self, sender_id: &AccountId, amount: u128) {
+        self.curation_pool += amount;
+        events::emit(
+            "curation_pool_funded",
+            json!({ "account_id": sender_id, "amount": U128(amount) }),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::{testing_env, NearToken};
+
+    fn flagged_agent(contract: &mut AgentRegistration) -> u64 {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        register(contract, &mut context, sample_metadata());
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        contract.flag_listing(accounts(1), "Endpoint has been down for a month".to_string()).flag_id
+    }
+
+    #[test]
+    fn test_confirmed_flag_suspends_agent_and_pays_bounty() {
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        let flag_id = flagged_agent(&mut contract);
+        assert_eq!(contract.get_pending_flag(accounts(1)).unwrap().flag_id, flag_id);
+
+        testing_env!(get_context(accounts(0)).build());
+        contract.set_curation_bounty(U128(500));
+        contract.fund_curation_pool(&accounts(0), 300);
+        let flag = contract.confirm_flag(flag_id);
+
+        assert_eq!(flag.status, FlagStatus::Confirmed);
+        assert_eq!(flag.bounty_paid, 300);
+        assert_eq!(contract.get_curation_bounty(), CurationBounty { bounty: 500, pool: 0 });
+        assert_eq!(contract.get_agent_status(accounts(1)), Some(AgentStatus::Suspended));
+        assert_eq!(contract.get_pending_flag(accounts(1)), None);
+    }
+
+    #[test]
+    fn test_rejected_flag_allows_a_new_one() {
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        let flag_id = flagged_agent(&mut contract);
+        let locked = contract.storage_accounts.get(&accounts(2)).unwrap().locked_bytes;
+
+        testing_env!(get_context(accounts(0)).build());
+        assert_eq!(contract.reject_flag(flag_id).status, FlagStatus::Rejected);
+        assert_eq!(contract.get_agent_status(accounts(1)), Some(AgentStatus::Active));
+        assert!(contract.storage_accounts.get(&accounts(2)).unwrap().locked_bytes < locked);
+
+        testing_env!(get_context(accounts(3)).attached_deposit(NearToken::from_near(1)).build());
+        assert_eq!(contract.flag_listing(accounts(1), "Claims are fake".to_string()).flag_id, flag_id + 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Listing already has a pending flag")]
+    fn test_one_pending_flag_per_listing() {
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        flagged_agent(&mut contract);
+        testing_env!(get_context(accounts(3)).attached_deposit(NearToken::from_near(1)).build());
+        contract.flag_listing(accounts(1), "Same listing again".to_string());
+    }
+}
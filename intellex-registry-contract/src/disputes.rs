@@ -0,0 +1,189 @@
+use near_sdk::{env, near_bindgen, require};
+use serde_json::json;
+
+pub use intellex_types::disputes::{
+    Dispute, DisputeRuling, DISPUTE_RESOLUTION_PERIOD, FULL_AGENT_SHARE_BPS, MAX_DISPUTE_EVIDENCE_LENGTH,
+};
+
+use crate::events;
+use crate::tasks::TaskStatus;
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Freezes an assigned or submitted task's escrow until the arbiter
+    /// rules, e.g. when the creator rejects a result or the agent is never
+    /// approved. Creator or assigned agent only. The evidence (up to 1024
+    /// bytes) is charged to the caller's storage balance, topped up by the
+    /// attached deposit.
+    #[payable]
+    pub fn dispute_task(&mut self, task_id: u64, evidence: String) -> Dispute {
+        when_not_paused!(self);
+        require!(evidence.len() <= MAX_DISPUTE_EVIDENCE_LENGTH, "Dispute evidence exceeds 1024 bytes");
+        let mut task = self.get_task(task_id).unwrap_or_else(|| env::panic_str("Task not found"));
+        require!(
+            matches!(task.status, TaskStatus::Assigned | TaskStatus::Submitted),
+            "Only assigned or submitted tasks can be disputed"
+        );
+        let raised_by = env::predecessor_account_id();
+        require!(
+            raised_by == task.creator || task.agent_id.as_ref() == Some(&raised_by),
+            "Only the task creator or assigned agent can dispute it"
+        );
+
+        self.credit_storage_deposit(&raised_by);
+        let initial_usage = env::storage_usage();
+        let now = env::block_timestamp();
+        let dispute = Dispute {
+            task_id,
+            raised_by: raised_by.clone(),
+            evidence,
+            disputed_status: task.status,
+            raised_at: now,
+            deadline: now + DISPUTE_RESOLUTION_PERIOD,
+            ruling: None,
+            resolved_at: None,
+        };
+        self.disputes.insert(&task_id, &dispute);
+        task.status = TaskStatus::Disputed;
+        self.save_task(&mut task);
+        self.settle_storage(&raised_by, initial_usage);
+        events::emit(
+            "task_disputed",
+            json!({ "task_id": task_id, "raised_by": raised_by, "deadline": dispute.deadline }),
+        );
+        dispute
+    }
+
+    /// Settles a dispute before its deadline: splits the escrow as ruled and
+    /// reports the outcome to the reputation contract. Arbiter only; a DAO
+    /// arbiter calls this from a passed proposal.
+    pub fn resolve_dispute(&mut self, task_id: u64, ruling: DisputeRuling) -> Dispute {
+        when_not_paused!(self);
+        require!(env::predecessor_account_id() == self.arbiter_id, "Only the arbiter can resolve disputes");
+        require!(
+            ruling.agent_share_bps <= FULL_AGENT_SHARE_BPS,
+            "Agent share cannot exceed 10000 basis points"
+        );
+        let dispute = self.open_dispute(task_id);
+        require!(env::block_timestamp() <= dispute.deadline, "Dispute deadline has passed");
+        self.settle_dispute(dispute, ruling, false)
+    }
+
+    /// Settles a dispute the arbiter left past its deadline with the
+    /// dispute's fallback ruling. Callable by anyone.
+    pub fn settle_expired_dispute(&mut self, task_id: u64) -> Dispute {
+        when_not_paused!(self);
+        let dispute = self.open_dispute(task_id);
+        require!(env::block_timestamp() > dispute.deadline, "Dispute deadline has not passed");
+        let ruling = dispute.fallback_ruling();
+        self.settle_dispute(dispute, ruling, true)
+    }
+
+    pub fn get_dispute(&self, task_id: u64) -> Option<Dispute> {
+        self.disputes.get(&task_id)
+    }
+}
+
+impl AgentRegistration {
+    fn open_dispute(&self, task_id: u64) -> Dispute {
+        let dispute = self
+            .disputes
+            .get(&task_id)
+            .unwrap_or_else(|| env::panic_str("Task is not disputed"));
+        require!(dispute.ruling.is_none(), "Dispute is already resolved");
+        dispute
+    }
+
+    fn settle_dispute(&mut self, mut dispute: Dispute, ruling: DisputeRuling, expired: bool) -> Dispute {
+        let mut task = self.get_task(dispute.task_id).unwrap();
+        task.status = TaskStatus::Resolved;
+        self.save_task(&mut task);
+        self.queue_settled_task(task.task_id);
+        self.release_agent_task(task.agent_id.as_ref().unwrap());
+        self.pay_task_rewards(&task, ruling.agent_share_bps);
+        if ruling.report_failure {
+            self.report_task_result(&task, false);
+        } else if ruling.agent_share_bps == FULL_AGENT_SHARE_BPS {
+            self.report_task_result(&task, true);
+        }
+
+        dispute.ruling = Some(ruling);
+        dispute.resolved_at = Some(env::block_timestamp());
+        self.disputes.insert(&dispute.task_id, &dispute);
+        events::emit(
+            "dispute_resolved",
+            json!({
+                "task_id": dispute.task_id,
+                "agent_id": task.agent_id,
+                "agent_share_bps": ruling.agent_share_bps,
+                "report_failure": ruling.report_failure,
+                "expired": expired,
+            }),
+        );
+        dispute
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::json_types::U128;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::{testing_env, NearToken};
+
+    fn disputed_task(contract: &mut AgentRegistration, submit: bool) -> u64 {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        register(contract, &mut context, sample_metadata());
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(2)).build());
+        let task = contract.create_task("Translate a README".to_string(), Vec::new(), U128(10u128.pow(24)));
+        testing_env!(get_context(accounts(2)).build());
+        contract.assign_task(task.task_id, accounts(1));
+        if submit {
+            testing_env!(context.build());
+            contract.submit_result(task.task_id, "Done".to_string());
+        }
+        testing_env!(get_context(accounts(2)).block_timestamp(1_000).build());
+        contract.dispute_task(task.task_id, "The translation is machine output".to_string());
+        task.task_id
+    }
+
+    #[test]
+    fn test_arbiter_splits_disputed_escrow() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.set_arbiter(accounts(3));
+        let task_id = disputed_task(&mut contract, true);
+        let dispute = contract.get_dispute(task_id).unwrap();
+        assert_eq!((dispute.disputed_status, dispute.deadline), (TaskStatus::Submitted, 1_000 + DISPUTE_RESOLUTION_PERIOD));
+        assert_eq!(contract.get_task(task_id).unwrap().status, TaskStatus::Disputed);
+
+        testing_env!(get_context(accounts(3)).build());
+        let ruling = DisputeRuling { agent_share_bps: 3_000, report_failure: true };
+        let dispute = contract.resolve_dispute(task_id, ruling);
+        assert_eq!(dispute.ruling, Some(ruling));
+        assert_eq!(contract.get_task(task_id).unwrap().status, TaskStatus::Resolved);
+    }
+
+    #[test]
+    fn test_expired_dispute_falls_back() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        let task_id = disputed_task(&mut contract, false);
+
+        testing_env!(get_context(accounts(4)).block_timestamp(1_001 + DISPUTE_RESOLUTION_PERIOD).build());
+        let dispute = contract.settle_expired_dispute(task_id);
+        assert_eq!(dispute.ruling, Some(DisputeRuling { agent_share_bps: 0, report_failure: false }));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the arbiter can resolve disputes")]
+    fn test_only_arbiter_resolves() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        let task_id = disputed_task(&mut contract, true);
+        contract.resolve_dispute(task_id, DisputeRuling { agent_share_bps: 0, report_failure: true });
+    }
+}
@@ -0,0 +1,256 @@
+use near_sdk::store::IterableSet;
+use near_sdk::{borsh, env, near_bindgen, require, AccountId};
+use serde_json::json;
+
+pub use intellex_types::endorsements::SkillEndorsement;
+
+use crate::events;
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Vouches for a skill `agent_id` lists. The caller must be a registered
+    /// agent and can endorse each skill of a peer once. The attached
+    /// deposit, plus any storage balance, pays for the endorsement.
+    #[payable]
+    pub fn endorse_skill(&mut self, agent_id: AccountId, skill: String) {
+        when_not_paused!(self);
+        self.mirror_call("endorse_skill", || json!({ "agent_id": agent_id, "skill": skill }));
+        let endorser = env::predecessor_account_id();
+        require!(self.agents.contains_key(&endorser), "Only registered agents can endorse");
+        require!(endorser != agent_id, "Cannot endorse your own skill");
+        let agent = self
+            .agents
+            .get(&agent_id)
+            .unwrap_or_else(|| env::panic_str("Agent not registered"));
+        require!(agent.metadata.skills.contains(&skill), "Agent does not list this skill");
+        self.credit_storage_deposit(&endorser);
+        let initial_usage = env::storage_usage();
+
+        let key = (agent_id.clone(), skill.clone());
+        let mut endorsers = self
+            .skill_endorsers
+            .get(&key)
+            .unwrap_or_else(|| IterableSet::new(self.endorsers_prefix(&agent_id, &skill)));
+        require!(endorsers.insert(endorser.clone()), "Skill already endorsed");
+        self.skill_endorsers.insert(&key, &endorsers);
+
+        let mut skills = self
+            .endorsed_skills
+            .get(&agent_id)
+            .unwrap_or_else(|| IterableSet::new(self.agent_collection_prefix('E', &agent_id)));
+        skills.insert(skill.clone());
+        self.endorsed_skills.insert(&agent_id, &skills);
+        // Flush both sets before measuring what they took
+        drop((endorsers, skills));
+        self.settle_storage(&endorser, initial_usage);
+
+        events::emit(
+            "skill_endorsed",
+            json!({ "agent_id": agent_id, "skill": skill, "endorser": endorser }),
+        );
+    }
+
+    /// Withdraws the caller's endorsement, unlocking its storage.
+    pub fn revoke_endorsement(&mut self, agent_id: AccountId, skill: String) {
+        when_not_paused!(self);
+        let endorser = env::predecessor_account_id();
+        let key = (agent_id.clone(), skill.clone());
+        let mut endorsers = self
+            .skill_endorsers
+            .get(&key)
+            .filter(|endorsers| endorsers.contains(&endorser))
+            .unwrap_or_else(|| env::panic_str("Skill not endorsed"));
+        let initial_usage = env::storage_usage();
+
+        endorsers.remove(&endorser);
+        if endorsers.is_empty() {
+            self.skill_endorsers.remove(&key);
+            let mut skills = self.endorsed_skills.get(&agent_id).unwrap();
+            skills.remove(&skill);
+            if skills.is_empty() {
+                self.endorsed_skills.remove(&agent_id);
+            } else {
+                self.endorsed_skills.insert(&agent_id, &skills);
+            }
+        } else {
+            self.skill_endorsers.insert(&key, &endorsers);
+        }
+        drop(endorsers);
+        self.settle_storage(&endorser, initial_usage);
+
+        events::emit(
+            "endorsement_revoked",
+            json!({ "agent_id": agent_id, "skill": skill, "endorser": endorser }),
+        );
+    }
+
+    /// Endorsements of the skills `agent_id` still lists, highest weighted
+    /// score first. Reads every endorser to weigh them by current reputation.
+    pub fn get_skill_endorsements(&self, agent_id: AccountId) -> Vec<SkillEndorsement> {
+        let (Some(agent), Some(skills)) = (self.agents.get(&agent_id), self.endorsed_skills.get(&agent_id)) else {
+            return Vec::new();
+        };
+        let mut endorsements: Vec<SkillEndorsement> = skills
+            .iter()
+            .filter(|skill| agent.metadata.skills.contains(skill))
+            .map(|skill| {
+                let endorsers = self.skill_endorsers.get(&(agent_id.clone(), skill.clone())).unwrap();
+                SkillEndorsement {
+                    skill: skill.clone(),
+                    endorsement_count: endorsers.len() as u64,
+                    weighted_score: endorsers
+                        .iter()
+                        .filter_map(|endorser| self.get_agent_reputation(endorser))
+                        .sum(),
+                }
+            })
+            .collect();
+        endorsements.sort_by(|a, b| b.weighted_score.cmp(&a.weighted_score).then_with(|| a.skill.cmp(&b.skill)));
+        endorsements
+    }
+
+    pub fn has_endorsed(&self, endorser: AccountId, agent_id: AccountId, skill: String) -> bool {
+        self.skill_endorsers
+            .get(&(agent_id, skill))
+            .is_some_and(|endorsers| endorsers.contains(&endorser))
+    }
+}
+
+impl AgentRegistration {
+    /// Storage prefix of the endorser set for `agent_id`'s `skill`. Hashed, as
+    /// skills may contain any separator.
+    fn endorsers_prefix(&self, agent_id: &AccountId, skill: &String) -> Vec<u8> {
+        let collection = self.agent_collection_prefix('G', agent_id);
+        let mut prefix = b"G".to_vec();
+        prefix.extend(env::sha256(&borsh::to_vec(&(collection, skill)).unwrap()));
+        prefix
+    }
+
+    /// Re-keys `agent_id`'s endorsed skills and their endorser sets to
+    /// `new_owner`, one entry per endorsed skill.
+    pub(crate) fn move_endorsements(&mut self, agent_id: &AccountId, new_owner: &AccountId) {
+        let Some(skills) = self.endorsed_skills.remove(agent_id) else {
+            return;
+        };
+        for skill in skills.iter() {
+            if let Some(endorsers) = self.skill_endorsers.remove(&(agent_id.clone(), skill.clone())) {
+                self.skill_endorsers.insert(&(new_owner.clone(), skill.clone()), &endorsers);
+            }
+        }
+        self.endorsed_skills.insert(new_owner, &skills);
+    }
+
+    /// Drops the endorsements of `agent_id`'s skills, leaving the set entries
+    /// unreachable like `clear_reviews`.
+    pub(crate) fn clear_endorsements(&mut self, agent_id: &AccountId) {
+        let Some(skills) = self.endorsed_skills.remove(agent_id) else {
+            return;
+        };
+        for skill in skills.iter() {
+            self.skill_endorsers.remove(&(agent_id.clone(), skill.clone()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::staking::MIN_REGISTRATION_STAKE;
+    use crate::storage::storage_cost;
+    use crate::tests::{deposit_stake, get_context, register, sample_metadata};
+    use crate::AgentInfo;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+    use near_sdk::NearToken;
+
+    fn setup() -> AgentRegistration {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        for account in [accounts(1), accounts(2), accounts(3)] {
+            testing_env!(context.predecessor_account_id(account).build());
+            register(&mut contract, &mut context, sample_metadata());
+        }
+        contract
+    }
+
+    #[test]
+    fn test_endorsements_weighted_by_endorser_reputation() {
+        let mut contract = setup();
+        let info = AgentInfo { reputation: 30, task_history: Vec::new(), reputation_history: vec![(0, 30)] };
+        testing_env!(get_context(accounts(0)).build());
+        contract.update_agent_reputation(accounts(2), info, String::new(), None);
+
+        let locked = |contract: &AgentRegistration| {
+            let balance = contract.storage_balance_of(env::predecessor_account_id()).unwrap();
+            balance.total - balance.available
+        };
+        for endorser in [accounts(2), accounts(3)] {
+            testing_env!(get_context(endorser).attached_deposit(NearToken::from_near(1)).build());
+            let (initial_locked, initial_usage) = (locked(&contract), env::storage_usage());
+            contract.endorse_skill(accounts(1), "Rust".to_string());
+            let grown = env::storage_usage() - initial_usage;
+            assert_eq!(locked(&contract) - initial_locked, storage_cost(grown));
+        }
+        let rust = SkillEndorsement { skill: "Rust".to_string(), endorsement_count: 2, weighted_score: 30 };
+        assert_eq!(contract.get_skill_endorsements(accounts(1)), vec![rust]);
+
+        testing_env!(get_context(accounts(2)).build());
+        contract.revoke_endorsement(accounts(1), "Rust".to_string());
+        assert!(!contract.has_endorsed(accounts(2), accounts(1), "Rust".to_string()));
+        assert_eq!(contract.get_skill_endorsements(accounts(1))[0].weighted_score, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Skill already endorsed")]
+    fn test_endorse_once_per_skill() {
+        let mut contract = setup();
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        contract.endorse_skill(accounts(1), "Rust".to_string());
+        contract.endorse_skill(accounts(1), "Rust".to_string());
+    }
+
+    #[test]
+    fn test_endorsements_follow_a_transferred_agent() {
+        let mut contract = setup();
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        contract.endorse_skill(accounts(1), "Rust".to_string());
+
+        testing_env!(get_context(accounts(1)).build());
+        contract.transfer_agent_ownership(accounts(4));
+        deposit_stake(&mut contract, accounts(4), MIN_REGISTRATION_STAKE);
+        testing_env!(get_context(accounts(4)).attached_deposit(NearToken::from_near(1)).build());
+        contract.accept_agent_transfer(accounts(1));
+        assert!(contract.has_endorsed(accounts(2), accounts(4), "Rust".to_string()));
+        assert_eq!(contract.get_skill_endorsements(accounts(4))[0].endorsement_count, 1);
+
+        // The old account starts over, and its new endorser sets do not
+        // overlap the moved ones
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        register(&mut contract, &mut context, sample_metadata());
+        assert!(!contract.has_endorsed(accounts(2), accounts(1), "Rust".to_string()));
+        testing_env!(get_context(accounts(3)).attached_deposit(NearToken::from_near(1)).build());
+        contract.endorse_skill(accounts(1), "Rust".to_string());
+        assert_eq!(contract.get_skill_endorsements(accounts(1))[0].endorsement_count, 1);
+
+        testing_env!(get_context(accounts(2)).build());
+        contract.revoke_endorsement(accounts(4), "Rust".to_string());
+        assert!(contract.get_skill_endorsements(accounts(4)).is_empty());
+    }
+
+    #[test]
+    fn test_deregistration_drops_endorsements() {
+        let mut contract = setup();
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        contract.endorse_skill(accounts(1), "Rust".to_string());
+
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        contract.deregister_agent();
+        register(&mut contract, &mut context, sample_metadata());
+        assert!(!contract.has_endorsed(accounts(2), accounts(1), "Rust".to_string()));
+        assert!(contract.get_skill_endorsements(accounts(1)).is_empty());
+    }
+}
@@ -0,0 +1,28 @@
+use near_sdk::env;
+use serde_json::{json, Value};
+
+pub const EVENT_STANDARD: &str = "iaip";
+pub const EVENT_STANDARD_VERSION: &str = "1.0.0";
+pub const NFT_EVENT_STANDARD: &str = "nep171";
+pub const NFT_EVENT_STANDARD_VERSION: &str = "1.0.0";
+
+/// Logs a NEP-297 event: `EVENT_JSON:{"standard":"iaip","version":..,"event":..,"data":[data]}`.
+pub(crate) fn emit(event: &str, data: Value) {
+    log_event(EVENT_STANDARD, EVENT_STANDARD_VERSION, event, data);
+}
+
+/// Logs a NEP-171 event (`nft_mint`, `nft_transfer`, `nft_burn`) so NFT
+/// indexers pick up agent tokens.
+pub(crate) fn emit_nft(event: &str, data: Value) {
+    log_event(NFT_EVENT_STANDARD, NFT_EVENT_STANDARD_VERSION, event, data);
+}
+
+fn log_event(standard: &str, version: &str, event: &str, data: Value) {
+    let payload = json!({
+        "standard": standard,
+        "version": version,
+        "event": event,
+        "data": [data],
+    });
+    env::log_str(&format!("EVENT_JSON:{}", payload));
+}
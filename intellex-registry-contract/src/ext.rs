@@ -0,0 +1,60 @@
+//! Interfaces of the contracts the registry calls. Calls go through the
+//! generated `ext_*` builders instead of method names and hand-built JSON,
+//! so a wrong argument name or type fails to compile.
+
+use near_sdk::json_types::U128;
+use near_sdk::{ext_contract, AccountId};
+
+use crate::{AgentInfo, TaskResult};
+
+#[ext_contract(ext_reputation)]
+pub trait ReputationContract {
+    fn initialize_agent(&mut self, agent_id: AccountId);
+    fn get_agent_info(&self, agent_id: AccountId) -> Option<AgentInfo>;
+    fn add_task_result(&mut self, agent_id: AccountId, task_result: TaskResult);
+    fn remove_agent(&mut self, agent_id: AccountId);
+    fn transfer_agent(&mut self, agent_id: AccountId, new_agent_id: AccountId);
+}
+
+/// The NEP-141 calls made on ITLX.
+#[ext_contract(ext_ft)]
+pub trait FungibleToken {
+    fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>);
+}
+
+#[ext_contract(ext_social_db)]
+pub trait SocialDb {
+    fn get(&self, keys: Vec<String>) -> serde_json::Value;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::staking::MIN_REGISTRATION_STAKE;
+    use crate::tests::{deposit_stake, get_context, sample_metadata};
+    use crate::AgentRegistration;
+    use near_sdk::mock::MockAction;
+    use near_sdk::test_utils::{accounts, get_created_receipts};
+    use near_sdk::{testing_env, NearToken};
+
+    #[test]
+    fn test_reputation_calls_send_named_arguments() {
+        testing_env!(get_context(accounts(1)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(2));
+        deposit_stake(&mut contract, accounts(1), MIN_REGISTRATION_STAKE);
+        testing_env!(get_context(accounts(1)).attached_deposit(NearToken::from_near(1)).build());
+        contract.register_agent(sample_metadata(), None);
+
+        let call = get_created_receipts()
+            .into_iter()
+            .filter(|receipt| receipt.receiver_id == accounts(2))
+            .flat_map(|receipt| receipt.actions)
+            .find_map(|action| match action {
+                MockAction::FunctionCallWeight { method_name, args, .. } => Some((method_name, args)),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(call.0, b"initialize_agent");
+        let args: serde_json::Value = serde_json::from_slice(&call.1).unwrap();
+        assert_eq!(args, serde_json::json!({ "agent_id": accounts(1) }));
+    }
+}
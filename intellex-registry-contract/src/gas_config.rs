@@ -0,0 +1,63 @@
+use near_sdk::{env, near_bindgen, Gas};
+use serde_json::json;
+
+pub use intellex_types::gas_config::{GasConfig, CALLER_RESERVED_TGAS, MAX_TRANSACTION_TGAS};
+
+use crate::events;
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Changes the gas attached to outbound calls and their callbacks.
+    /// Promises already scheduled keep their gas. Owner only.
+    pub fn set_gas_config(&mut self, config: GasConfig) {
+        self.assert_owner();
+        if let Err(message) = config.validate() {
+            env::panic_str(message);
+        }
+        self.gas_config = config;
+        events::emit("gas_config_changed", json!({ "config": config }));
+    }
+
+    pub fn get_gas_config(&self) -> GasConfig {
+        self.gas_config
+    }
+}
+
+/// A `GasConfig` budget as `Gas`.
+pub(crate) fn tgas(budget: u64) -> Gas {
+    Gas::from_tgas(budget)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::get_context;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn test_owner_sets_gas_config() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        let config = GasConfig { reputation_call_tgas: 20, ..Default::default() };
+        contract.set_gas_config(config);
+        assert_eq!(contract.get_gas_config(), config);
+    }
+
+    #[test]
+    #[should_panic(expected = "Gas budgets must be at most 300 TGas")]
+    fn test_gas_budgets_must_fit_a_transaction() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.set_gas_config(GasConfig { ft_transfer_tgas: 301, ..Default::default() });
+    }
+
+    #[test]
+    #[should_panic(expected = "A call and its callback must leave 20 TGas of a transaction for the caller")]
+    fn test_call_and_callback_must_fit_together() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.set_gas_config(GasConfig { ft_transfer_tgas: 200, ft_callback_tgas: 100, ..Default::default() });
+    }
+}
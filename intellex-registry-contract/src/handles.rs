@@ -0,0 +1,226 @@
+use near_sdk::{env, near_bindgen, require, AccountId};
+
+pub use intellex_types::handles::{
+    HandleRecord, HandleStatus, HANDLE_RESERVATION_TTL_NS, MAX_HANDLE_LENGTH, MIN_HANDLE_LENGTH,
+};
+
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+/// Handles are lowercase ASCII letters, digits, `-` and `_`, and must start
+/// and end with a letter or digit.
+pub fn assert_valid_handle(handle: &str) {
+    require!(
+        (MIN_HANDLE_LENGTH..=MAX_HANDLE_LENGTH).contains(&handle.len()),
+        "Handle must be between 3 and 32 characters"
+    );
+    require!(
+        handle
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-' || b == b'_'),
+        "Handle may only contain lowercase letters, digits, '-' and '_'"
+    );
+    let first = handle.as_bytes()[0];
+    let last = handle.as_bytes()[handle.len() - 1];
+    require!(
+        first.is_ascii_alphanumeric() && last.is_ascii_alphanumeric(),
+        "Handle must start and end with a letter or digit"
+    );
+}
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Reserves a handle for the caller ahead of registering an agent.
+    pub fn reserve_handle(&mut self, handle: String) -> HandleRecord {
+        when_not_paused!(self);
+        assert_valid_handle(&handle);
+        let account_id = env::predecessor_account_id();
+        self.assert_handle_available(&handle, &account_id);
+        require!(
+            self.handle_holders.get(&account_id).is_none(),
+            "Account already holds a handle"
+        );
+
+        let now = env::block_timestamp();
+        let record = HandleRecord {
+            holder: account_id.clone(),
+            status: HandleStatus::Reserved {
+                expires_at: now + HANDLE_RESERVATION_TTL_NS,
+            },
+            updated_at: now,
+        };
+        self.handles.insert(&handle, &record);
+        self.handle_holders.insert(&account_id, &handle);
+        record
+    }
+
+    /// Binds a handle to the caller's agent, converting the caller's own
+    /// reservation if there is one and releasing any handle held before.
+    pub fn claim_handle(&mut self, handle: String) -> HandleRecord {
+        when_not_paused!(self);
+        assert_valid_handle(&handle);
+        let account_id = env::predecessor_account_id();
+        require!(self.agents.contains_key(&account_id), "Agent not registered");
+        self.assert_handle_available(&handle, &account_id);
+
+        if let Some(previous) = self.handle_holders.get(&account_id) {
+            if previous != handle {
+                self.handles.remove(&previous);
+            }
+        }
+
+        let record = HandleRecord {
+            holder: account_id.clone(),
+            status: HandleStatus::Claimed,
+            updated_at: env::block_timestamp(),
+        };
+        self.handles.insert(&handle, &record);
+        self.handle_holders.insert(&account_id, &handle);
+        self.bump_agent_version(&account_id);
+        record
+    }
+
+    /// Gives up the caller's handle or reservation.
+    pub fn release_handle(&mut self) {
+        when_not_paused!(self);
+        let account_id = env::predecessor_account_id();
+        let handle = self
+            .handle_holders
+            .remove(&account_id)
+            .unwrap_or_else(|| env::panic_str("Account holds no handle"));
+        self.handles.remove(&handle);
+        self.bump_agent_version(&account_id);
+    }
+
+    /// Hands the caller's handle to `new_holder`. A claimed handle may only
+    /// move to another registered agent; a reservation keeps its expiry.
+    pub fn transfer_handle(&mut self, new_holder: AccountId) -> HandleRecord {
+        when_not_paused!(self);
+        let account_id = env::predecessor_account_id();
+        require!(new_holder != account_id, "Cannot transfer a handle to yourself");
+        require!(
+            self.handle_holders.get(&new_holder).is_none(),
+            "Receiver already holds a handle"
+        );
+        let handle = self
+            .handle_holders
+            .get(&account_id)
+            .unwrap_or_else(|| env::panic_str("Account holds no handle"));
+        let mut record = self.handles.get(&handle).unwrap();
+        let now = env::block_timestamp();
+        require!(!record.is_expired(now), "Handle reservation has expired");
+        if record.status == HandleStatus::Claimed {
+            require!(
+                self.agents.contains_key(&new_holder),
+                "Claimed handles can only be transferred to registered agents"
+            );
+        }
+
+        record.holder = new_holder.clone();
+        record.updated_at = now;
+        self.handles.insert(&handle, &record);
+        self.handle_holders.remove(&account_id);
+        self.handle_holders.insert(&new_holder, &handle);
+        self.bump_agent_version(&account_id);
+        self.bump_agent_version(&new_holder);
+        record
+    }
+
+    /// Resolves a claimed handle to its agent. Reservations do not resolve.
+    pub fn get_agent_by_handle(&self, handle: String) -> Option<AccountId> {
+        self.handles
+            .get(&handle)
+            .filter(|record| record.status == HandleStatus::Claimed)
+            .map(|record| record.holder)
+    }
+
+    pub fn get_handle(&self, account_id: AccountId) -> Option<String> {
+        self.handle_holders.get(&account_id)
+    }
+
+    pub fn get_handle_record(&self, handle: String) -> Option<HandleRecord> {
+        let now = env::block_timestamp();
+        self.handles.get(&handle).filter(|record| !record.is_expired(now))
+    }
+}
+
+impl AgentRegistration {
+    /// Panics unless `handle` is free, held under a lapsed reservation, or
+    /// already held by `account_id`. Lapsed reservations are cleared here.
+    fn assert_handle_available(&mut self, handle: &String, account_id: &AccountId) {
+        if let Some(record) = self.handles.get(handle) {
+            if record.is_expired(env::block_timestamp()) {
+                self.handle_holders.remove(&record.holder);
+                self.handles.remove(handle);
+            } else {
+                require!(&record.holder == account_id, "Handle already taken");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn test_reserve_then_claim_handle() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+
+        contract.reserve_handle("acme-bot".to_string());
+        assert_eq!(contract.get_agent_by_handle("acme-bot".to_string()), None);
+
+        register(&mut contract, &mut context, sample_metadata());
+        contract.claim_handle("acme-bot".to_string());
+        assert_eq!(
+            contract.get_agent_by_handle("acme-bot".to_string()),
+            Some(accounts(1))
+        );
+
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        register(&mut contract, &mut context, sample_metadata());
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.transfer_handle(accounts(2));
+        assert_eq!(contract.get_handle(accounts(2)), Some("acme-bot".to_string()));
+        assert_eq!(contract.get_handle(accounts(1)), None);
+    }
+
+    #[test]
+    fn test_expired_reservation_can_be_taken() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.reserve_handle("acme".to_string());
+
+        testing_env!(context
+            .predecessor_account_id(accounts(2))
+            .block_timestamp(HANDLE_RESERVATION_TTL_NS + 1)
+            .build());
+        contract.reserve_handle("acme".to_string());
+        assert_eq!(contract.get_handle(accounts(2)), Some("acme".to_string()));
+        assert_eq!(contract.get_handle(accounts(1)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Handle already taken")]
+    fn test_cannot_claim_reserved_handle() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.reserve_handle("acme".to_string());
+
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        register(&mut contract, &mut context, sample_metadata());
+        contract.claim_handle("acme".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Handle may only contain")]
+    fn test_invalid_handle_rejected() {
+        assert_valid_handle("Acme Bot");
+    }
+}
@@ -0,0 +1,106 @@
+use near_sdk::{borsh, env, near_bindgen};
+
+pub use intellex_types::health::{CollectionCounts, HealthReport, PausedScopes, PendingBacklog};
+
+use crate::audit_log::to_hex;
+use crate::storage::storage_cost;
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// One call for ops to alert on: storage headroom, collection sizes,
+    /// queued work, paused scopes and a checksum of the configuration.
+    pub fn health_check(&self) -> HealthReport {
+        let storage_usage = env::storage_usage();
+        let account_balance = env::account_balance().as_yoctonear();
+        let byte_cost = env::storage_byte_cost().as_yoctonear();
+        let affordable_bytes = u64::try_from(account_balance / byte_cost).unwrap_or(u64::MAX);
+        HealthReport {
+            storage_usage,
+            account_balance,
+            storage_cost: storage_cost(storage_usage),
+            storage_headroom_bytes: affordable_bytes.saturating_sub(storage_usage),
+            counts: CollectionCounts {
+                agents: self.total_agents,
+                tasks: self.next_task_id,
+                listing_flags: self.next_flag_id,
+            },
+            backlog: PendingBacklog {
+                reconciliation_queue: self.reconciliation_queue.len() as u64,
+                held_reputation_updates: self.get_held_reputation_update_count(),
+            },
+            paused: PausedScopes {
+                registry: self.paused,
+                registration: self.registration_paused,
+                reputation_updates: self.reputation_updates_paused,
+            },
+            config_checksum: self.config_checksum(),
+        }
+    }
+}
+
+impl AgentRegistration {
+    fn config_checksum(&self) -> String {
+        let config = (
+            &self.owner_id,
+            &self.pending_owner_id,
+            &self.reputation_mode,
+            &self.treasury_id,
+            self.protocol_fee_bps,
+            &self.arbiter_id,
+            self.curation_bounty,
+            &self.reputation_engine,
+            &self.ranked_skills,
+            self.invite_only,
+            &self.mirrored_methods,
+            self.strict_skill_taxonomy,
+        );
+        to_hex(&env::sha256(&borsh::to_vec(&config).unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+    use near_sdk::NearToken;
+
+    #[test]
+    fn test_health_check_reports_state() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        testing_env!(get_context(accounts(0)).build());
+        contract.pause_registration();
+
+        let report = contract.health_check();
+        assert_eq!(report.counts, CollectionCounts { agents: 1, tasks: 0, listing_flags: 0 });
+        assert_eq!(report.backlog, PendingBacklog { reconciliation_queue: 0, held_reputation_updates: 0 });
+        assert_eq!(report.paused, PausedScopes { registry: false, registration: true, reputation_updates: false });
+        assert_eq!(report.storage_usage, env::storage_usage());
+        assert!(report.storage_headroom_bytes > 0);
+    }
+
+    #[test]
+    fn test_headroom_is_zero_when_underfunded() {
+        testing_env!(get_context(accounts(0)).account_balance(NearToken::from_yoctonear(0)).build());
+        let contract = AgentRegistration::new(accounts(0), accounts(0));
+        let report = contract.health_check();
+        assert_eq!(report.storage_headroom_bytes, 0);
+        assert!(report.storage_cost > report.account_balance);
+    }
+
+    #[test]
+    fn test_config_checksum_tracks_config() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        let before = contract.health_check().config_checksum;
+        contract.pause();
+        assert_eq!(contract.health_check().config_checksum, before);
+        contract.set_arbiter(accounts(2));
+        assert_ne!(contract.health_check().config_checksum, before);
+    }
+}
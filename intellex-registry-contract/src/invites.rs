@@ -0,0 +1,354 @@
+use near_sdk::store::Vector;
+use near_sdk::{env, near_bindgen, require, AccountId};
+use serde_json::json;
+
+pub use intellex_types::invites::{
+    InviteCode, InviteRedemption, Referral, MAX_COHORT_LENGTH, MAX_INVITE_MINT_BATCH, MAX_OPEN_REFERRAL_CODES,
+    MIN_REFERRER_REPUTATION, REFERRAL_COHORT,
+};
+
+use crate::audit_log::{from_hex, to_hex};
+use crate::events;
+use crate::pagination::{page_limit, paginate, sequence_start, CursorKey, Page};
+use crate::{AgentMetadata, AgentRegistration, AgentRegistrationExt, AgentStatus};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Adds single-use invite codes, each given as the hex SHA-256 of the
+    /// code. Owner only; the registry pays for their storage.
+    pub fn mint_invite_codes(&mut self, invites: Vec<InviteCode>) {
+        self.assert_owner();
+        require!(!invites.is_empty(), "No invite codes to mint");
+        require!(
+            invites.len() <= MAX_INVITE_MINT_BATCH,
+            "Cannot mint more than 100 invite codes at once"
+        );
+        let count = invites.len();
+        for invite in invites {
+            self.insert_invite_code(invite);
+        }
+        events::emit("invite_codes_minted", json!({ "count": count }));
+    }
+
+    /// Adds a single-use referral code, given as the hex SHA-256 of the
+    /// code, for the caller to hand out. Registering with it waives the
+    /// ITLX stake minimum and counts as the caller's referral. Active agents
+    /// with at least `MIN_REFERRER_REPUTATION` only; the attached deposit,
+    /// plus any storage balance, pays for the code until it is redeemed or
+    /// revoked.
+    #[payable]
+    pub fn create_invite(&mut self, code_hash: String, expires_at: Option<u64>, bound_to: Option<AccountId>) -> InviteCode {
+        when_not_paused!(self);
+        let inviter = env::predecessor_account_id();
+        let agent = self.owned_agent(&inviter);
+        require!(agent.status == AgentStatus::Active, "Agent is not active");
+        require!(
+            agent.reputation_info.reputation >= MIN_REFERRER_REPUTATION,
+            "Inviting takes a reputation of at least 70"
+        );
+        let mut open_codes = self.open_referral_codes.get(&inviter).unwrap_or_default();
+        require!(open_codes.len() < MAX_OPEN_REFERRAL_CODES as usize, "An agent can hold at most 5 unredeemed invites");
+        self.credit_storage_deposit(&inviter);
+        let initial_usage = env::storage_usage();
+
+        let invite = InviteCode { code_hash, cohort: REFERRAL_COHORT.to_string(), expires_at, bound_to };
+        let invite = self.insert_invite_code(invite);
+        self.referral_codes.insert(&invite.code_hash, &inviter);
+        open_codes.push(invite.code_hash.clone());
+        self.open_referral_codes.insert(&inviter, &open_codes);
+        self.settle_storage(&inviter, initial_usage);
+        events::emit("invite_created", json!({ "inviter": inviter, "code_hash": invite.code_hash }));
+        invite
+    }
+
+    /// Withdraws an unredeemed code. Owner only, or the agent that created
+    /// a referral code, which gets its storage back.
+    pub fn revoke_invite_code(&mut self, code_hash: String) {
+        when_not_paused!(self);
+        let code_hash = code_hash.to_lowercase();
+        let caller = env::predecessor_account_id();
+        let inviter = self.referral_codes.get(&code_hash);
+        require!(
+            caller == self.owner_id || inviter.as_ref() == Some(&caller),
+            "Only the owner or the inviter can revoke an invite code"
+        );
+        let initial_usage = env::storage_usage();
+        require!(self.invite_codes.remove(&code_hash).is_some(), "Invite code not found");
+        if let Some(inviter) = inviter {
+            self.forget_referral_code(&code_hash, &inviter);
+            self.settle_storage(&inviter, initial_usage);
+        }
+        events::emit("invite_code_revoked", json!({ "code_hash": code_hash }));
+    }
+
+    /// `register_agent` with an invite code.
+    #[payable]
+    pub fn register_agent_with_invite(&mut self, metadata: AgentMetadata, code: String) {
+        self.register_agent(metadata, Some(code));
+    }
+
+    /// Makes `register_agent` require an invite code. Owner only.
+    pub fn enable_invite_only(&mut self) {
+        self.assert_owner();
+        require!(!self.invite_only, "Registration is already invite-only");
+        self.invite_only = true;
+        events::emit("invite_only_enabled", json!({ "by": self.owner_id }));
+    }
+
+    pub fn disable_invite_only(&mut self) {
+        self.assert_owner();
+        require!(self.invite_only, "Registration is not invite-only");
+        self.invite_only = false;
+        events::emit("invite_only_disabled", json!({ "by": self.owner_id }));
+    }
+
+    pub fn is_invite_only(&self) -> bool {
+        self.invite_only
+    }
+
+    /// An unredeemed code, by the hex SHA-256 of the code.
+    pub fn get_invite_code(&self, code_hash: String) -> Option<InviteCode> {
+        self.invite_codes.get(&code_hash)
+    }
+
+    pub fn get_agent_invite(&self, agent_id: AccountId) -> Option<InviteRedemption> {
+        self.agent_invites.get(&agent_id)
+    }
+
+    /// Codes redeemed for `cohort`, including by agents since deregistered.
+    pub fn get_cohort_size(&self, cohort: String) -> u64 {
+        self.cohort_sizes.get(&cohort).unwrap_or_default()
+    }
+
+    /// Agents that registered with `agent_id`'s referral codes, oldest
+    /// first, including ones since deregistered.
+    pub fn get_referrals(&self, agent_id: AccountId, cursor: Option<String>, limit: Option<u64>) -> Page<Referral> {
+        let start = sequence_start("get_referrals", cursor);
+
+        self.referrals
+            .get(&agent_id)
+            .map(|referrals| {
+                let referrals = referrals
+                    .iter()
+                    .enumerate()
+                    .skip(start as usize)
+                    .map(|(i, referral)| (CursorKey::Sequence(i as u64), referral.clone()));
+                paginate("get_referrals", referrals, page_limit(limit))
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn get_referral_count(&self, agent_id: AccountId) -> u32 {
+        self.referrals.get(&agent_id).map_or(0, |referrals| referrals.len())
+    }
+}
+
+impl AgentRegistration {
+    fn insert_invite_code(&mut self, mut invite: InviteCode) -> InviteCode {
+        invite.code_hash = invite.code_hash.to_lowercase();
+        require!(
+            invite.code_hash.len() == 64 && from_hex(&invite.code_hash).is_some(),
+            "Invite code hash must be 32 bytes of hex"
+        );
+        require!(!invite.cohort.is_empty(), "Cohort must not be empty");
+        require!(invite.cohort.len() <= MAX_COHORT_LENGTH, "Cohort exceeds 64 bytes");
+        require!(!self.invite_codes.contains_key(&invite.code_hash), "Invite code already minted");
+        self.invite_codes.insert(&invite.code_hash, &invite);
+        invite
+    }
+
+    fn forget_referral_code(&mut self, code_hash: &String, inviter: &AccountId) {
+        self.referral_codes.remove(code_hash);
+        let mut open_codes = self.open_referral_codes.get(inviter).unwrap_or_default();
+        open_codes.retain(|open_code| open_code != code_hash);
+        if open_codes.is_empty() {
+            self.open_referral_codes.remove(inviter);
+        } else {
+            self.open_referral_codes.insert(inviter, &open_codes);
+        }
+    }
+
+    /// Drops the referral codes `inviter` has not seen redeemed. Storage is
+    /// left for the caller to settle.
+    pub(crate) fn clear_referral_codes(&mut self, inviter: &AccountId) {
+        for code_hash in self.open_referral_codes.remove(inviter).unwrap_or_default() {
+            self.invite_codes.remove(&code_hash);
+            self.referral_codes.remove(&code_hash);
+        }
+    }
+
+    /// Checks `code` for `account_id` and burns it, before the registrant's
+    /// storage is measured so they are not credited for the freed code; a
+    /// referral code's storage goes back to its inviter, which is returned.
+    pub(crate) fn take_invite_code(&mut self, account_id: &AccountId, code: &str) -> (InviteCode, Option<AccountId>) {
+        let code_hash = to_hex(&env::sha256(code.as_bytes()));
+        let invite = self
+            .invite_codes
+            .get(&code_hash)
+            .unwrap_or_else(|| env::panic_str("Invalid invite code"));
+        require!(
+            invite.expires_at.is_none_or(|expires_at| env::block_timestamp() < expires_at),
+            "Invite code has expired"
+        );
+        require!(
+            invite.bound_to.as_ref().is_none_or(|bound_to| bound_to == account_id),
+            "Invite code is bound to another account"
+        );
+        let initial_usage = env::storage_usage();
+        self.invite_codes.remove(&code_hash);
+        let inviter = self.referral_codes.get(&code_hash);
+        if let Some(inviter) = &inviter {
+            require!(self.agents.contains_key(inviter), "The inviting agent is no longer registered");
+            self.forget_referral_code(&code_hash, inviter);
+            self.settle_storage(inviter, initial_usage);
+        }
+        (invite, inviter)
+    }
+
+    /// Records the cohort of a newly registered agent, and the referral if
+    /// `inviter` created its code.
+    pub(crate) fn record_invite_redemption(&mut self, agent_id: &AccountId, invite: InviteCode, inviter: Option<AccountId>) {
+        let redemption = InviteRedemption {
+            code_hash: invite.code_hash,
+            cohort: invite.cohort,
+            redeemed_at: env::block_timestamp(),
+        };
+        let cohort_size = self.cohort_sizes.get(&redemption.cohort).unwrap_or_default();
+        self.cohort_sizes.insert(&redemption.cohort, &(cohort_size + 1));
+        self.agent_invites.insert(agent_id, &redemption);
+        if let Some(inviter) = &inviter {
+            let mut referrals = self
+                .referrals
+                .get(inviter)
+                .unwrap_or_else(|| Vector::new(format!("3_{}", inviter).into_bytes()));
+            referrals.push(Referral { agent_id: agent_id.clone(), referred_at: redemption.redeemed_at });
+            self.referrals.insert(inviter, &referrals);
+        }
+        events::emit(
+            "invite_redeemed",
+            json!({
+                "agent_id": agent_id,
+                "code_hash": redemption.code_hash,
+                "cohort": redemption.cohort,
+                "inviter": inviter,
+            }),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{deposit_stake, get_context, register, sample_metadata};
+    use crate::AgentInfo;
+    use crate::staking::MIN_REGISTRATION_STAKE;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+    use near_sdk::NearToken;
+
+    fn invite(code: &str, bound_to: Option<AccountId>) -> InviteCode {
+        InviteCode {
+            code_hash: to_hex(&env::sha256(code.as_bytes())),
+            cohort: "beta-1".to_string(),
+            expires_at: Some(1_000),
+            bound_to,
+        }
+    }
+
+    fn setup() -> AgentRegistration {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.mint_invite_codes(vec![invite("open-code", None), invite("bob-code", Some(accounts(1)))]);
+        contract.enable_invite_only();
+        deposit_stake(&mut contract, accounts(1), MIN_REGISTRATION_STAKE);
+        deposit_stake(&mut contract, accounts(2), MIN_REGISTRATION_STAKE);
+        contract
+    }
+
+    #[test]
+    fn test_invite_is_redeemed_once_and_recorded() {
+        let mut contract = setup();
+        testing_env!(get_context(accounts(1)).attached_deposit(NearToken::from_near(1)).build());
+        contract.register_agent(sample_metadata(), Some("bob-code".to_string()));
+
+        let redemption = contract.get_agent_invite(accounts(1)).unwrap();
+        assert_eq!(redemption.cohort, "beta-1");
+        assert_eq!(contract.get_invite_code(redemption.code_hash), None);
+        assert_eq!(contract.get_cohort_size("beta-1".to_string()), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invite code is bound to another account")]
+    fn test_bound_invite_rejects_other_accounts() {
+        let mut contract = setup();
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        contract.register_agent(sample_metadata(), Some("bob-code".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Registration requires an invite code")]
+    fn test_invite_only_requires_code() {
+        let mut contract = setup();
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        contract.register_agent(sample_metadata(), None);
+    }
+
+    #[test]
+    fn test_referral_code_waives_stake_and_is_tracked() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        let info = AgentInfo { reputation: 75, task_history: Vec::new(), reputation_history: vec![(0, 75)] };
+        testing_env!(get_context(accounts(0)).build());
+        contract.update_agent_reputation(accounts(1), info, String::new(), None);
+
+        testing_env!(get_context(accounts(1)).attached_deposit(NearToken::from_near(1)).build());
+        let locked = |contract: &AgentRegistration| {
+            let balance = contract.storage_balance_of(accounts(1)).unwrap();
+            balance.total - balance.available
+        };
+        let locked_before = locked(&contract);
+        contract.create_invite(to_hex(&env::sha256(b"friend-code")), None, Some(accounts(2)));
+        assert!(locked(&contract) > locked_before);
+
+        // accounts(2) never staked
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        contract.register_agent_with_invite(sample_metadata(), "friend-code".to_string());
+        assert_eq!(locked(&contract), locked_before);
+        assert_eq!(contract.get_agent_invite(accounts(2)).unwrap().cohort, REFERRAL_COHORT);
+        let referrals = contract.get_referrals(accounts(1), None, None).items;
+        assert_eq!(referrals.iter().map(|referral| &referral.agent_id).collect::<Vec<_>>(), vec![&accounts(2)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Inviting takes a reputation of at least 70")]
+    fn test_low_reputation_agents_cannot_invite() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        testing_env!(get_context(accounts(1)).attached_deposit(NearToken::from_near(1)).build());
+        contract.create_invite(to_hex(&env::sha256(b"friend-code")), None, None);
+    }
+
+    #[test]
+    fn test_referral_codes_are_revoked_by_any_case_and_dropped_on_deregistration() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        let info = AgentInfo { reputation: 75, task_history: Vec::new(), reputation_history: vec![(0, 75)] };
+        testing_env!(get_context(accounts(0)).build());
+        contract.update_agent_reputation(accounts(1), info, String::new(), None);
+
+        testing_env!(get_context(accounts(1)).attached_deposit(NearToken::from_near(1)).build());
+        let revoked = contract.create_invite(to_hex(&env::sha256(b"revoked-code")), None, None);
+        let open = contract.create_invite(to_hex(&env::sha256(b"open-code")), None, None);
+        contract.revoke_invite_code(revoked.code_hash.to_uppercase());
+        assert_eq!(contract.get_invite_code(revoked.code_hash), None);
+
+        contract.deregister_agent();
+        assert_eq!(contract.get_invite_code(open.code_hash), None);
+    }
+}
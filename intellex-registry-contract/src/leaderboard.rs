@@ -0,0 +1,116 @@
+use near_sdk::{near_bindgen, AccountId};
+use serde_json::json;
+
+use crate::events;
+use crate::pagination::page_limit;
+use crate::rankings::RankedAgent;
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+/// Key of an agent in `reputation_ranking`. Reputation is inverted so an
+/// in-order walk yields the highest first, ties broken by account id.
+fn ranking_key(reputation: u64, agent_id: &AccountId) -> (u64, AccountId) {
+    (u64::MAX - reputation, agent_id.clone())
+}
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Active agents by reputation, highest first, ties broken by account
+    /// id. `limit` defaults to 50, at most 100.
+    pub fn get_top_agents(&self, limit: Option<u64>) -> Vec<RankedAgent> {
+        self.top_agents(page_limit(limit), |_| true)
+    }
+
+    /// Like `get_top_agents`, restricted to agents listing `skill`. Walks the
+    /// overall ranking, so a skill whose agents rank low costs more gas.
+    pub fn get_top_agents_by_skill(&self, skill: String, limit: Option<u64>) -> Vec<RankedAgent> {
+        let Some(skill_agents) = self.skill_agents(&skill) else {
+            return Vec::new();
+        };
+        self.top_agents(page_limit(limit), |agent_id| skill_agents.contains(agent_id))
+    }
+
+    /// Adds agents registered before the ranking existed to it. Agents
+    /// already ranked are left alone. Owner only.
+    pub fn migrate_reputation_ranking(&mut self, agent_ids: Vec<AccountId>) -> u64 {
+        self.assert_owner();
+        let mut ranked = 0;
+        for agent_id in agent_ids {
+            let Some(agent) = self.agents.get(&agent_id) else {
+                continue;
+            };
+            let key = ranking_key(agent.reputation_info.reputation, &agent_id);
+            if self.reputation_ranking.insert(&key, &()).is_none() {
+                ranked += 1;
+            }
+        }
+        events::emit("reputation_ranking_migrated", json!({ "ranked": ranked }));
+        ranked
+    }
+}
+
+impl AgentRegistration {
+    /// Moves an agent in the ranking from `old` to `new` reputation; `None`
+    /// on either side adds or drops it.
+    pub(crate) fn rerank_agent(&mut self, agent_id: &AccountId, old: Option<u64>, new: Option<u64>) {
+        if old == new {
+            return;
+        }
+        if let Some(reputation) = old {
+            self.reputation_ranking.remove(&ranking_key(reputation, agent_id));
+        }
+        if let Some(reputation) = new {
+            self.reputation_ranking.insert(&ranking_key(reputation, agent_id), &());
+        }
+    }
+
+    fn top_agents(&self, limit: usize, include: impl Fn(&AccountId) -> bool) -> Vec<RankedAgent> {
+        self.reputation_ranking
+            .iter()
+            .map(|((inverted, agent_id), _)| RankedAgent { agent_id, reputation: u64::MAX - inverted })
+            .filter(|ranked| include(&ranked.agent_id) && self.is_agent_active(&ranked.agent_id))
+            .take(limit)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use crate::AgentInfo;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn test_top_agents_follow_reputation_changes() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        for account in [accounts(1), accounts(2), accounts(3)] {
+            testing_env!(context.predecessor_account_id(account.clone()).build());
+            let mut metadata = sample_metadata();
+            if account == accounts(3) {
+                metadata.skills = vec!["Go".to_string()];
+            }
+            register(&mut contract, &mut context, metadata);
+        }
+
+        testing_env!(get_context(accounts(0)).build());
+        for (agent_id, reputation) in [(accounts(2), 50), (accounts(3), 80), (accounts(2), 40)] {
+            let prev_tip = contract.get_history_tip(agent_id.clone());
+            let info = AgentInfo { reputation, task_history: Vec::new(), reputation_history: Vec::new() };
+            contract.update_agent_reputation(agent_id, info, prev_tip, None);
+        }
+
+        let ranked = |agent_id: AccountId, reputation| RankedAgent { agent_id, reputation };
+        assert_eq!(
+            contract.get_top_agents(None),
+            vec![ranked(accounts(3), 80), ranked(accounts(2), 40), ranked(accounts(1), 0)]
+        );
+        assert_eq!(contract.get_top_agents(Some(1)), vec![ranked(accounts(3), 80)]);
+        assert_eq!(
+            contract.get_top_agents_by_skill("rust".to_string(), None),
+            vec![ranked(accounts(2), 40), ranked(accounts(1), 0)]
+        );
+    }
+}
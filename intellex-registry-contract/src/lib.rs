@@ -0,0 +1,1036 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::{LookupMap, TreeMap};
+use near_sdk::store::{IterableMap, IterableSet, Vector};
+use near_sdk::{env, near_bindgen, AccountId, Promise, PanicOnDefault, require};
+
+use crate::gas_config::tgas;
+use crate::reputation_calls::PendingReputationCall;
+use crate::skill_history::SkillChangeKind;
+use crate::skills_registry::{lists_skill, skill_lookup_key};
+
+const ITLX_TOKEN_CONTRACT: &str = "itlx.token.near"; // Replace with actual ITLX token contract
+const LOCAL_REPUTATION_UNAVAILABLE: &str = "Local reputation needs a build with the reputation-engine feature";
+
+// Shared types live in intellex-types so off-chain consumers can use them
+// without near-sdk; re-exported here so contract paths stay unchanged.
+pub use intellex_types::{Agent, AgentInfo, AgentMetadata, AgentStatus, TaskResult};
+pub use intellex_types::reputation::ReputationBreakdown;
+pub use intellex_types as types;
+pub use intellex_types::reputation_engine::{ReputationEngineConfig, ReputationMode};
+
+/// Panics while the registry is paused. Every mutating endpoint except the
+/// owner's admin methods and promise callbacks starts with this guard.
+macro_rules! when_not_paused {
+    ($contract:expr) => {
+        near_sdk::require!(!$contract.paused, "Registry is paused")
+    };
+}
+
+pub mod admin;
+pub mod agent_nft;
+pub mod agent_service;
+pub mod agent_skills;
+pub mod agent_transfer;
+pub mod audit_log;
+pub mod availability;
+pub mod bands;
+pub mod batch;
+pub mod continuity;
+pub mod credentials;
+pub mod curation;
+pub mod disputes;
+pub mod endorsements;
+pub mod events;
+pub mod ext;
+pub mod gas_config;
+pub mod handles;
+pub mod health;
+pub mod invites;
+pub mod leaderboard;
+pub mod matching;
+pub mod metadata_reference;
+pub mod metrics;
+pub mod migration;
+pub mod mirroring;
+pub mod operators;
+pub mod pagination;
+pub mod protocol;
+pub mod rankings;
+pub mod registration_access;
+pub mod registration_limits;
+pub mod registry_config;
+pub mod registry_stats;
+pub mod reputation_calls;
+#[cfg(feature = "reputation-engine")]
+pub mod reputation_engine;
+pub mod reputation_history;
+pub mod reputation_push;
+pub mod reputation_sync;
+pub mod requester_lists;
+pub mod reviews;
+pub mod search;
+pub mod skill_history;
+pub mod skill_versions;
+pub mod skills_registry;
+pub mod slashing;
+pub mod social;
+pub mod staking;
+pub mod status;
+pub mod storage;
+pub mod storage_estimate;
+pub mod system_skills;
+pub mod task_archive;
+pub mod task_history;
+pub mod tasks;
+pub mod teams;
+pub mod trending;
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
+pub struct AgentRegistration {
+    state_version: migration::StateVersion,
+    owner_id: AccountId,
+    // Circuit breaker: freezes every mutating endpoint but the admin ones
+    paused: bool,
+    // Set by `propose_owner`; becomes owner on `accept_ownership`
+    pending_owner_id: Option<AccountId>,
+    registration_paused: bool,
+    agents: migration::AgentStore,
+    skills_index: LookupMap<String, IterableSet<AccountId>>,
+    total_agents: u64,
+    reputation_mode: ReputationMode,
+    // Scoring rules for `ReputationMode::Local`
+    reputation_engine: ReputationEngineConfig,
+    // Limits on agent metadata
+    registry_config: registry_config::RegistryConfig,
+    handles: LookupMap<String, handles::HandleRecord>,
+    handle_holders: LookupMap<AccountId, String>,
+    skill_versions: LookupMap<String, IterableSet<String>>,
+    audit_logs: LookupMap<AccountId, Vector<audit_log::AuditAnchor>>,
+    history_tips: LookupMap<AccountId, String>,
+    // Nonce of each agent's last incremental reputation push
+    reputation_push_nonces: LookupMap<AccountId, u64>,
+    reconciliation_queue: IterableMap<AccountId, continuity::ReconciliationItem>,
+    availability: LookupMap<AccountId, availability::Availability>,
+    last_pings: LookupMap<AccountId, u64>,
+    blocklists: LookupMap<AccountId, IterableSet<String>>,
+    watchlists: LookupMap<AccountId, IterableSet<AccountId>>,
+    stakes: LookupMap<AccountId, staking::StakeInfo>,
+    slash_history: LookupMap<AccountId, Vector<slashing::SlashEvent>>,
+    // Slashed ITLX, held by the registry and no longer owed to any agent
+    total_slashed: u128,
+    skill_history: LookupMap<AccountId, Vector<skill_history::SkillChange>>,
+    // Latest task results per agent, and counts over all of them
+    task_histories: LookupMap<AccountId, Vector<TaskResult>>,
+    task_stats: LookupMap<AccountId, task_history::TaskStats>,
+    task_history_window: u32,
+    reputation_updates_paused: bool,
+    // FIFO of pushes received while paused, keyed by sequence number
+    held_reputation_updates: LookupMap<u64, continuity::HeldReputationUpdate>,
+    held_updates_head: u64,
+    held_updates_tail: u64,
+    social_links: LookupMap<AccountId, social::SocialLink>,
+    // Successful links per agent; feeds the nonce so old proofs go stale
+    social_link_counts: LookupMap<AccountId, u64>,
+    storage_accounts: LookupMap<AccountId, storage::StorageAccount>,
+    // Agent -> account proposed to take it over with `accept_agent_transfer`
+    pending_agent_transfers: LookupMap<AccountId, AccountId>,
+    tasks: LookupMap<u64, tasks::Task>,
+    next_task_id: u64,
+    // Agent -> its assigned, submitted or disputed tasks
+    agent_open_tasks: LookupMap<AccountId, u32>,
+    protocol_fee_bps: u16,
+    treasury_id: AccountId,
+    // ITLX task payouts whose `ft_transfer` failed, claimable by the receiver
+    itlx_payouts_owed: LookupMap<AccountId, u128>,
+    // Rules on disputed tasks; an account or a DAO contract
+    arbiter_id: AccountId,
+    disputes: LookupMap<u64, disputes::Dispute>,
+    listing_flags: LookupMap<u64, curation::ListingFlag>,
+    // Agent -> its pending flag; one open flag per listing at a time
+    pending_flags: LookupMap<AccountId, u64>,
+    next_flag_id: u64,
+    // ITLX paid per confirmed flag, out of `curation_pool`
+    curation_bounty: u128,
+    curation_pool: u128,
+    // Agent -> when its reputation was last pushed or pulled, starting at
+    // registration. Holds every agent registered since it was added, so it
+    // doubles as the list of agents to rank.
+    reputation_synced_at: IterableMap<AccountId, u64>,
+    ranking_snapshots: LookupMap<u64, rankings::RankingSnapshot>,
+    latest_ranking_epoch: Option<u64>,
+    // Skills that get their own leaderboard in each snapshot
+    ranked_skills: Vec<String>,
+    reputation_breakdowns: LookupMap<AccountId, ReputationBreakdown>,
+    // Registration needs an invite code while set
+    invite_only: bool,
+    // Hex SHA-256 of an unredeemed code -> the code
+    invite_codes: LookupMap<String, invites::InviteCode>,
+    agent_invites: LookupMap<AccountId, invites::InviteRedemption>,
+    // Hex SHA-256 of an unredeemed referral code -> the agent that created it
+    referral_codes: LookupMap<String, AccountId>,
+    // Agent -> its referral codes not yet redeemed or revoked
+    open_referral_codes: LookupMap<AccountId, Vec<String>>,
+    // Agent -> agents registered with its referral codes
+    referrals: LookupMap<AccountId, Vector<invites::Referral>>,
+    // Cohort -> codes redeemed for it
+    cohort_sizes: LookupMap<String, u64>,
+    agent_reviews: LookupMap<AccountId, Vector<reviews::Review>>,
+    review_stats: LookupMap<AccountId, reviews::ReviewStats>,
+    // Task -> index of its review in the agent's reviews
+    task_reviews: LookupMap<u64, u32>,
+    // Skill -> daily registration and assignment counts, last 30 days
+    skill_activity: IterableMap<String, Vec<trending::SkillActivity>>,
+    // Agent -> its skills with at least one endorsement
+    endorsed_skills: LookupMap<AccountId, IterableSet<String>>,
+    skill_endorsers: LookupMap<(AccountId, String), IterableSet<AccountId>>,
+    // Methods whose calls are mirrored as events for canary replay
+    mirrored_methods: Vec<String>,
+    // Canonical skill -> its aliases
+    canonical_skills: IterableMap<String, Vec<String>>,
+    // `skill_lookup_key` of a canonical skill or alias -> the canonical skill
+    skill_aliases: LookupMap<String, String>,
+    // Reject skills outside the taxonomy
+    strict_skill_taxonomy: bool,
+    // Settled tasks compacted after their retention period
+    archived_tasks: LookupMap<u64, tasks::ArchivedTask>,
+    // Queue position -> settled task awaiting compaction, in settlement order
+    settled_tasks: LookupMap<u64, u64>,
+    settled_queue_head: u64,
+    settled_queue_tail: u64,
+    // Lookup keys of the skills with a bucket in `skills_index`
+    indexed_skills: IterableSet<String>,
+    // Hour % REGISTRATION_BUCKETS -> (hour, registrations in that hour)
+    registration_counts: LookupMap<u64, (u64, u64)>,
+    // (u64::MAX - reputation, agent) of every agent, for leaderboards
+    reputation_ranking: TreeMap<(u64, AccountId), ()>,
+    // Agent -> id of its NEP-171 token, and back
+    agent_tokens: LookupMap<AccountId, u64>,
+    token_agents: LookupMap<u64, AccountId>,
+    next_agent_token_id: u64,
+    agent_nft_transferable: bool,
+    // Accounts besides the owner allowed to issue credentials
+    credential_verifiers: IterableSet<AccountId>,
+    // Agent -> credential name -> credential
+    agent_credentials: LookupMap<AccountId, IterableMap<String, credentials::Credential>>,
+    // Agent -> accounts allowed to act for it
+    agent_operators: LookupMap<AccountId, IterableSet<AccountId>>,
+    teams: LookupMap<u64, teams::Team>,
+    team_members: LookupMap<u64, IterableSet<AccountId>>,
+    // Team -> agents invited but not yet joined
+    team_invites: LookupMap<u64, IterableSet<AccountId>>,
+    // Agent -> teams it belongs to
+    agent_teams: LookupMap<AccountId, IterableSet<u64>>,
+    next_team_id: u64,
+    registration_limits: registration_limits::RegistrationLimits,
+    registration_bonds: LookupMap<AccountId, registration_limits::RegistrationBond>,
+    // (block height, agents registered in it) of the latest registration
+    block_registrations: (u64, u32),
+    // Account -> when it last deregistered, while a cooldown applies
+    deregistered_at: LookupMap<AccountId, u64>,
+    registration_mode: registration_access::RegistrationMode,
+    registration_allowlist: IterableSet<AccountId>,
+    banned_accounts: IterableMap<AccountId, registration_access::Ban>,
+    // Agents whose `initialize_agent` call failed
+    pending_initializations: IterableSet<AccountId>,
+    // Agent -> its failed `transfer_agent` or `remove_agent` call
+    pending_reputation_calls: IterableMap<AccountId, reputation_calls::PendingReputationCall>,
+    gas_config: gas_config::GasConfig,
+}
+
+#[near_bindgen]
+impl AgentRegistration {
+    #[init]
+    pub fn new(owner_id: AccountId, reputation_contract_id: AccountId) -> Self {
+        Self::new_with_reputation_mode(owner_id, ReputationMode::External(reputation_contract_id))
+    }
+
+    /// Like `new`, but lets the registry compute reputation itself with
+    /// `ReputationMode::Local` instead of relying on a reputation contract.
+    #[init]
+    pub fn new_with_reputation_mode(owner_id: AccountId, reputation_mode: ReputationMode) -> Self {
+        if reputation_mode == ReputationMode::Local && !cfg!(feature = "reputation-engine") {
+            env::panic_str(LOCAL_REPUTATION_UNAVAILABLE);
+        }
+        Self {
+            treasury_id: owner_id.clone(),
+            arbiter_id: owner_id.clone(),
+            state_version: migration::StateVersion::V2,
+            owner_id,
+            paused: false,
+            pending_owner_id: None,
+            registration_paused: false,
+            agents: migration::AgentStore::new(),
+            skills_index: LookupMap::new(b"s"),
+            total_agents: 0,
+            reputation_mode,
+            reputation_engine: ReputationEngineConfig::default(),
+            registry_config: registry_config::RegistryConfig::default(),
+            handles: LookupMap::new(b"h"),
+            handle_holders: LookupMap::new(b"H"),
+            skill_versions: LookupMap::new(b"v"),
+            audit_logs: LookupMap::new(b"l"),
+            history_tips: LookupMap::new(b"t"),
+            reputation_push_nonces: LookupMap::new(b"$"),
+            reconciliation_queue: IterableMap::new(b"r"),
+            availability: LookupMap::new(b"w"),
+            last_pings: LookupMap::new(b"k"),
+            blocklists: LookupMap::new(b"B"),
+            watchlists: LookupMap::new(b"W"),
+            stakes: LookupMap::new(b"S"),
+            slash_history: LookupMap::new(b"x"),
+            total_slashed: 0,
+            skill_history: LookupMap::new(b"y"),
+            task_histories: LookupMap::new(b"%"),
+            task_stats: LookupMap::new(b"&"),
+            task_history_window: task_history::DEFAULT_TASK_HISTORY_WINDOW,
+            reputation_updates_paused: false,
+            held_reputation_updates: LookupMap::new(b"q"),
+            held_updates_head: 0,
+            held_updates_tail: 0,
+            social_links: LookupMap::new(b"n"),
+            social_link_counts: LookupMap::new(b"N"),
+            storage_accounts: LookupMap::new(b"D"),
+            pending_agent_transfers: LookupMap::new(b"T"),
+            tasks: LookupMap::new(b"j"),
+            next_task_id: 0,
+            agent_open_tasks: LookupMap::new(b"4"),
+            protocol_fee_bps: 0,
+            itlx_payouts_owed: LookupMap::new(b"o"),
+            disputes: LookupMap::new(b"d"),
+            listing_flags: LookupMap::new(b"f"),
+            pending_flags: LookupMap::new(b"F"),
+            next_flag_id: 0,
+            curation_bounty: 0,
+            curation_pool: 0,
+            reputation_synced_at: IterableMap::new(b"R"),
+            ranking_snapshots: LookupMap::new(b"e"),
+            latest_ranking_epoch: None,
+            ranked_skills: Vec::new(),
+            reputation_breakdowns: LookupMap::new(b"g"),
+            invite_only: false,
+            invite_codes: LookupMap::new(b"c"),
+            agent_invites: LookupMap::new(b"C"),
+            referral_codes: LookupMap::new(b"2"),
+            open_referral_codes: LookupMap::new(b"!"),
+            referrals: LookupMap::new(b"3"),
+            cohort_sizes: LookupMap::new(b"u"),
+            agent_reviews: LookupMap::new(b"m"),
+            review_stats: LookupMap::new(b"M"),
+            task_reviews: LookupMap::new(b"p"),
+            skill_activity: IterableMap::new(b"z"),
+            endorsed_skills: LookupMap::new(b"E"),
+            skill_endorsers: LookupMap::new(b"G"),
+            mirrored_methods: Vec::new(),
+            canonical_skills: IterableMap::new(b"K"),
+            skill_aliases: LookupMap::new(b"L"),
+            strict_skill_taxonomy: false,
+            archived_tasks: LookupMap::new(b"P"),
+            settled_tasks: LookupMap::new(b"Q"),
+            settled_queue_head: 0,
+            settled_queue_tail: 0,
+            indexed_skills: IterableSet::new(b"V"),
+            registration_counts: LookupMap::new(b"U"),
+            reputation_ranking: TreeMap::new(b"X"),
+            agent_tokens: LookupMap::new(b"Y"),
+            token_agents: LookupMap::new(b"Z"),
+            next_agent_token_id: 0,
+            agent_nft_transferable: false,
+            credential_verifiers: IterableSet::new(b"i"),
+            agent_credentials: LookupMap::new(b"I"),
+            agent_operators: LookupMap::new(b"O"),
+            teams: LookupMap::new(b"J"),
+            team_members: LookupMap::new(b"b"),
+            team_invites: LookupMap::new(b"0"),
+            agent_teams: LookupMap::new(b"1"),
+            next_team_id: 0,
+            registration_limits: registration_limits::RegistrationLimits::default(),
+            registration_bonds: LookupMap::new(b"5"),
+            block_registrations: (0, 0),
+            deregistered_at: LookupMap::new(b"6"),
+            registration_mode: registration_access::RegistrationMode::Open,
+            registration_allowlist: IterableSet::new(b"7"),
+            banned_accounts: IterableMap::new(b"8"),
+            pending_initializations: IterableSet::new(b"9"),
+            pending_reputation_calls: IterableMap::new(b"#"),
+            gas_config: gas_config::GasConfig::default(),
+        }
+    }
+
+    /// Registers the caller as an agent. The caller must first stake at
+    /// least `MIN_REGISTRATION_STAKE` ITLX through `ft_transfer_call`,
+    /// unless registering with another agent's referral code. The
+    /// attached deposit, plus any `storage_deposit` balance, must cover the
+    /// storage the agent record and skill index entries take up, plus any
+    /// registration bond (see `set_registration_limits`). While
+    /// registration is invite-only, `invite_code` must be an unredeemed
+    /// code; a code given otherwise is still redeemed, recording the
+    /// agent's cohort.
+    #[payable]
+    pub fn register_agent(&mut self, metadata: AgentMetadata, invite_code: Option<String>) {
+        when_not_paused!(self);
+        self.mirror_call("register_agent", || serde_json::json!({ "metadata": metadata }));
+        require!(!self.registration_paused, "Registration is paused");
+        let account_id = env::predecessor_account_id();
+        self.assert_registration_permitted(&account_id);
+        
+        // Check if agent is already registered
+        require!(
+            !self.agents.contains_key(&account_id),
+            "Agent already registered"
+        );
+        let metadata = self.normalize_metadata(metadata);
+        Self::assert_system_skills_unchanged(&metadata, &[]);
+        let (invite, inviter) = match invite_code {
+            Some(code) => {
+                let (invite, inviter) = self.take_invite_code(&account_id, &code);
+                (Some(invite), inviter)
+            }
+            None => {
+                require!(!self.invite_only, "Registration requires an invite code");
+                (None, None)
+            }
+        };
+        // An agent's referral code stands in for the stake
+        if inviter.is_none() {
+            self.assert_min_stake(&account_id);
+        }
+        self.assert_registration_allowed(&account_id);
+        self.credit_storage_deposit(&account_id);
+
+        let initial_usage = env::storage_usage();
+        self.lock_registration_bond(&account_id);
+        self.store_new_agent(&account_id, metadata);
+        if let Some(invite) = invite {
+            self.record_invite_redemption(&account_id, invite, inviter);
+        }
+        self.settle_storage(&account_id, initial_usage);
+    }
+
+    /// Replaces the caller's agent metadata and re-indexes its skills.
+    /// Growth is charged to the caller's storage balance, topped up by the
+    /// attached deposit; shrinking unlocks storage again.
+    #[payable]
+    pub fn update_agent_metadata(&mut self, metadata: AgentMetadata) {
+        when_not_paused!(self);
+        self.mirror_call("update_agent_metadata", || serde_json::json!({ "metadata": metadata }));
+        let account_id = env::predecessor_account_id();
+        self.credit_storage_deposit(&account_id);
+        let initial_usage = env::storage_usage();
+        self.replace_agent_metadata(&account_id, metadata);
+        self.settle_storage(&account_id, initial_usage);
+    }
+
+    /// Removes the caller's agent from the registry and every skill index,
+    /// releases its handle, refunds its storage deposit, and tells the
+    /// reputation contract to drop it.
+    pub fn deregister_agent(&mut self) {
+        when_not_paused!(self);
+        self.mirror_call("deregister_agent", || serde_json::json!({}));
+        let account_id = env::predecessor_account_id();
+        let agent = self
+            .agents
+            .get(&account_id)
+            .unwrap_or_else(|| env::panic_str("Agent not registered"));
+        require!(agent.owner_id == account_id, "Only the agent owner can deregister");
+
+        for skill in &agent.metadata.skills {
+            self.unindex_agent_skill(skill, &account_id);
+            self.record_skill_change(&account_id, skill, SkillChangeKind::Removed);
+        }
+        self.agents.remove(&account_id);
+        self.total_agents -= 1;
+        self.rerank_agent(&account_id, Some(agent.reputation_info.reputation), None);
+        self.burn_agent_token(&account_id);
+
+        if let Some(handle) = self.handle_holders.remove(&account_id) {
+            self.handles.remove(&handle);
+        }
+        self.history_tips.remove(&account_id);
+        self.reputation_push_nonces.remove(&account_id);
+        self.clear_task_results(&account_id);
+        self.reconciliation_queue.remove(&account_id);
+        self.reputation_synced_at.remove(&account_id);
+        self.reputation_breakdowns.remove(&account_id);
+        self.agent_invites.remove(&account_id);
+        self.clear_referral_codes(&account_id);
+        self.clear_reviews(&account_id);
+        self.clear_endorsements(&account_id);
+        self.clear_credentials(&account_id);
+        self.availability.remove(&account_id);
+        self.last_pings.remove(&account_id);
+        self.social_links.remove(&account_id);
+        self.pending_agent_transfers.remove(&account_id);
+        self.clear_operators(&account_id);
+        self.leave_all_teams(&account_id);
+        self.release_storage(&account_id);
+        self.record_deregistration(&account_id);
+
+        // Let the reputation contract drop its record as well, unless it
+        // never created one
+        if !self.pending_initializations.remove(&account_id) {
+            let record_id = self.reputation_record_id(&account_id);
+            self.send_reputation_call(&record_id, PendingReputationCall::Remove);
+        }
+    }
+
+    /// Push from the reputation contract. `prev_tip_hash` must match
+    /// `get_history_tip(agent_id)`; mismatched pushes are not applied and
+    /// land in the reconciliation queue instead. While the registry or its
+    /// reputation updates are paused, or earlier pushes are still held, the
+    /// push is held rather than rejected. A `breakdown` replaces the agent's
+    /// per-dimension scores when the push is applied; without one they are
+    /// left as they were.
+    #[private]
+    pub fn update_agent_reputation(
+        &mut self,
+        agent_id: AccountId,
+        reputation_info: AgentInfo,
+        prev_tip_hash: String,
+        breakdown: Option<ReputationBreakdown>,
+    ) {
+        require!(
+            self.reputation_contract() == Some(&env::predecessor_account_id()),
+            "Only reputation contract can update reputation"
+        );
+
+        if self.paused || self.reputation_updates_paused || self.held_updates_head < self.held_updates_tail {
+            let push = continuity::ReputationPush::Full { reputation_info, prev_tip_hash, breakdown };
+            self.hold_reputation_update(agent_id, push);
+            return;
+        }
+        self.apply_reputation_update(&agent_id, reputation_info, &prev_tip_hash, breakdown);
+    }
+
+    pub fn get_agent(&self, agent_id: &AccountId) -> Option<Agent> {
+        self.agents.get(agent_id)
+    }
+
+    /// Agents listing `skill`, ignoring case and spacing. With
+    /// `active_only`, agents that are inactive, suspended or retired are
+    /// left out.
+    pub fn get_agents_by_skill(&self, skill: String, active_only: Option<bool>) -> Vec<AccountId> {
+        match self.skill_agents(&skill) {
+            Some(skill_agents) => skill_agents
+                .iter()
+                .filter(|agent_id| !active_only.unwrap_or(false) || self.is_agent_active(agent_id))
+                .cloned()
+                .collect(),
+            None => Vec::new()
+        }
+    }
+
+    /// Counter bumped on any change to the agent; cheaper to poll than the
+    /// full profile. `None` if the agent is not registered.
+    pub fn get_agent_version(&self, agent_id: AccountId) -> Option<u64> {
+        self.agents.get(&agent_id).map(|agent| agent.version)
+    }
+
+    pub fn get_total_agents(&self) -> u64 {
+        self.total_agents
+    }
+
+    pub fn get_agent_skills(&self, agent_id: &AccountId) -> Option<Vec<String>> {
+        self.agents
+            .get(agent_id)
+            .map(|agent| agent.metadata.skills.clone())
+    }
+
+    pub fn get_agent_reputation(&self, agent_id: &AccountId) -> Option<u64> {
+        self.agents
+            .get(agent_id)
+            .map(|agent| agent.reputation_info.reputation)
+    }
+
+    /// Quality, timeliness and communication scores, once the reputation
+    /// contract has pushed any.
+    pub fn get_agent_reputation_breakdown(&self, agent_id: AccountId) -> Option<ReputationBreakdown> {
+        self.reputation_breakdowns.get(&agent_id)
+    }
+
+    pub fn sync_reputation(&mut self, agent_id: AccountId) -> Promise {
+        when_not_paused!(self);
+        ext::ext_reputation::ext(self.expect_reputation_contract())
+            .with_static_gas(tgas(self.gas_config.reputation_call_tgas))
+            .get_agent_info(agent_id.clone())
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(tgas(self.gas_config.reputation_callback_tgas))
+                    .on_reputation_synced(agent_id),
+            )
+    }
+
+    /// Applies the `AgentInfo` returned by the reputation contract's
+    /// `get_agent_info`. Returns whether the stored agent was updated; a
+    /// failed call or an unknown agent leaves state untouched.
+    #[private]
+    pub fn on_reputation_synced(&mut self, agent_id: AccountId) -> bool {
+        match self.apply_synced_reputation(&agent_id, env::promise_result(0)) {
+            Ok(()) => true,
+            Err(reason) => {
+                env::log_str(&format!("Reputation sync for {} {}", agent_id, reason));
+                false
+            }
+        }
+    }
+}
+
+impl AgentRegistration {
+    pub(crate) fn apply_reputation_update(
+        &mut self,
+        agent_id: &AccountId,
+        mut reputation_info: AgentInfo,
+        prev_tip_hash: &str,
+        breakdown: Option<ReputationBreakdown>,
+    ) {
+        if let Some(mut agent) = self.agents.get(agent_id) {
+            if !self.check_history_continuity(agent_id, prev_tip_hash, &reputation_info) {
+                return;
+            }
+            self.advance_history_tip(agent_id, continuity::history_tip_hash(&reputation_info));
+            self.move_task_history(agent_id, &mut reputation_info);
+            reputation_history::compact_history(&mut reputation_info);
+            if let Some(breakdown) = breakdown {
+                self.reputation_breakdowns.insert(agent_id, &breakdown);
+            }
+            self.rerank_agent(agent_id, Some(agent.reputation_info.reputation), Some(reputation_info.reputation));
+            agent.reputation_info = reputation_info;
+            agent.version += 1;
+            self.agents.insert(agent_id, &agent);
+        }
+    }
+
+    fn store_new_agent(&mut self, account_id: &AccountId, metadata: AgentMetadata) {
+        // Initialize agent with default reputation info
+        let agent = Agent {
+            owner_id: account_id.clone(),
+            metadata: metadata.clone(),
+            registered_at: env::block_timestamp(),
+            updated_at: env::block_timestamp(),
+            reputation_info: AgentInfo {
+                reputation: 0,
+                task_history: Vec::new(),
+                reputation_history: vec![(env::block_timestamp(), 0)],
+            },
+            version: 0,
+            status: AgentStatus::Active,
+        };
+
+        self.agents.insert(account_id, &agent);
+        self.reputation_synced_at.insert(account_id.clone(), agent.registered_at);
+        self.total_agents += 1;
+        self.record_registration();
+        self.rerank_agent(account_id, None, Some(0));
+        self.mint_agent_token(account_id);
+
+        // Index by skills
+        for skill in &metadata.skills {
+            self.index_agent_skill(skill, account_id);
+            self.record_skill_change(account_id, skill, SkillChangeKind::Added);
+        }
+        self.record_skill_registrations(&metadata.skills);
+
+        // Call reputation contract to initialize agent's reputation
+        self.call_reputation_contract("initialize_agent", account_id, None, |reputation| {
+            reputation.initialize_agent(account_id.clone())
+        });
+    }
+
+    /// The external reputation contract; `None` in `ReputationMode::Local`.
+    pub(crate) fn reputation_contract(&self) -> Option<&AccountId> {
+        match &self.reputation_mode {
+            ReputationMode::External(reputation_contract_id) => Some(reputation_contract_id),
+            ReputationMode::Local => None,
+        }
+    }
+
+    /// Like `reputation_contract`, for calls that only make sense with one.
+    pub(crate) fn expect_reputation_contract(&self) -> AccountId {
+        self.reputation_contract()
+            .cloned()
+            .unwrap_or_else(|| env::panic_str("Reputation is computed locally; there is no reputation contract"))
+    }
+
+    /// Replaces `account_id`'s metadata, moving it between skill indexes.
+    /// Storage is left for the caller to settle.
+    pub(crate) fn replace_agent_metadata(&mut self, account_id: &AccountId, metadata: AgentMetadata) {
+        let mut agent = self
+            .agents
+            .get(account_id)
+            .unwrap_or_else(|| env::panic_str("Agent not registered"));
+        require!(&agent.owner_id == account_id, "Only the agent owner can update metadata");
+        let metadata = self.normalize_metadata(metadata);
+        Self::assert_system_skills_unchanged(&metadata, &agent.metadata.skills);
+
+        for skill in &agent.metadata.skills {
+            if !lists_skill(&metadata.skills, skill) {
+                self.unindex_agent_skill(skill, account_id);
+                self.record_skill_change(account_id, skill, SkillChangeKind::Removed);
+            }
+        }
+        for skill in &metadata.skills {
+            if !lists_skill(&agent.metadata.skills, skill) {
+                self.index_agent_skill(skill, account_id);
+                self.record_skill_change(account_id, skill, SkillChangeKind::Added);
+            }
+        }
+
+        agent.metadata = metadata;
+        agent.updated_at = env::block_timestamp();
+        agent.version += 1;
+        self.agents.insert(account_id, &agent);
+    }
+
+    /// Bumps the version of `agent_id` for changes stored outside its
+    /// record, such as handles. No-op for accounts that are not agents.
+    pub(crate) fn bump_agent_version(&mut self, agent_id: &AccountId) {
+        if let Some(mut agent) = self.agents.get(agent_id) {
+            agent.version += 1;
+            self.agents.insert(agent_id, &agent);
+        }
+    }
+
+    /// The skill bucket `skill` falls in; buckets are keyed by
+    /// `skill_lookup_key`.
+    pub(crate) fn skill_agents(&self, skill: &str) -> Option<IterableSet<AccountId>> {
+        self.skills_index.get(&skill_lookup_key(skill))
+    }
+
+    pub(crate) fn index_agent_skill(&mut self, skill: &str, account_id: &AccountId) {
+        let key = skill_lookup_key(skill);
+        let mut skill_agents = match self.skills_index.get(&key) {
+            Some(existing_set) => existing_set,
+            None => {
+                self.indexed_skills.insert(key.clone());
+                IterableSet::<AccountId>::new(format!("s_{}", key).into_bytes())
+            }
+        };
+
+        skill_agents.insert(account_id.clone());
+        self.skills_index.insert(&key, &skill_agents);
+        self.index_skill_version(skill);
+    }
+
+    /// Removes an agent from a skill bucket, dropping the bucket once empty.
+    pub(crate) fn unindex_agent_skill(&mut self, skill: &str, account_id: &AccountId) {
+        let key = skill_lookup_key(skill);
+        let Some(mut skill_agents) = self.skills_index.get(&key) else {
+            return;
+        };
+
+        skill_agents.remove(account_id);
+        if skill_agents.is_empty() {
+            self.skills_index.remove(&key);
+            self.indexed_skills.remove(&key);
+            self.unindex_skill_version(skill);
+        } else {
+            self.skills_index.insert(&key, &skill_agents);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::json_types::U128;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::{testing_env, NearToken, PromiseResult};
+
+    pub(crate) fn get_context(predecessor_account_id: AccountId) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id(accounts(0))
+            .signer_account_id(predecessor_account_id.clone())
+            .predecessor_account_id(predecessor_account_id);
+        builder
+    }
+
+    /// Credits `amount` ITLX of stake to `account_id` as the token contract
+    /// would on `ft_transfer_call`, then restores the caller as predecessor.
+    pub(crate) fn deposit_stake(contract: &mut AgentRegistration, account_id: AccountId, amount: u128) {
+        let caller = env::predecessor_account_id();
+        testing_env!(get_context(ITLX_TOKEN_CONTRACT.parse().unwrap()).build());
+        contract.ft_on_transfer(account_id, U128(amount), String::new());
+        testing_env!(get_context(caller).build());
+    }
+
+    /// Stakes the minimum for the current predecessor and registers it,
+    /// attaching enough NEAR for storage.
+    pub(crate) fn register(contract: &mut AgentRegistration, context: &mut VMContextBuilder, metadata: AgentMetadata) {
+        let caller = env::predecessor_account_id();
+        deposit_stake(contract, caller.clone(), staking::MIN_REGISTRATION_STAKE);
+        testing_env!(context.predecessor_account_id(caller).attached_deposit(NearToken::from_near(1)).build());
+        contract.register_agent(metadata, None);
+        testing_env!(context.attached_deposit(NearToken::from_yoctonear(0)).build());
+    }
+
+    pub(crate) fn sample_metadata() -> AgentMetadata {
+        AgentMetadata {
+            name: "Test Agent".to_string(),
+            description: "Test Description".to_string(),
+            skills: vec!["Rust".to_string()],
+            purpose: "Testing".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_registration_with_reputation() {
+        let reputation_contract = accounts(0);
+        let agent_account = accounts(1);
+        
+        let mut context = get_context(agent_account.clone());
+        testing_env!(context.build());
+        
+        let mut contract = AgentRegistration::new(accounts(0), reputation_contract.clone());
+        
+        let metadata = AgentMetadata {
+            name: "Test Agent".to_string(),
+            description: "Test Description".to_string(),
+            skills: vec!["Rust".to_string()],
+            purpose: "Testing".to_string(),
+            ..Default::default()
+        };
+        
+        register(&mut contract, &mut context, metadata);
+        
+        let agent = contract.get_agent(&agent_account).unwrap();
+        assert_eq!(agent.reputation_info.reputation, 0);
+        assert_eq!(agent.reputation_info.task_history.len(), 0);
+        assert_eq!(agent.reputation_info.reputation_history.len(), 1);
+    }
+
+    #[test]
+    fn test_reputation_sync() {
+        let reputation_contract = accounts(0);
+        let agent_account = accounts(1);
+        
+        let mut context = get_context(agent_account.clone());
+        testing_env!(context.build());
+        
+        let mut contract = AgentRegistration::new(accounts(0), reputation_contract.clone());
+        
+        // Register agent
+        register(&mut contract, &mut context, AgentMetadata {
+            name: "Test Agent".to_string(),
+            description: "Test Description".to_string(),
+            skills: vec!["Rust".to_string()],
+            purpose: "Testing".to_string(),
+            ..Default::default()
+        });
+        
+        // Update reputation as reputation contract
+        let new_reputation_info = AgentInfo {
+            reputation: 50,
+            task_history: vec![TaskResult {
+                task_id: "test_task".to_string(),
+                success: true,
+                timestamp: env::block_timestamp(),
+                details: "Test task completed".to_string(),
+            }],
+            reputation_history: vec![(env::block_timestamp(), 50)],
+        };
+        
+        let context = get_context(reputation_contract.clone());
+        testing_env!(context.build());
+        
+        contract.update_agent_reputation(agent_account.clone(), new_reputation_info, String::new(), None);
+        
+        let agent = contract.get_agent(&agent_account).unwrap();
+        assert_eq!(agent.reputation_info.reputation, 50);
+        assert_eq!(contract.get_agent_task_history(&agent_account, None, None).items.len(), 1);
+        assert_eq!(agent.reputation_info.reputation_history.len(), 1);
+    }
+
+    fn resolve_reputation_sync(contract: &mut AgentRegistration, agent_id: AccountId, result: PromiseResult) -> bool {
+        testing_env!(
+            get_context(accounts(0)).build(),
+            near_sdk::test_vm_config(),
+            near_sdk::RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![result],
+        );
+        contract.on_reputation_synced(agent_id)
+    }
+
+    #[test]
+    fn test_reputation_sync_callback_applies_result() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+
+        let info = AgentInfo {
+            reputation: 42,
+            task_history: Vec::new(),
+            reputation_history: vec![(0, 0), (10, 42)],
+        };
+        let result = PromiseResult::Successful(serde_json::to_vec(&info).unwrap());
+        assert!(resolve_reputation_sync(&mut contract, accounts(1), result));
+        assert_eq!(contract.get_agent_reputation(&accounts(1)), Some(42));
+        assert_eq!(contract.get_agent_reputation_history(&accounts(1), None, None, None, None).items.len(), 2);
+    }
+
+    #[test]
+    fn test_reputation_sync_callback_failure_keeps_state() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+
+        assert!(!resolve_reputation_sync(&mut contract, accounts(1), PromiseResult::Failed));
+        let malformed = PromiseResult::Successful(b"{\"reputation\": \"high\"}".to_vec());
+        assert!(!resolve_reputation_sync(&mut contract, accounts(1), malformed));
+        let unknown = PromiseResult::Successful(b"null".to_vec());
+        assert!(!resolve_reputation_sync(&mut contract, accounts(1), unknown));
+        assert_eq!(contract.get_agent_reputation(&accounts(1)), Some(0));
+    }
+
+    #[test]
+    fn test_history_continuity_across_pushes() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        testing_env!(get_context(accounts(0)).build());
+
+        let first = AgentInfo { reputation: 10, task_history: Vec::new(), reputation_history: vec![(1, 10)] };
+        let second = AgentInfo { reputation: 20, task_history: Vec::new(), reputation_history: vec![(1, 10), (2, 20)] };
+        contract.update_agent_reputation(accounts(1), first.clone(), String::new(), None);
+        assert_eq!(contract.get_history_tip(accounts(1)), continuity::history_tip_hash(&first));
+
+        // A push that skips the first update's tip is held back
+        let third = AgentInfo { reputation: 30, task_history: Vec::new(), reputation_history: Vec::new() };
+        contract.update_agent_reputation(accounts(1), third, "stale".to_string(), None);
+        assert_eq!(contract.get_agent_reputation(&accounts(1)), Some(10));
+        let queue = contract.get_reconciliation_queue(None, None).items;
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue[0].proposed_reputation, 30);
+
+        contract.update_agent_reputation(accounts(1), second.clone(), continuity::history_tip_hash(&first), None);
+        assert_eq!(contract.get_agent_reputation(&accounts(1)), Some(20));
+        assert!(contract.get_reconciliation_queue(None, None).items.is_empty());
+    }
+
+    #[test]
+    fn test_update_agent_metadata_reindexes_skills() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        let mut metadata = sample_metadata();
+        metadata.skills = vec!["Rust".to_string(), "near-sdk@4".to_string()];
+        register(&mut contract, &mut context, metadata);
+
+        testing_env!(context.block_timestamp(1_000).build());
+        let mut metadata = sample_metadata();
+        metadata.name = "Renamed".to_string();
+        metadata.skills = vec!["Rust".to_string(), "Python".to_string()];
+        contract.update_agent_metadata(metadata);
+
+        let agent = contract.get_agent(&accounts(1)).unwrap();
+        assert_eq!(agent.metadata.name, "Renamed");
+        assert_eq!(agent.updated_at, 1_000);
+        assert_eq!(agent.registered_at, 0);
+        assert_eq!(contract.get_agents_by_skill("Rust".to_string(), None), vec![accounts(1)]);
+        assert_eq!(contract.get_agents_by_skill("Python".to_string(), None), vec![accounts(1)]);
+        assert!(contract.get_agents_by_skill("near-sdk@4".to_string(), None).is_empty());
+        assert!(contract.get_skill_versions("near-sdk".to_string()).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Agent not registered")]
+    fn test_update_agent_metadata_requires_registration() {
+        let context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.update_agent_metadata(sample_metadata());
+    }
+
+    #[test]
+    fn test_deregister_agent_cleans_up_indexes() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        let mut metadata = sample_metadata();
+        metadata.skills = vec!["Rust".to_string(), "near-sdk@5".to_string()];
+        register(&mut contract, &mut context, metadata);
+        contract.claim_handle("rusty".to_string());
+
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        register(&mut contract, &mut context, sample_metadata());
+
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.deregister_agent();
+
+        assert!(contract.get_agent(&accounts(1)).is_none());
+        assert_eq!(contract.get_total_agents(), 1);
+        assert_eq!(contract.get_agents_by_skill("Rust".to_string(), None), vec![accounts(2)]);
+        assert!(contract.get_agents_by_skill("near-sdk@5".to_string(), None).is_empty());
+        assert!(contract.get_skill_versions("near-sdk".to_string()).is_empty());
+        assert_eq!(contract.get_agent_by_handle("rusty".to_string()), None);
+
+        // The account can register again afterwards
+        register(&mut contract, &mut context, sample_metadata());
+        assert_eq!(contract.get_total_agents(), 2);
+    }
+
+    #[test]
+    fn test_agent_version_tracks_changes() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        assert_eq!(contract.get_agent_version(accounts(1)), None);
+        register(&mut contract, &mut context, sample_metadata());
+        assert_eq!(contract.get_agent_version(accounts(1)), Some(0));
+
+        contract.update_agent_metadata(sample_metadata());
+        contract.claim_handle("rusty".to_string());
+        contract.ping();
+        assert_eq!(contract.get_agent_version(accounts(1)), Some(2));
+
+        testing_env!(get_context(accounts(0)).build());
+        let info = AgentInfo { reputation: 5, task_history: Vec::new(), reputation_history: Vec::new() };
+        contract.update_agent_reputation(accounts(1), info, String::new(), None);
+        assert_eq!(contract.get_agent_version(accounts(1)), Some(3));
+        assert_eq!(contract.get_agent_summary(accounts(1)).unwrap().version, 3);
+    }
+
+    #[test]
+    fn test_reputation_breakdown_follows_applied_pushes() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        assert_eq!(contract.get_agent_reputation_breakdown(accounts(1)), None);
+
+        let breakdown = ReputationBreakdown { quality: 80, timeliness: 40, communication: 60 };
+        let first = AgentInfo { reputation: 60, task_history: Vec::new(), reputation_history: Vec::new() };
+        testing_env!(get_context(accounts(0)).build());
+        contract.update_agent_reputation(accounts(1), first.clone(), String::new(), Some(breakdown));
+        assert_eq!(contract.get_agent_reputation_breakdown(accounts(1)), Some(breakdown));
+
+        // A push without a breakdown keeps the last one; a rejected push changes nothing
+        let second = AgentInfo { reputation: 65, ..first.clone() };
+        contract.update_agent_reputation(accounts(1), second, continuity::history_tip_hash(&first), None);
+        let stray = ReputationBreakdown { quality: 0, timeliness: 0, communication: 0 };
+        contract.update_agent_reputation(accounts(1), first, "stale".to_string(), Some(stray));
+        assert_eq!(contract.get_agent_reputation(&accounts(1)), Some(65));
+        assert_eq!(contract.get_agent_reputation_breakdown(accounts(1)), Some(breakdown));
+    }
+
+    #[test]
+    #[should_panic(expected = "Agent not registered")]
+    fn test_deregister_unknown_agent() {
+        let context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.deregister_agent();
+    }
+    #[test]
+    #[cfg(not(feature = "reputation-engine"))]
+    #[should_panic(expected = "Local reputation needs a build with the reputation-engine feature")]
+    fn test_local_reputation_requires_feature() {
+        testing_env!(get_context(accounts(0)).build());
+        AgentRegistration::new_with_reputation_mode(accounts(0), ReputationMode::Local);
+    }
+}
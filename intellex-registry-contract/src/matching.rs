@@ -0,0 +1,112 @@
+use std::collections::BTreeSet;
+
+use near_sdk::{near_bindgen, require, AccountId};
+
+pub use intellex_types::matching::{match_score, AgentMatch, MATCH_REPUTATION_CAP, MATCH_SKILL_WEIGHT_PCT};
+
+use crate::search::MAX_SEARCH_LIMIT;
+use crate::skills_registry::lists_skill;
+use crate::{AgentRegistration, AgentRegistrationExt, AgentStatus};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Active agents listing at least one of `required_skills`, with at
+    /// least `min_reputation`, ranked by `match_score`. Ties go to the
+    /// higher reputation, then the lower account id. `limit` is capped at
+    /// 100.
+    pub fn match_agents(&self, required_skills: Vec<String>, min_reputation: u64, limit: u64) -> Vec<AgentMatch> {
+        require!(!required_skills.is_empty(), "At least one required skill is needed");
+        let mut required: Vec<String> = Vec::new();
+        for skill in required_skills {
+            if !lists_skill(&required, &skill) {
+                required.push(skill);
+            }
+        }
+
+        let candidates: BTreeSet<AccountId> = required
+            .iter()
+            .filter_map(|skill| self.skill_agents(skill))
+            .flat_map(|skill_agents| skill_agents.iter().cloned().collect::<Vec<_>>())
+            .collect();
+        let mut matches: Vec<AgentMatch> = candidates
+            .into_iter()
+            .filter_map(|agent_id| {
+                let agent = self.agents.get(&agent_id)?;
+                let reputation = agent.reputation_info.reputation;
+                if agent.status != AgentStatus::Active || reputation < min_reputation {
+                    return None;
+                }
+                let matched_skills: Vec<String> = required
+                    .iter()
+                    .filter(|skill| lists_skill(&agent.metadata.skills, skill))
+                    .cloned()
+                    .collect();
+                Some(AgentMatch {
+                    score: match_score(matched_skills.len(), required.len(), reputation),
+                    agent_id,
+                    matched_skills,
+                    reputation,
+                })
+            })
+            .collect();
+
+        matches.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| b.reputation.cmp(&a.reputation))
+                .then_with(|| a.agent_id.cmp(&b.agent_id))
+        });
+        matches.truncate(limit.min(MAX_SEARCH_LIMIT) as usize);
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use crate::AgentInfo;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn test_match_agents_ranks_overlap_then_reputation() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        for (i, skills, reputation) in [(1, vec!["Rust"], 95), (2, vec!["Rust", "Go"], 40), (3, vec!["Go"], 10)] {
+            let mut metadata = sample_metadata();
+            metadata.skills = skills.into_iter().map(String::from).collect();
+            testing_env!(context.predecessor_account_id(accounts(i)).build());
+            register(&mut contract, &mut context, metadata);
+            testing_env!(get_context(accounts(0)).build());
+            contract.update_agent_reputation(
+                accounts(i),
+                AgentInfo { reputation, task_history: Vec::new(), reputation_history: Vec::new() },
+                String::new(),
+                None,
+            );
+        }
+
+        let required = vec!["Rust".to_string(), "Go".to_string()];
+        let matches = contract.match_agents(required.clone(), 0, 10);
+        let ids: Vec<AccountId> = matches.iter().map(|m| m.agent_id.clone()).collect();
+        assert_eq!(ids, vec![accounts(2), accounts(1), accounts(3)]);
+        assert_eq!(matches[0].matched_skills, required);
+        assert_eq!(matches[0].score, match_score(2, 2, 40));
+
+        let ids: Vec<AccountId> = contract.match_agents(required, 20, 1).into_iter().map(|m| m.agent_id).collect();
+        assert_eq!(ids, vec![accounts(2)]);
+    }
+
+    #[test]
+    fn test_match_agents_skips_inactive_agents() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        contract.set_status(AgentStatus::Inactive);
+
+        assert!(contract.match_agents(vec!["Rust".to_string(), "Rust".to_string()], 0, 10).is_empty());
+    }
+}
@@ -0,0 +1,45 @@
+use near_sdk::{env, near_bindgen, AccountId};
+
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Whether `payload`, e.g. the document fetched from the agent's
+    /// `metadata_uri`, hashes to its `metadata_hash`. False for unknown
+    /// agents and agents without a hash.
+    pub fn verify_metadata_hash(&self, agent_id: AccountId, payload: Vec<u8>) -> bool {
+        self.agents
+            .get(&agent_id)
+            .and_then(|agent| agent.metadata.metadata_hash)
+            .is_some_and(|hash| env::sha256_array(&payload) == hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use crate::AgentMetadata;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn test_verify_metadata_hash() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        let document = br#"{"description":"A long description kept off chain"}"#.to_vec();
+        let metadata = AgentMetadata {
+            metadata_uri: Some("ar://bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U".to_string()),
+            metadata_hash: Some(env::sha256_array(&document)),
+            ..sample_metadata()
+        };
+        register(&mut contract, &mut context, metadata);
+
+        assert!(contract.verify_metadata_hash(accounts(1), document));
+        assert!(!contract.verify_metadata_hash(accounts(1), b"tampered".to_vec()));
+        assert!(!contract.verify_metadata_hash(accounts(2), Vec::new()));
+        let token = contract.nft_token("0".to_string()).unwrap().metadata.unwrap();
+        assert_eq!(token.reference.as_deref(), Some("ar://bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U"));
+    }
+}
@@ -0,0 +1,54 @@
+use near_sdk::{near_bindgen, AccountId};
+
+pub use intellex_types::metrics::{wilson_lower_bound_bps, MIN_SUCCESS_RATE_SAMPLE};
+
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Success rate in basis points, as the 95% Wilson lower bound over the
+    /// agent's whole task history. `None` until the agent has completed
+    /// `MIN_SUCCESS_RATE_SAMPLE` tasks.
+    pub fn get_success_rate(&self, agent_id: AccountId) -> Option<u32> {
+        let stats = self.get_agent_task_stats(agent_id);
+        wilson_lower_bound_bps(stats.succeeded, stats.total, MIN_SUCCESS_RATE_SAMPLE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use crate::{AgentInfo, TaskResult};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn test_get_success_rate_requires_min_sample() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        assert_eq!(contract.get_success_rate(accounts(1)), None);
+
+        let task_history: Vec<TaskResult> = (0..20)
+            .map(|i| TaskResult {
+                task_id: format!("task{}", i),
+                success: i != 0,
+                timestamp: i,
+                details: String::new(),
+            })
+            .collect();
+        testing_env!(get_context(accounts(0)).build());
+        contract.update_agent_reputation(
+            accounts(1),
+            AgentInfo { reputation: 50, task_history, reputation_history: Vec::new() },
+            String::new(),
+            None,
+        );
+
+        let rate = contract.get_success_rate(accounts(1)).unwrap();
+        assert!(rate > 7_000 && rate < 9_500, "got {}", rate);
+        assert_eq!(contract.get_success_rate(accounts(2)), None);
+    }
+}
@@ -0,0 +1,213 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::LookupMap;
+use near_sdk::store::IterableSet;
+use near_sdk::{env, near_bindgen, AccountId};
+use serde::Serialize;
+use serde_json::json;
+
+pub use intellex_types::agent::{AgentV1, VersionedAgent};
+
+use crate::events;
+use crate::skills_registry::{lists_skill, skill_lookup_key};
+use crate::storage_estimate::record_bytes;
+use crate::{Agent, AgentRegistration, AgentRegistrationExt};
+
+/// Layout of the contract state. Bump alongside a new `migrate` whenever a
+/// field is added to or removed from `AgentRegistration`.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum StateVersion {
+    /// First release: agents, skills index, total and reputation contract.
+    V1,
+    V2,
+}
+
+/// Contract state as written by the first release.
+#[derive(BorshDeserialize)]
+#[cfg_attr(test, derive(BorshSerialize))]
+pub(crate) struct AgentRegistrationV1 {
+    agents: LookupMap<AccountId, AgentV1>,
+    skills_index: LookupMap<String, IterableSet<AccountId>>,
+    total_agents: u64,
+    reputation_contract_id: AccountId,
+}
+
+const LEGACY_PREFIX: &[u8] = b"a";
+const RECORDS_PREFIX: &[u8] = b"A";
+
+/// Agent records keyed by account. Records left by the first release stay
+/// under the legacy prefix, are upgraded on read, and move to `records` the
+/// next time they are written.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub(crate) struct AgentStore {
+    legacy: LookupMap<AccountId, AgentV1>,
+    records: LookupMap<AccountId, VersionedAgent>,
+}
+
+impl AgentStore {
+    pub(crate) fn new() -> Self {
+        Self {
+            legacy: LookupMap::new(LEGACY_PREFIX),
+            records: LookupMap::new(RECORDS_PREFIX),
+        }
+    }
+
+    pub(crate) fn get(&self, agent_id: &AccountId) -> Option<Agent> {
+        self.records
+            .get(agent_id)
+            .map(Agent::from)
+            .or_else(|| self.legacy.get(agent_id).map(Agent::from))
+    }
+
+    pub(crate) fn contains_key(&self, agent_id: &AccountId) -> bool {
+        self.records.contains_key(agent_id) || self.legacy.contains_key(agent_id)
+    }
+
+    pub(crate) fn insert(&mut self, agent_id: &AccountId, agent: &Agent) {
+        self.records.insert(agent_id, &VersionedAgent::V2(Box::new(agent.clone())));
+        self.legacy.remove(agent_id);
+    }
+
+    pub(crate) fn remove(&mut self, agent_id: &AccountId) -> Option<Agent> {
+        let legacy = self.legacy.remove(agent_id).map(Agent::from);
+        self.records.remove(agent_id).map(Agent::from).or(legacy)
+    }
+
+    /// Bytes `insert` would store for `agent`.
+    pub(crate) fn record_bytes(&self, agent_id: &AccountId, agent: &Agent) -> u64 {
+        let key_len = RECORDS_PREFIX.len() + borsh::to_vec(agent_id).unwrap().len();
+        record_bytes(key_len, borsh::to_vec(&VersionedAgent::V2(Box::new(agent.clone()))).unwrap().len())
+    }
+
+    /// Bytes the stored record of `agent_id` takes up, and whether it is
+    /// still a legacy record.
+    pub(crate) fn stored_record_bytes(&self, agent_id: &AccountId) -> Option<(u64, bool)> {
+        let agent_key = borsh::to_vec(agent_id).unwrap();
+        [(RECORDS_PREFIX, false), (LEGACY_PREFIX, true)].into_iter().find_map(|(prefix, legacy)| {
+            let key = [prefix, agent_key.as_slice()].concat();
+            env::storage_read(&key).map(|value| (record_bytes(key.len(), value.len()), legacy))
+        })
+    }
+}
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Upgrades first-release state in place after deploying this code.
+    /// Agent records are converted lazily, as `AgentStore` reads them.
+    /// Indexes added since then (skill versions, skill history) start empty
+    /// for migrated agents, and skill buckets must be re-keyed with
+    /// `migrate_skill_index`.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate(owner_id: AccountId) -> Self {
+        let old: AgentRegistrationV1 =
+            env::state_read().unwrap_or_else(|| env::panic_str("No contract state to migrate"));
+
+        let mut contract = Self::new(owner_id, old.reputation_contract_id);
+        contract.agents = AgentStore {
+            legacy: old.agents,
+            records: LookupMap::new(RECORDS_PREFIX),
+        };
+        contract.skills_index = old.skills_index;
+        contract.total_agents = old.total_agents;
+        contract
+    }
+
+    /// Moves skill buckets indexed under their raw name, as releases before
+    /// case-insensitive indexing wrote them, to their lookup key, and lists
+    /// the skill in `get_skill_counts`. Pass the skills as agents listed
+    /// them; only agents still listing the skill are carried over. Returns
+    /// the skills re-keyed. Owner only.
+    pub fn migrate_skill_index(&mut self, skills: Vec<String>) -> Vec<String> {
+        self.assert_owner();
+        let mut rekeyed = Vec::new();
+        for skill in skills {
+            let key = skill_lookup_key(&skill);
+            if self.skills_index.contains_key(&key) {
+                self.indexed_skills.insert(key.clone());
+            }
+            if key == skill {
+                continue;
+            }
+            let Some(mut legacy) = self.skills_index.get(&skill) else {
+                continue;
+            };
+            let mut skill_agents = self
+                .skills_index
+                .get(&key)
+                .unwrap_or_else(|| IterableSet::new(format!("s_{}", key).into_bytes()));
+            for agent_id in legacy.iter() {
+                if self.agents.get(agent_id).is_some_and(|agent| lists_skill(&agent.metadata.skills, &skill)) {
+                    skill_agents.insert(agent_id.clone());
+                }
+            }
+            legacy.clear();
+            self.skills_index.remove(&skill);
+            if !skill_agents.is_empty() {
+                self.skills_index.insert(&key, &skill_agents);
+                self.indexed_skills.insert(key);
+            }
+            rekeyed.push(skill);
+        }
+        events::emit("skill_index_migrated", json!({ "skills": rekeyed }));
+        rekeyed
+    }
+
+    pub fn get_state_version(&self) -> StateVersion {
+        self.state_version
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, sample_metadata};
+    use crate::AgentInfo;
+    use intellex_types::agent::AgentMetadataV1;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn test_migrate_from_v1_keeps_agents() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut old = AgentRegistrationV1 {
+            agents: LookupMap::new(b"a"),
+            skills_index: LookupMap::new(b"s"),
+            total_agents: 1,
+            reputation_contract_id: accounts(0),
+        };
+        old.agents.insert(
+            &accounts(1),
+            &AgentV1 {
+                owner_id: accounts(1),
+                metadata: AgentMetadataV1 {
+                    name: "Test Agent".to_string(),
+                    description: "Test Description".to_string(),
+                    skills: vec!["Rust".to_string()],
+                    purpose: "Testing".to_string(),
+                },
+                registered_at: 42,
+                reputation_info: AgentInfo { reputation: 70, task_history: Vec::new(), reputation_history: Vec::new() },
+            },
+        );
+        let mut rust_agents = IterableSet::new(b"s_Rust".to_vec());
+        rust_agents.insert(accounts(1));
+        old.skills_index.insert(&"Rust".to_string(), &rust_agents);
+        drop(rust_agents);
+        env::state_write(&old);
+
+        let mut contract = AgentRegistration::migrate(accounts(0));
+        assert_eq!(contract.get_state_version(), StateVersion::V2);
+        assert_eq!(contract.get_total_agents(), 1);
+        assert!(contract.get_agents_by_skill("Rust".to_string(), None).is_empty());
+        assert_eq!(contract.migrate_skill_index(vec!["Rust".to_string(), "go".to_string()]), vec!["Rust".to_string()]);
+        assert_eq!(contract.get_agents_by_skill("RUST".to_string(), None), vec![accounts(1)]);
+        let agent = contract.get_agent(&accounts(1)).unwrap();
+        assert_eq!((agent.updated_at, agent.reputation_info.reputation), (42, 70));
+
+        // Writing the agent moves it out of the legacy map
+        testing_env!(get_context(accounts(1)).attached_deposit(near_sdk::NearToken::from_near(1)).build());
+        contract.update_agent_metadata(sample_metadata());
+        assert!(contract.agents.records.contains_key(&accounts(1)));
+        assert!(!contract.agents.legacy.contains_key(&accounts(1)));
+    }
+}
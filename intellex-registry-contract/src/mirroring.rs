@@ -0,0 +1,102 @@
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, require};
+use serde_json::{json, Value};
+
+pub use intellex_types::mirroring::{MAX_MIRRORED_ARGS_LENGTH, MIRRORABLE_METHODS};
+
+use crate::events;
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Mutating methods whose calls emit a `call_mirrored` event with their
+    /// arguments, for a relayer to replay against a canary deployment.
+    /// Must come from `MIRRORABLE_METHODS`; an empty list turns mirroring
+    /// off. Owner only.
+    pub fn set_mirrored_methods(&mut self, methods: Vec<String>) {
+        self.assert_owner();
+        let mut methods = methods;
+        methods.sort();
+        methods.dedup();
+        for method in &methods {
+            require!(
+                MIRRORABLE_METHODS.contains(&method.as_str()),
+                format!("Method {} cannot be mirrored", method)
+            );
+        }
+        self.mirrored_methods = methods;
+        events::emit("mirrored_methods_changed", json!({ "methods": self.mirrored_methods }));
+    }
+
+    pub fn get_mirrored_methods(&self) -> Vec<String> {
+        self.mirrored_methods.clone()
+    }
+}
+
+impl AgentRegistration {
+    /// Emits `call_mirrored` for `method` if it is mirrored. `args` is only
+    /// built then, so unmirrored calls pay nothing for it.
+    pub(crate) fn mirror_call(&self, method: &str, args: impl FnOnce() -> Value) {
+        if !self.mirrored_methods.iter().any(|mirrored| mirrored == method) {
+            return;
+        }
+        let args = args();
+        let oversized = args.to_string().len() > MAX_MIRRORED_ARGS_LENGTH;
+        events::emit(
+            "call_mirrored",
+            json!({
+                "method": method,
+                "caller": env::predecessor_account_id(),
+                "deposit": U128(env::attached_deposit().as_yoctonear()),
+                "args": if oversized { Value::Null } else { args },
+                "args_omitted": oversized,
+            }),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit_log::to_hex;
+    use crate::invites::InviteCode;
+    use crate::staking::MIN_REGISTRATION_STAKE;
+    use crate::tests::{deposit_stake, get_context, sample_metadata};
+    use near_sdk::test_utils::{accounts, get_logs};
+    use near_sdk::testing_env;
+    use near_sdk::NearToken;
+
+    #[test]
+    fn test_mirrored_registration_drops_invite_code() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.set_mirrored_methods(vec!["register_agent".to_string(), "register_agent".to_string()]);
+        assert_eq!(contract.get_mirrored_methods(), vec!["register_agent".to_string()]);
+        contract.mint_invite_codes(vec![InviteCode {
+            code_hash: to_hex(&env::sha256(b"secret-code")),
+            cohort: "beta-1".to_string(),
+            expires_at: None,
+            bound_to: None,
+        }]);
+
+        deposit_stake(&mut contract, accounts(1), MIN_REGISTRATION_STAKE);
+        testing_env!(context.predecessor_account_id(accounts(1)).attached_deposit(NearToken::from_near(1)).build());
+        contract.register_agent(sample_metadata(), Some("secret-code".to_string()));
+        let mirrored = get_logs()
+            .into_iter()
+            .find(|log| log.contains("\"call_mirrored\""))
+            .expect("registration was mirrored");
+        assert!(mirrored.contains("\"caller\":\"bob\""));
+        assert!(mirrored.contains("\"skills\":[\"Rust\"]"));
+        assert!(!mirrored.contains("secret-code"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Method block_agent cannot be mirrored")]
+    fn test_only_mirrorable_methods() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.set_mirrored_methods(vec!["block_agent".to_string()]);
+    }
+}
@@ -0,0 +1,166 @@
+use near_sdk::store::IterableSet;
+use near_sdk::{env, near_bindgen, require, AccountId};
+use serde_json::json;
+
+pub use intellex_types::operators::MAX_AGENT_OPERATORS;
+
+use crate::events;
+use crate::{AgentMetadata, AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Lets `account_id` update the caller's agent metadata and submit its
+    /// task results. Operators are dropped when the agent is transferred or
+    /// deregistered. The attached deposit, plus any storage balance, pays
+    /// for the entry. Agent owner only.
+    #[payable]
+    pub fn add_operator(&mut self, account_id: AccountId) {
+        when_not_paused!(self);
+        let agent_id = env::predecessor_account_id();
+        self.owned_agent(&agent_id);
+        require!(account_id != agent_id, "The agent owner cannot be its own operator");
+        self.credit_storage_deposit(&agent_id);
+        let initial_usage = env::storage_usage();
+
+        let mut operators = self
+            .agent_operators
+            .get(&agent_id)
+            .unwrap_or_else(|| IterableSet::new(format!("O_{}", agent_id).into_bytes()));
+        require!(operators.len() < MAX_AGENT_OPERATORS, "An agent can have at most 10 operators");
+        require!(operators.insert(account_id.clone()), "Already an operator");
+        self.agent_operators.insert(&agent_id, &operators);
+        // Flush the set before measuring what the entry took
+        drop(operators);
+        self.settle_storage(&agent_id, initial_usage);
+        events::emit("operator_added", json!({ "agent_id": agent_id, "operator_id": account_id }));
+    }
+
+    /// Revokes an operator of the caller's agent. Agent owner only.
+    pub fn remove_operator(&mut self, account_id: AccountId) {
+        when_not_paused!(self);
+        let agent_id = env::predecessor_account_id();
+        let mut operators = self
+            .agent_operators
+            .get(&agent_id)
+            .unwrap_or_else(|| env::panic_str("Not an operator"));
+        let initial_usage = env::storage_usage();
+        require!(operators.remove(&account_id), "Not an operator");
+        if operators.is_empty() {
+            self.agent_operators.remove(&agent_id);
+        } else {
+            self.agent_operators.insert(&agent_id, &operators);
+        }
+        drop(operators);
+        self.settle_storage(&agent_id, initial_usage);
+        events::emit("operator_removed", json!({ "agent_id": agent_id, "operator_id": account_id }));
+    }
+
+    pub fn get_operators(&self, agent_id: AccountId) -> Vec<AccountId> {
+        self.agent_operators
+            .get(&agent_id)
+            .map(|operators| operators.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn is_operator(&self, agent_id: AccountId, account_id: AccountId) -> bool {
+        self.is_agent_operator(&agent_id, &account_id)
+    }
+
+    /// `update_agent_metadata` for an operator of `agent_id`. Storage is
+    /// charged to the agent's balance, topped up by the attached deposit.
+    #[payable]
+    pub fn update_agent_metadata_as_operator(&mut self, agent_id: AccountId, metadata: AgentMetadata) {
+        when_not_paused!(self);
+        self.mirror_call("update_agent_metadata_as_operator", || {
+            json!({ "agent_id": agent_id, "metadata": metadata })
+        });
+        require!(
+            self.is_agent_operator(&agent_id, &env::predecessor_account_id()),
+            "Only an operator of the agent can update its metadata"
+        );
+        self.credit_storage_deposit(&agent_id);
+        let initial_usage = env::storage_usage();
+        self.replace_agent_metadata(&agent_id, metadata);
+        self.settle_storage(&agent_id, initial_usage);
+    }
+}
+
+impl AgentRegistration {
+    /// The agent a profile change applies to: `agent_id` if given, else the
+    /// caller's own. Panics unless the caller is that agent or one of its
+    /// operators.
+    pub(crate) fn managed_agent_id(&self, agent_id: Option<AccountId>) -> AccountId {
+        let caller = env::predecessor_account_id();
+        let agent_id = agent_id.unwrap_or_else(|| caller.clone());
+        require!(
+            caller == agent_id || self.is_agent_operator(&agent_id, &caller),
+            "Only the agent owner or its operators can update the agent"
+        );
+        agent_id
+    }
+
+    pub(crate) fn is_agent_operator(&self, agent_id: &AccountId, account_id: &AccountId) -> bool {
+        self.agent_operators
+            .get(agent_id)
+            .is_some_and(|operators| operators.contains(account_id))
+    }
+
+    /// Drops every operator of `agent_id`. Storage is left for the caller
+    /// to settle.
+    pub(crate) fn clear_operators(&mut self, agent_id: &AccountId) {
+        if let Some(mut operators) = self.agent_operators.remove(agent_id) {
+            operators.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::json_types::U128;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+    use near_sdk::NearToken;
+
+    #[test]
+    fn test_operator_updates_metadata_and_submits_results() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        testing_env!(context.attached_deposit(NearToken::from_near(1)).build());
+        contract.add_operator(accounts(2));
+        assert_eq!(contract.get_operators(accounts(1)), vec![accounts(2)]);
+
+        testing_env!(get_context(accounts(2)).build());
+        let metadata = AgentMetadata { purpose: "Ops".to_string(), ..sample_metadata() };
+        contract.update_agent_metadata_as_operator(accounts(1), metadata);
+        assert_eq!(contract.get_agent(&accounts(1)).unwrap().metadata.purpose, "Ops");
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        contract.add_skill("Go".to_string(), Some(accounts(1)));
+        assert_eq!(contract.get_agents_by_skill("Go".to_string(), None), vec![accounts(1)]);
+
+        testing_env!(get_context(accounts(3)).attached_deposit(NearToken::from_near(1)).build());
+        let task = contract.create_task("Translate a paper".to_string(), Vec::new(), U128(1));
+        contract.assign_task(task.task_id, accounts(1));
+        testing_env!(get_context(accounts(2)).build());
+        contract.submit_result(task.task_id, "Done".to_string());
+        assert_eq!(contract.get_task(task.task_id).unwrap().result, Some("Done".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only an operator of the agent can update its metadata")]
+    fn test_removed_operator_is_refused() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        testing_env!(context.attached_deposit(NearToken::from_near(1)).build());
+        contract.add_operator(accounts(2));
+        contract.remove_operator(accounts(2));
+
+        testing_env!(get_context(accounts(2)).build());
+        contract.update_agent_metadata_as_operator(accounts(1), sample_metadata());
+    }
+}
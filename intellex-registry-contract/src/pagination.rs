@@ -0,0 +1,91 @@
+use near_sdk::base64::engine::general_purpose::STANDARD;
+use near_sdk::base64::Engine;
+use near_sdk::{borsh, env, AccountId};
+
+pub use intellex_types::pagination::{Cursor, CursorKey, Page};
+
+pub const DEFAULT_PAGE_LIMIT: u64 = 50;
+pub const MAX_PAGE_LIMIT: u64 = 100;
+
+/// Page size for an optional `limit`: 50 by default, at most 100.
+pub(crate) fn page_limit(limit: Option<u64>) -> usize {
+    limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize
+}
+
+/// Key a cursor passed to `list` resumes after, panicking on tokens that are
+/// malformed or were issued by another view.
+pub(crate) fn decode_cursor(list: &str, cursor: Option<String>) -> Option<CursorKey> {
+    let cursor = cursor?;
+    let Cursor::V1 { list: issued_by, after } = STANDARD
+        .decode(cursor)
+        .ok()
+        .and_then(|bytes| borsh::from_slice::<Cursor>(&bytes).ok())
+        .unwrap_or_else(|| env::panic_str("Invalid cursor"));
+    if issued_by != list {
+        env::panic_str("Cursor belongs to another list");
+    }
+    Some(after)
+}
+
+/// First position to read for a cursor over an append-only list.
+pub(crate) fn sequence_start(list: &str, cursor: Option<String>) -> u64 {
+    match decode_cursor(list, cursor) {
+        None => 0,
+        Some(CursorKey::Sequence(last)) => last + 1,
+        Some(_) => env::panic_str("Invalid cursor"),
+    }
+}
+
+/// Account a cursor over an account-ordered list resumes after.
+pub(crate) fn account_after(list: &str, cursor: Option<String>) -> Option<AccountId> {
+    match decode_cursor(list, cursor) {
+        None => None,
+        Some(CursorKey::Account(last)) => Some(last),
+        Some(_) => env::panic_str("Invalid cursor"),
+    }
+}
+
+/// Takes up to `limit` keyed items, with a cursor after the last one when
+/// more remain.
+pub(crate) fn paginate<T>(list: &str, items: impl Iterator<Item = (CursorKey, T)>, limit: usize) -> Page<T> {
+    let mut keyed: Vec<(CursorKey, T)> = items.take(limit + 1).collect();
+    let next_cursor = if keyed.len() > limit {
+        keyed.truncate(limit);
+        keyed.last().map(|(key, _)| encode_cursor(list, key.clone()))
+    } else {
+        None
+    };
+    Page { items: keyed.into_iter().map(|(_, item)| item).collect(), next_cursor }
+}
+
+fn encode_cursor(list: &str, after: CursorKey) -> String {
+    STANDARD.encode(borsh::to_vec(&Cursor::V1 { list: list.to_string(), after }).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::get_context;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn test_paginate_round_trips_cursor() {
+        testing_env!(get_context(accounts(0)).build());
+        let items = || (0..5u64).map(|i| (CursorKey::Sequence(i), i));
+        let page = paginate("get_tasks", items(), 2);
+        assert_eq!(page.items, vec![0, 1]);
+        let start = sequence_start("get_tasks", page.next_cursor);
+        assert_eq!(start, 2);
+        let last = paginate("get_tasks", items().skip(4), 2);
+        assert_eq!((last.items, last.next_cursor), (vec![4], None));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cursor belongs to another list")]
+    fn test_cursor_rejected_by_other_list() {
+        testing_env!(get_context(accounts(0)).build());
+        let page = paginate("get_tasks", (0..3u64).map(|i| (CursorKey::Sequence(i), i)), 1);
+        sequence_start("get_skill_history", page.next_cursor);
+    }
+}
@@ -0,0 +1,80 @@
+use near_sdk::{env, near_bindgen};
+
+pub use intellex_types::protocol::{MethodVersionRequirement, PROTOCOL_VERSION};
+use intellex_types::skill_versions::Version;
+
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+/// Minimum client protocol version per method. Add an entry here whenever a
+/// method's argument format changes incompatibly, alongside bumping
+/// `PROTOCOL_VERSION`.
+pub const MIN_CLIENT_VERSIONS: &[(&str, &str)] = &[
+    // 2.0.0: list views take a cursor instead of `from_index` and return a `Page`
+    ("get_agent_task_history", "2.0.0"),
+    ("get_audit_anchors", "2.0.0"),
+    ("get_held_reputation_updates", "2.0.0"),
+    ("get_reconciliation_queue", "2.0.0"),
+    ("get_skill_history", "2.0.0"),
+    ("get_slash_history", "2.0.0"),
+    ("get_tasks", "2.0.0"),
+    ("search_agents_by_skill", "2.0.0"),
+    ("search_agents_for_requester", "2.0.0"),
+];
+
+/// Panics with a readable message if `client_version` is older than the
+/// version `requirements` list for `method_name`.
+pub(crate) fn assert_client_version(requirements: &[(&str, &str)], method_name: &str, client_version: &str) {
+    let client = Version::parse(client_version)
+        .unwrap_or_else(|| env::panic_str("Invalid client version"));
+    if let Some((_, min)) = requirements.iter().find(|(method, _)| *method == method_name) {
+        let min = Version::parse(min).unwrap();
+        if client < min {
+            env::panic_str(&format!(
+                "Client too old for method {}: requires protocol {} or newer, got {}",
+                method_name, min, client
+            ));
+        }
+    }
+}
+
+#[near_bindgen]
+impl AgentRegistration {
+    pub fn protocol_version(&self) -> String {
+        PROTOCOL_VERSION.to_string()
+    }
+
+    pub fn get_min_client_versions(&self) -> Vec<MethodVersionRequirement> {
+        MIN_CLIENT_VERSIONS
+            .iter()
+            .map(|(method_name, min_client_version)| MethodVersionRequirement {
+                method_name: method_name.to_string(),
+                min_client_version: min_client_version.to_string(),
+            })
+            .collect()
+    }
+
+    /// View for clients to call before `method_name`; panics with
+    /// "Client too old for method ..." when the client must upgrade.
+    pub fn check_client_version(&self, method_name: String, client_version: String) {
+        assert_client_version(MIN_CLIENT_VERSIONS, &method_name, &client_version);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REQUIREMENTS: &[(&str, &str)] = &[("register_agent", "2.0.0")];
+
+    #[test]
+    fn test_client_version_accepted() {
+        assert_client_version(REQUIREMENTS, "register_agent", "2.1.0");
+        assert_client_version(REQUIREMENTS, "get_agent", "0.1.0");
+    }
+
+    #[test]
+    #[should_panic(expected = "Client too old for method register_agent: requires protocol 2.0.0 or newer, got 1.4.0")]
+    fn test_client_version_too_old() {
+        assert_client_version(REQUIREMENTS, "register_agent", "1.4");
+    }
+}
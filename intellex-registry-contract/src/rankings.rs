@@ -0,0 +1,152 @@
+use near_sdk::{env, near_bindgen, require, AccountId};
+use serde_json::json;
+
+pub use intellex_types::rankings::{
+    ranking_epoch, RankedAgent, RankingSnapshot, SkillRanking, MAX_RANKED_SKILLS, RANKING_EPOCH_LENGTH,
+    RANKING_SNAPSHOT_SIZE,
+};
+
+use crate::events;
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Records the current epoch's leaderboards, overall and for each
+    /// ranked skill, so later audits can check what the rankings were when
+    /// a match was made. Callable by anyone, once per epoch; the snapshot
+    /// is charged to the caller's storage balance, topped up by the
+    /// attached deposit. Every agent is read, so gas grows with the
+    /// registry.
+    #[payable]
+    pub fn snapshot_rankings(&mut self) -> RankingSnapshot {
+        when_not_paused!(self);
+        let epoch = ranking_epoch(env::block_timestamp());
+        require!(
+            !self.ranking_snapshots.contains_key(&epoch),
+            "Rankings for this epoch are already snapshotted"
+        );
+        let taken_by = env::predecessor_account_id();
+        self.credit_storage_deposit(&taken_by);
+        let initial_usage = env::storage_usage();
+
+        let overall = self.leaderboard(self.reputation_synced_at.keys());
+        let by_skill = self
+            .ranked_skills
+            .iter()
+            .map(|skill| SkillRanking {
+                skill: skill.clone(),
+                agents: self
+                    .skill_agents(skill)
+                    .map(|agents| self.leaderboard(agents.iter()))
+                    .unwrap_or_default(),
+            })
+            .collect();
+        let snapshot = RankingSnapshot {
+            epoch,
+            taken_at: env::block_timestamp(),
+            taken_by: taken_by.clone(),
+            overall,
+            by_skill,
+        };
+        self.ranking_snapshots.insert(&epoch, &snapshot);
+        self.latest_ranking_epoch = Some(epoch);
+        self.settle_storage(&taken_by, initial_usage);
+        events::emit("rankings_snapshotted", json!({ "epoch": epoch, "taken_by": taken_by }));
+        snapshot
+    }
+
+    /// Sets the skills that get their own leaderboard from the next
+    /// snapshot on. Owner only.
+    pub fn set_ranked_skills(&mut self, skills: Vec<String>) {
+        self.assert_owner();
+        require!(skills.len() <= MAX_RANKED_SKILLS, "Cannot rank more than 10 skills");
+        self.ranked_skills = skills;
+        events::emit("ranked_skills_changed", json!({ "skills": self.ranked_skills }));
+    }
+
+    pub fn get_ranked_skills(&self) -> Vec<String> {
+        self.ranked_skills.clone()
+    }
+
+    pub fn get_ranking_snapshot(&self, epoch: u64) -> Option<RankingSnapshot> {
+        self.ranking_snapshots.get(&epoch)
+    }
+
+    pub fn get_latest_ranking_epoch(&self) -> Option<u64> {
+        self.latest_ranking_epoch
+    }
+}
+
+impl AgentRegistration {
+    /// The top `RANKING_SNAPSHOT_SIZE` active agents among `agent_ids`.
+    fn leaderboard<'a>(&self, agent_ids: impl Iterator<Item = &'a AccountId>) -> Vec<RankedAgent> {
+        let mut ranked: Vec<RankedAgent> = agent_ids
+            .filter(|agent_id| self.is_agent_active(agent_id))
+            .filter_map(|agent_id| {
+                let agent = self.agents.get(agent_id)?;
+                Some(RankedAgent { agent_id: agent_id.clone(), reputation: agent.reputation_info.reputation })
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.reputation.cmp(&a.reputation).then_with(|| a.agent_id.cmp(&b.agent_id)));
+        ranked.truncate(RANKING_SNAPSHOT_SIZE);
+        ranked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use crate::AgentInfo;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::{testing_env, NearToken};
+
+    #[test]
+    fn test_snapshot_freezes_rankings() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        let mut metadata = sample_metadata();
+        metadata.skills = vec!["Go".to_string()];
+        register(&mut contract, &mut context, metadata);
+
+        testing_env!(get_context(accounts(0)).build());
+        contract.set_ranked_skills(vec!["Rust".to_string()]);
+        let info = AgentInfo { reputation: 30, task_history: Vec::new(), reputation_history: Vec::new() };
+        contract.update_agent_reputation(accounts(2), info, String::new(), None);
+
+        testing_env!(get_context(accounts(3))
+            .block_timestamp(RANKING_EPOCH_LENGTH * 2 + 5)
+            .attached_deposit(NearToken::from_near(1))
+            .build());
+        contract.snapshot_rankings();
+        let info = AgentInfo { reputation: 90, task_history: Vec::new(), reputation_history: Vec::new() };
+        testing_env!(get_context(accounts(0)).build());
+        contract.update_agent_reputation(accounts(1), info, String::new(), None);
+
+        let snapshot = contract.get_ranking_snapshot(2).unwrap();
+        assert_eq!(contract.get_latest_ranking_epoch(), Some(2));
+        assert_eq!(
+            snapshot.overall,
+            vec![
+                RankedAgent { agent_id: accounts(2), reputation: 30 },
+                RankedAgent { agent_id: accounts(1), reputation: 0 },
+            ]
+        );
+        assert_eq!(snapshot.by_skill[0].skill, "Rust");
+        assert_eq!(snapshot.by_skill[0].agents, vec![RankedAgent { agent_id: accounts(1), reputation: 0 }]);
+        assert_eq!(snapshot.taken_by, accounts(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "Rankings for this epoch are already snapshotted")]
+    fn test_one_snapshot_per_epoch() {
+        testing_env!(get_context(accounts(3)).block_timestamp(10).attached_deposit(NearToken::from_near(1)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.snapshot_rankings();
+        testing_env!(get_context(accounts(3)).block_timestamp(RANKING_EPOCH_LENGTH - 1).build());
+        contract.snapshot_rankings();
+    }
+}
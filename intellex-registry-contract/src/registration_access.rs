@@ -0,0 +1,199 @@
+use near_sdk::{env, near_bindgen, require, AccountId};
+use serde_json::json;
+
+pub use intellex_types::registration_access::{Ban, RegistrationMode, MAX_ALLOWLIST_BATCH, MAX_BAN_REASON_LENGTH};
+
+use crate::events;
+use crate::pagination::{account_after, page_limit, paginate, CursorKey, Page};
+use crate::{AgentRegistration, AgentRegistrationExt, AgentStatus};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Opens registration to everyone, or limits it to the allowlist.
+    /// Agents already registered are unaffected. Owner only.
+    pub fn set_registration_mode(&mut self, mode: RegistrationMode) {
+        self.assert_owner();
+        require!(self.registration_mode != mode, "Registration mode is unchanged");
+        self.registration_mode = mode;
+        events::emit("registration_mode_changed", json!({ "mode": mode }));
+    }
+
+    pub fn get_registration_mode(&self) -> RegistrationMode {
+        self.registration_mode
+    }
+
+    /// Lets `account_ids` register while the mode is `Allowlist`. Accounts
+    /// already listed are skipped. Owner only; the registry pays for the
+    /// entries.
+    pub fn add_to_allowlist(&mut self, account_ids: Vec<AccountId>) {
+        self.assert_owner();
+        require!(!account_ids.is_empty(), "No accounts to allowlist");
+        require!(
+            account_ids.len() <= MAX_ALLOWLIST_BATCH,
+            "Cannot allowlist more than 100 accounts at once"
+        );
+        let added: Vec<AccountId> = account_ids
+            .into_iter()
+            .filter(|account_id| self.registration_allowlist.insert(account_id.clone()))
+            .collect();
+        events::emit("allowlist_updated", json!({ "added": added, "removed": [] }));
+    }
+
+    /// Owner only. Agents already registered stay registered.
+    pub fn remove_from_allowlist(&mut self, account_id: AccountId) {
+        self.assert_owner();
+        require!(self.registration_allowlist.remove(&account_id), "Account is not allowlisted");
+        events::emit("allowlist_updated", json!({ "added": [], "removed": [account_id] }));
+    }
+
+    pub fn is_allowlisted(&self, account_id: AccountId) -> bool {
+        self.registration_allowlist.contains(&account_id)
+    }
+
+    /// Ordered by account id.
+    pub fn get_allowlist(&self, cursor: Option<String>, limit: Option<u64>) -> Page<AccountId> {
+        let after = account_after("get_allowlist", cursor);
+        let mut accounts: Vec<&AccountId> = self
+            .registration_allowlist
+            .iter()
+            .filter(|account_id| after.as_ref().is_none_or(|after| *account_id > after))
+            .collect();
+        accounts.sort();
+
+        let accounts = accounts
+            .into_iter()
+            .map(|account_id| (CursorKey::Account(account_id.clone()), account_id.clone()));
+        paginate("get_allowlist", accounts, page_limit(limit))
+    }
+
+    /// Bars `account_id` from registering, receiving an agent or handing its
+    /// own agent off until unbanned, and suspends its agent if it has one.
+    /// Owner only; the registry pays for the record.
+    pub fn ban_agent(&mut self, account_id: AccountId, reason: String) {
+        self.assert_owner();
+        require!(reason.len() <= MAX_BAN_REASON_LENGTH, "Ban reason exceeds 256 bytes");
+        require!(!self.banned_accounts.contains_key(&account_id), "Account is already banned");
+        let ban = Ban { account_id: account_id.clone(), reason, banned_at: env::block_timestamp() };
+        self.banned_accounts.insert(account_id.clone(), ban.clone());
+        if self.get_agent_status(account_id.clone()).is_some_and(|status| status != AgentStatus::Suspended) {
+            self.change_status(&account_id, AgentStatus::Suspended);
+        }
+        events::emit("account_banned", json!({ "account_id": account_id, "reason": ban.reason }));
+    }
+
+    /// Lifts a ban. A suspended agent stays suspended until
+    /// `reinstate_agent`. Owner only.
+    pub fn unban_agent(&mut self, account_id: AccountId) {
+        self.assert_owner();
+        require!(self.banned_accounts.remove(&account_id).is_some(), "Account is not banned");
+        events::emit("account_unbanned", json!({ "account_id": account_id }));
+    }
+
+    pub fn get_ban(&self, account_id: AccountId) -> Option<Ban> {
+        self.banned_accounts.get(&account_id).cloned()
+    }
+
+    /// Ordered by account id.
+    pub fn get_banned_accounts(&self, cursor: Option<String>, limit: Option<u64>) -> Page<Ban> {
+        let after = account_after("get_banned_accounts", cursor);
+        let mut bans: Vec<&Ban> = self
+            .banned_accounts
+            .values()
+            .filter(|ban| after.as_ref().is_none_or(|after| &ban.account_id > after))
+            .collect();
+        bans.sort_by(|a, b| a.account_id.cmp(&b.account_id));
+
+        let bans = bans
+            .into_iter()
+            .map(|ban| (CursorKey::Account(ban.account_id.clone()), ban.clone()));
+        paginate("get_banned_accounts", bans, page_limit(limit))
+    }
+}
+
+impl AgentRegistration {
+    /// Panics unless `account_id` may become an agent: it must not be
+    /// banned and, in `Allowlist` mode, must be allowlisted.
+    pub(crate) fn assert_registration_permitted(&self, account_id: &AccountId) {
+        require!(!self.banned_accounts.contains_key(account_id), "Account is banned");
+        require!(
+            self.registration_mode == RegistrationMode::Open || self.registration_allowlist.contains(account_id),
+            "Account is not on the registration allowlist"
+        );
+    }
+
+    pub(crate) fn is_banned(&self, account_id: &AccountId) -> bool {
+        self.banned_accounts.contains_key(account_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::staking::MIN_REGISTRATION_STAKE;
+    use crate::tests::{deposit_stake, get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+    use near_sdk::NearToken;
+
+    fn setup() -> AgentRegistration {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.set_registration_mode(RegistrationMode::Allowlist);
+        contract.add_to_allowlist(vec![accounts(1), accounts(1)]);
+        deposit_stake(&mut contract, accounts(2), MIN_REGISTRATION_STAKE);
+        contract
+    }
+
+    #[test]
+    fn test_allowlisted_account_registers_and_ban_suspends() {
+        let mut contract = setup();
+        assert_eq!(contract.get_allowlist(None, None).items, vec![accounts(1)]);
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        register(&mut contract, &mut context, sample_metadata());
+
+        testing_env!(get_context(accounts(0)).build());
+        contract.ban_agent(accounts(1), "Spam".to_string());
+        assert_eq!(contract.get_agent_status(accounts(1)), Some(AgentStatus::Suspended));
+        assert_eq!(contract.get_banned_accounts(None, None).items[0].reason, "Spam");
+        contract.unban_agent(accounts(1));
+        contract.reinstate_agent(accounts(1));
+        assert_eq!(contract.get_agent_status(accounts(1)), Some(AgentStatus::Inactive));
+    }
+
+    #[test]
+    #[should_panic(expected = "Account is not on the registration allowlist")]
+    fn test_allowlist_mode_rejects_others() {
+        let mut contract = setup();
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        contract.register_agent(sample_metadata(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Agent is banned")]
+    fn test_banned_agent_cannot_be_reinstated() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        testing_env!(get_context(accounts(0)).build());
+        contract.ban_agent(accounts(1), "Fraud".to_string());
+        contract.reinstate_agent(accounts(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "A banned account cannot transfer its agent")]
+    fn test_banned_account_cannot_hand_off_its_agent() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        contract.transfer_agent_ownership(accounts(2));
+        testing_env!(get_context(accounts(0)).build());
+        contract.ban_agent(accounts(1), "Fraud".to_string());
+
+        deposit_stake(&mut contract, accounts(2), MIN_REGISTRATION_STAKE);
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        contract.accept_agent_transfer(accounts(1));
+    }
+}
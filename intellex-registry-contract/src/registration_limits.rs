@@ -0,0 +1,204 @@
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, require, AccountId, NearToken, Promise};
+use serde_json::json;
+
+pub use intellex_types::registration_limits::{RegistrationBond, RegistrationLimits};
+
+use crate::events;
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Changes the anti-sybil limits on registration. Bonds already locked
+    /// keep their unlock time. Owner only.
+    pub fn set_registration_limits(&mut self, limits: RegistrationLimits) {
+        self.assert_owner();
+        self.registration_limits = limits;
+        events::emit("registration_limits_changed", json!({ "limits": limits }));
+    }
+
+    pub fn get_registration_limits(&self) -> RegistrationLimits {
+        self.registration_limits
+    }
+
+    pub fn get_registration_bond(&self, account_id: AccountId) -> Option<RegistrationBond> {
+        self.registration_bonds.get(&account_id)
+    }
+
+    /// Sends the caller's registration bond back once it has unlocked,
+    /// whether or not the caller is still registered. Returns the amount.
+    pub fn claim_registration_bond(&mut self) -> U128 {
+        when_not_paused!(self);
+        let account_id = env::predecessor_account_id();
+        let bond = self
+            .registration_bonds
+            .get(&account_id)
+            .unwrap_or_else(|| env::panic_str("No registration bond"));
+        require!(env::block_timestamp() >= bond.unlocks_at, "Registration bond is still locked");
+        let initial_usage = env::storage_usage();
+        self.registration_bonds.remove(&account_id);
+        // A deregistered account's record is the registry's to pay for
+        if self.storage_accounts.contains_key(&account_id) {
+            self.settle_storage(&account_id, initial_usage);
+        }
+        Promise::new(account_id.clone()).transfer(NearToken::from_yoctonear(bond.amount));
+        events::emit("registration_bond_claimed", json!({ "account_id": account_id, "amount": U128(bond.amount) }));
+        U128(bond.amount)
+    }
+}
+
+impl AgentRegistration {
+    /// Enforces the re-registration cooldown and the per-block cap, counting
+    /// this registration towards the cap.
+    pub(crate) fn assert_registration_allowed(&mut self, account_id: &AccountId) {
+        let limits = self.registration_limits;
+        if let Some(deregistered_at) = self.deregistered_at.get(account_id) {
+            require!(
+                env::block_timestamp() >= deregistered_at.saturating_add(limits.reregistration_cooldown_ns),
+                "Account deregistered too recently to register again"
+            );
+            self.deregistered_at.remove(account_id);
+        }
+        let height = env::block_height();
+        let (block, count) = self.block_registrations;
+        let count = if block == height { count } else { 0 };
+        require!(
+            limits.max_registrations_per_block == 0 || count < limits.max_registrations_per_block,
+            "Too many registrations in this block; try again in the next one"
+        );
+        self.block_registrations = (height, count + 1);
+    }
+
+    /// Moves the registration bond from `account_id`'s storage balance into
+    /// a bond locked for `bond_lock_ns`, adding to any bond it still holds.
+    /// Storage is left for the caller to settle.
+    pub(crate) fn lock_registration_bond(&mut self, account_id: &AccountId) {
+        let limits = self.registration_limits;
+        if limits.registration_bond == 0 {
+            return;
+        }
+        let mut account = self.storage_accounts.get(account_id).unwrap_or_default();
+        require!(
+            account.total >= limits.registration_bond,
+            "Attach the registration bond on top of the storage deposit"
+        );
+        account.total -= limits.registration_bond;
+        self.storage_accounts.insert(account_id, &account);
+
+        let amount = self.registration_bonds.get(account_id).map_or(0, |bond| bond.amount);
+        let bond = RegistrationBond {
+            amount: amount + limits.registration_bond,
+            unlocks_at: env::block_timestamp().saturating_add(limits.bond_lock_ns),
+        };
+        self.registration_bonds.insert(account_id, &bond);
+        events::emit(
+            "registration_bond_locked",
+            json!({ "account_id": account_id, "amount": U128(limits.registration_bond), "unlocks_at": bond.unlocks_at }),
+        );
+    }
+
+    /// Starts the re-registration cooldown for a deregistered account. The
+    /// registry pays for the record.
+    pub(crate) fn record_deregistration(&mut self, account_id: &AccountId) {
+        if self.registration_limits.reregistration_cooldown_ns > 0 {
+            self.deregistered_at.insert(account_id, &env::block_timestamp());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::staking::MIN_REGISTRATION_STAKE;
+    use crate::tests::{deposit_stake, get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    const DAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+    #[test]
+    fn test_bond_accumulates_and_unlocks_after_cooldown() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        testing_env!(get_context(accounts(0)).build());
+        let bond = NearToken::from_near(2).as_yoctonear();
+        contract.set_registration_limits(RegistrationLimits {
+            registration_bond: bond,
+            bond_lock_ns: 30 * DAY,
+            max_registrations_per_block: 0,
+            reregistration_cooldown_ns: 7 * DAY,
+        });
+
+        testing_env!(context.attached_deposit(NearToken::from_near(3)).build());
+        contract.storage_deposit(None, None);
+        register(&mut contract, &mut context, sample_metadata());
+        assert_eq!(contract.get_registration_bond(accounts(1)).unwrap().amount, bond);
+        assert_eq!(contract.storage_balance_of(accounts(1)).unwrap().total, NearToken::from_near(2).as_yoctonear());
+        contract.deregister_agent();
+
+        testing_env!(context.block_timestamp(7 * DAY).attached_deposit(NearToken::from_near(3)).build());
+        contract.storage_deposit(None, None);
+        register(&mut contract, &mut context, sample_metadata());
+        let locked = contract.get_registration_bond(accounts(1)).unwrap();
+        assert_eq!(locked, RegistrationBond { amount: 2 * bond, unlocks_at: 37 * DAY });
+
+        testing_env!(get_context(accounts(1)).block_timestamp(37 * DAY).build());
+        assert_eq!(contract.claim_registration_bond(), U128(2 * bond));
+        assert_eq!(contract.get_registration_bond(accounts(1)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account deregistered too recently to register again")]
+    fn test_cooldown_rejects_early_reregistration() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        testing_env!(get_context(accounts(0)).build());
+        let limits = RegistrationLimits { reregistration_cooldown_ns: 7 * DAY, ..Default::default() };
+        contract.set_registration_limits(limits);
+        testing_env!(context.build());
+        register(&mut contract, &mut context, sample_metadata());
+        contract.deregister_agent();
+        testing_env!(context.block_timestamp(DAY).build());
+        register(&mut contract, &mut context, sample_metadata());
+    }
+
+    #[test]
+    #[should_panic(expected = "Too many registrations in this block")]
+    fn test_registrations_per_block_are_capped() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        testing_env!(get_context(accounts(0)).build());
+        let limits = RegistrationLimits { max_registrations_per_block: 1, ..Default::default() };
+        contract.set_registration_limits(limits);
+        testing_env!(context.build());
+        register(&mut contract, &mut context, sample_metadata());
+        deposit_stake(&mut contract, accounts(2), MIN_REGISTRATION_STAKE);
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        contract.register_agent(sample_metadata(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account deregistered too recently to register again")]
+    fn test_cooldown_applies_to_accepted_transfers() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        testing_env!(get_context(accounts(0)).build());
+        let limits = RegistrationLimits { reregistration_cooldown_ns: 7 * DAY, ..Default::default() };
+        contract.set_registration_limits(limits);
+        testing_env!(get_context(accounts(2)).build());
+        let mut other = get_context(accounts(2));
+        register(&mut contract, &mut other, sample_metadata());
+        contract.deregister_agent();
+
+        testing_env!(context.build());
+        register(&mut contract, &mut context, sample_metadata());
+        contract.transfer_agent_ownership(accounts(2));
+        deposit_stake(&mut contract, accounts(2), MIN_REGISTRATION_STAKE);
+        testing_env!(get_context(accounts(2)).block_timestamp(DAY).attached_deposit(NearToken::from_near(1)).build());
+        contract.accept_agent_transfer(accounts(1));
+    }
+}
@@ -0,0 +1,65 @@
+use near_sdk::{env, near_bindgen};
+use serde_json::json;
+
+pub use intellex_types::registry_config::RegistryConfig;
+
+use crate::events;
+use crate::{AgentMetadata, AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Changes the limits on agent metadata. Agents already stored are kept
+    /// as they are; the new limits apply from their next update. Owner only.
+    pub fn set_registry_config(&mut self, config: RegistryConfig) {
+        self.assert_owner();
+        if let Err(message) = config.validate() {
+            env::panic_str(message);
+        }
+        self.registry_config = config;
+        events::emit("registry_config_changed", json!({ "config": config }));
+    }
+
+    pub fn get_registry_config(&self) -> RegistryConfig {
+        self.registry_config
+    }
+}
+
+impl AgentRegistration {
+    /// Panics with the first way `metadata` breaks `registry_config`.
+    pub(crate) fn assert_metadata_within_limits(&self, metadata: &AgentMetadata) {
+        if let Err(message) = self.registry_config.validate_metadata(metadata) {
+            env::panic_str(&message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    #[should_panic(expected = "Agent name must be at most 4 bytes, got 10")]
+    fn test_register_respects_configured_limits() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.set_registry_config(RegistryConfig { max_name_length: 4, ..RegistryConfig::default() });
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        register(&mut contract, &mut context, sample_metadata());
+    }
+
+    #[test]
+    #[should_panic(expected = "An agent can list at most 1 skills")]
+    fn test_add_skill_respects_skill_limit() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.set_registry_config(RegistryConfig { max_skills: 1, ..RegistryConfig::default() });
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        register(&mut contract, &mut context, sample_metadata());
+        contract.add_skill("Go".to_string(), None);
+    }
+}
@@ -0,0 +1,87 @@
+use near_sdk::{env, near_bindgen};
+
+pub use intellex_types::registry_stats::{
+    registration_hour, RegistryStats, REGISTRATION_BUCKETS, REGISTRATION_BUCKET_LENGTH,
+};
+
+use crate::pagination::page_limit;
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    pub fn get_registry_stats(&self) -> RegistryStats {
+        let now = registration_hour(env::block_timestamp());
+        RegistryStats {
+            total_agents: self.total_agents,
+            total_skills: self.indexed_skills.len() as u64,
+            registered_last_day: self.registrations_since(now, 24),
+            registered_last_week: self.registrations_since(now, REGISTRATION_BUCKETS),
+        }
+    }
+
+    /// Indexed skills, by lookup key, with the number of agents listing
+    /// each, from `from_index`. `limit` defaults to 50, at most 100. Removing
+    /// the last agent of a skill moves the last skill into its place, so
+    /// indexes are only stable while skills are added.
+    pub fn get_skill_counts(&self, from_index: u64, limit: Option<u64>) -> Vec<(String, u64)> {
+        self.indexed_skills
+            .iter()
+            .skip(from_index as usize)
+            .take(page_limit(limit))
+            .map(|skill| (skill.clone(), self.skills_index.get(skill).map_or(0, |agents| agents.len() as u64)))
+            .collect()
+    }
+}
+
+impl AgentRegistration {
+    /// Counts a registration in the current hour's bucket. Buckets are
+    /// reused a week later, so only `REGISTRATION_BUCKETS` are ever stored.
+    pub(crate) fn record_registration(&mut self) {
+        let hour = registration_hour(env::block_timestamp());
+        let slot = hour % REGISTRATION_BUCKETS;
+        let count = match self.registration_counts.get(&slot) {
+            Some((bucket_hour, count)) if bucket_hour == hour => count + 1,
+            _ => 1,
+        };
+        self.registration_counts.insert(&slot, &(hour, count));
+    }
+
+    /// Registrations in the `hours` hours up to and including `now`.
+    fn registrations_since(&self, now: u64, hours: u64) -> u64 {
+        (0..hours.min(now + 1))
+            .filter_map(|ago| {
+                let hour = now - ago;
+                self.registration_counts
+                    .get(&(hour % REGISTRATION_BUCKETS))
+                    .filter(|(bucket_hour, _)| *bucket_hour == hour)
+                    .map(|(_, count)| count)
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn test_registry_stats_count_recent_registrations() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+
+        testing_env!(context.predecessor_account_id(accounts(2)).block_timestamp(3 * 24 * REGISTRATION_BUCKET_LENGTH).build());
+        let mut metadata = sample_metadata();
+        metadata.skills = vec!["rust".to_string(), "Go".to_string()];
+        register(&mut contract, &mut context, metadata);
+
+        let stats = contract.get_registry_stats();
+        assert_eq!(stats, RegistryStats { total_agents: 2, total_skills: 2, registered_last_day: 1, registered_last_week: 2 });
+        assert_eq!(contract.get_skill_counts(0, None), vec![("rust".to_string(), 2), ("go".to_string(), 1)]);
+        assert_eq!(contract.get_skill_counts(1, Some(1)), vec![("go".to_string(), 1)]);
+    }
+}
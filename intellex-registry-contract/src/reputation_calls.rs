@@ -0,0 +1,231 @@
+use near_sdk::{env, near_bindgen, require, AccountId, Promise, PromiseResult};
+use serde_json::json;
+
+pub use intellex_types::reputation::PendingReputationCall;
+
+use crate::events;
+use crate::ext::ext_reputation;
+use crate::gas_config::tgas;
+use crate::pagination::{account_after, page_limit, paginate, CursorKey, Page};
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Checks the outcome of a call made with `call_reputation_contract`.
+    /// A failed `initialize_agent` puts the agent in the pending
+    /// initializations, for `retry_initialization`, and a failed `retry`
+    /// call is kept for `retry_reputation_call`. Any failure emits
+    /// `reputation_call_failed`. Returns whether the call went through.
+    #[private]
+    pub fn on_reputation_call(&mut self, method: String, agent_id: AccountId, retry: Option<PendingReputationCall>) -> bool {
+        let succeeded = matches!(env::promise_result(0), PromiseResult::Successful(_));
+        if method == "initialize_agent" {
+            if succeeded {
+                self.pending_initializations.remove(&agent_id);
+            } else if self.agents.contains_key(&agent_id) {
+                self.pending_initializations.insert(agent_id.clone());
+            }
+        }
+        if let Some(retry) = retry {
+            if succeeded {
+                self.pending_reputation_calls.remove(&agent_id);
+            } else {
+                self.pending_reputation_calls.insert(agent_id.clone(), retry);
+            }
+        }
+        if !succeeded {
+            events::emit("reputation_call_failed", json!({ "method": method, "agent_id": agent_id }));
+        }
+        succeeded
+    }
+
+    /// Sends `initialize_agent` again for an agent whose first one failed.
+    /// Anyone may call it; the agent stays pending until a call succeeds.
+    pub fn retry_initialization(&mut self, agent_id: AccountId) {
+        when_not_paused!(self);
+        require!(
+            self.pending_initializations.contains(&agent_id),
+            "Agent has no pending reputation initialization"
+        );
+        self.call_reputation_contract("initialize_agent", &agent_id, None, |reputation| {
+            reputation.initialize_agent(agent_id.clone())
+        });
+    }
+
+    /// Sends a failed `transfer_agent` or `remove_agent` again. Anyone may
+    /// call it; the call stays pending until it succeeds.
+    pub fn retry_reputation_call(&mut self, agent_id: AccountId) {
+        when_not_paused!(self);
+        let call = self
+            .pending_reputation_calls
+            .get(&agent_id)
+            .cloned()
+            .unwrap_or_else(|| env::panic_str("Agent has no failed reputation call"));
+        self.send_reputation_call(&agent_id, call);
+    }
+
+    pub fn get_pending_reputation_call(&self, agent_id: AccountId) -> Option<PendingReputationCall> {
+        self.pending_reputation_calls.get(&agent_id).cloned()
+    }
+
+    pub fn is_initialization_pending(&self, agent_id: AccountId) -> bool {
+        self.pending_initializations.contains(&agent_id)
+    }
+
+    /// Agents whose reputation record the reputation contract failed to
+    /// create. Ordered by agent id.
+    pub fn get_pending_initializations(&self, cursor: Option<String>, limit: Option<u64>) -> Page<AccountId> {
+        let after = account_after("get_pending_initializations", cursor);
+        let mut agents: Vec<&AccountId> = self
+            .pending_initializations
+            .iter()
+            .filter(|agent_id| after.as_ref().is_none_or(|after| *agent_id > after))
+            .collect();
+        agents.sort();
+
+        let agents = agents
+            .into_iter()
+            .map(|agent_id| (CursorKey::Account(agent_id.clone()), agent_id.clone()));
+        paginate("get_pending_initializations", agents, page_limit(limit))
+    }
+}
+
+impl AgentRegistration {
+    /// Makes the reputation contract call built by `call`, if there is a
+    /// reputation contract, with its outcome checked by
+    /// `on_reputation_call` under the name `method`. `retry` is kept for
+    /// `retry_reputation_call` if the call fails.
+    pub(crate) fn call_reputation_contract(
+        &self,
+        method: &str,
+        agent_id: &AccountId,
+        retry: Option<PendingReputationCall>,
+        call: impl FnOnce(ext_reputation::ReputationContractExt) -> Promise,
+    ) {
+        let Some(reputation_contract_id) = self.reputation_contract() else {
+            return;
+        };
+        let reputation = ext_reputation::ext(reputation_contract_id.clone())
+            .with_static_gas(tgas(self.gas_config.reputation_call_tgas));
+        call(reputation).then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(tgas(self.gas_config.reputation_callback_tgas))
+                .on_reputation_call(method.to_string(), agent_id.clone(), retry),
+        );
+    }
+
+    /// Sends `call` for `agent_id`, keeping it for `retry_reputation_call`
+    /// until it succeeds.
+    pub(crate) fn send_reputation_call(&self, agent_id: &AccountId, call: PendingReputationCall) {
+        match &call {
+            PendingReputationCall::Transfer { from } => {
+                let from = from.clone();
+                self.call_reputation_contract("transfer_agent", agent_id, Some(call), |reputation| {
+                    reputation.transfer_agent(from, agent_id.clone())
+                });
+            }
+            PendingReputationCall::Remove => {
+                self.call_reputation_contract("remove_agent", agent_id, Some(call), |reputation| {
+                    reputation.remove_agent(agent_id.clone())
+                });
+            }
+        }
+    }
+
+    /// The account the reputation contract keeps `agent_id`'s record under:
+    /// the previous owner's while a transfer of it is pending. Forgets that
+    /// pending transfer, which the caller's next call replaces.
+    pub(crate) fn reputation_record_id(&mut self, agent_id: &AccountId) -> AccountId {
+        match self.pending_reputation_calls.get(agent_id) {
+            Some(PendingReputationCall::Transfer { from }) => {
+                let from = from.clone();
+                self.pending_reputation_calls.remove(agent_id);
+                from
+            }
+            _ => agent_id.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::staking::MIN_REGISTRATION_STAKE;
+    use crate::tests::{deposit_stake, get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::{testing_env, NearToken};
+
+    fn resolve(contract: &mut AgentRegistration, method: &str, result: PromiseResult) -> bool {
+        resolve_call(contract, method, accounts(1), None, result)
+    }
+
+    fn resolve_call(
+        contract: &mut AgentRegistration,
+        method: &str,
+        agent_id: AccountId,
+        retry: Option<PendingReputationCall>,
+        result: PromiseResult,
+    ) -> bool {
+        testing_env!(
+            get_context(accounts(0)).build(),
+            near_sdk::test_vm_config(),
+            near_sdk::RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![result],
+        );
+        contract.on_reputation_call(method.to_string(), agent_id, retry)
+    }
+
+    #[test]
+    fn test_failed_initialization_is_retried_until_it_succeeds() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+
+        assert!(!resolve(&mut contract, "initialize_agent", PromiseResult::Failed));
+        assert_eq!(contract.get_pending_initializations(None, None).items, vec![accounts(1)]);
+        testing_env!(get_context(accounts(2)).build());
+        contract.retry_initialization(accounts(1));
+        assert!(contract.is_initialization_pending(accounts(1)));
+
+        assert!(resolve(&mut contract, "initialize_agent", PromiseResult::Successful(Vec::new())));
+        assert!(!contract.is_initialization_pending(accounts(1)));
+        // Other failures are only reported
+        assert!(!resolve(&mut contract, "add_task_result", PromiseResult::Failed));
+        assert!(!contract.is_initialization_pending(accounts(1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Agent has no pending reputation initialization")]
+    fn test_retry_requires_a_failed_initialization() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        contract.retry_initialization(accounts(1));
+    }
+
+    #[test]
+    fn test_failed_transfer_is_kept_until_a_retry_succeeds() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        contract.transfer_agent_ownership(accounts(2));
+        deposit_stake(&mut contract, accounts(2), MIN_REGISTRATION_STAKE);
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        contract.accept_agent_transfer(accounts(1));
+
+        let transfer = PendingReputationCall::Transfer { from: accounts(1) };
+        let failed = resolve_call(&mut contract, "transfer_agent", accounts(2), Some(transfer.clone()), PromiseResult::Failed);
+        assert!(!failed);
+        assert_eq!(contract.get_pending_reputation_call(accounts(2)), Some(transfer.clone()));
+        testing_env!(get_context(accounts(3)).build());
+        contract.retry_reputation_call(accounts(2));
+
+        let result = PromiseResult::Successful(Vec::new());
+        assert!(resolve_call(&mut contract, "transfer_agent", accounts(2), Some(transfer), result));
+        assert_eq!(contract.get_pending_reputation_call(accounts(2)), None);
+    }
+}
@@ -0,0 +1,119 @@
+use near_sdk::{env, near_bindgen, require, AccountId};
+use serde_json::json;
+
+use crate::events;
+use crate::reputation_history::compact_history;
+use crate::{AgentRegistration, AgentRegistrationExt, ReputationEngineConfig, TaskResult};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Changes how `ReputationMode::Local` scores task results. Scores
+    /// already stored are kept; the new rules apply from the next result.
+    /// Owner only.
+    pub fn set_reputation_engine_config(&mut self, config: ReputationEngineConfig) {
+        self.assert_owner();
+        require!(config.decay_bps <= 10_000, "Decay cannot exceed 10000 basis points");
+        require!(config.max_reputation > 0, "Maximum reputation must be positive");
+        self.reputation_engine = config;
+        events::emit("reputation_engine_config_changed", json!({ "config": config }));
+    }
+
+    pub fn get_reputation_engine_config(&self) -> ReputationEngineConfig {
+        self.reputation_engine
+    }
+}
+
+impl AgentRegistration {
+    /// Scores a task result into the agent's reputation in place of the
+    /// reputation contract's `add_task_result`. No-op for agents that have
+    /// deregistered since.
+    pub(crate) fn record_local_task_result(&mut self, agent_id: &AccountId, task_result: TaskResult) {
+        let Some(mut agent) = self.agents.get(agent_id) else {
+            return;
+        };
+        let reputation = self.reputation_engine.apply(agent.reputation_info.reputation, task_result.success);
+        self.move_task_history(agent_id, &mut agent.reputation_info);
+        self.append_task_results(agent_id, vec![task_result]);
+        self.rerank_agent(agent_id, Some(agent.reputation_info.reputation), Some(reputation));
+        let info = &mut agent.reputation_info;
+        info.reputation = reputation;
+        info.reputation_history.push((env::block_timestamp(), info.reputation));
+        compact_history(info);
+        agent.version += 1;
+        self.agents.insert(agent_id, &agent);
+        self.reputation_synced_at.insert(agent_id.clone(), env::block_timestamp());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use crate::ReputationMode;
+    use near_sdk::json_types::U128;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::{testing_env, NearToken};
+
+    fn local_registry() -> AgentRegistration {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new_with_reputation_mode(accounts(0), ReputationMode::Local);
+        register(&mut contract, &mut context, sample_metadata());
+        contract
+    }
+
+    fn complete_task(contract: &mut AgentRegistration, approve: bool) {
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        let task = contract.create_task("Summarize a paper".to_string(), Vec::new(), U128(1));
+        testing_env!(get_context(accounts(2)).build());
+        contract.assign_task(task.task_id, accounts(1));
+        testing_env!(get_context(accounts(1)).build());
+        contract.submit_result(task.task_id, "Summary".to_string());
+        testing_env!(get_context(accounts(2)).build());
+        if approve {
+            contract.approve_task(task.task_id);
+        } else {
+            contract.dispute_task(task.task_id, "Summary is wrong".to_string());
+            testing_env!(get_context(accounts(0)).build());
+            contract.resolve_dispute(
+                task.task_id,
+                crate::disputes::DisputeRuling { agent_share_bps: 0, report_failure: true },
+            );
+        }
+    }
+
+    #[test]
+    fn test_local_mode_scores_task_results() {
+        let mut contract = local_registry();
+        assert_eq!(contract.get_reputation_contract(), None);
+
+        complete_task(&mut contract, true);
+        complete_task(&mut contract, true);
+        assert_eq!(contract.get_agent_reputation(&accounts(1)), Some(19));
+        complete_task(&mut contract, false);
+        assert_eq!(contract.get_agent_reputation(&accounts(1)), Some(0));
+
+        let agent = contract.get_agent(&accounts(1)).unwrap();
+        assert_eq!(contract.get_agent_task_stats(accounts(1)).total, 3);
+        assert_eq!(agent.reputation_info.reputation_history.len(), 4);
+    }
+
+    #[test]
+    fn test_engine_config_applies_to_next_result() {
+        let mut contract = local_registry();
+        testing_env!(get_context(accounts(0)).build());
+        contract.set_reputation_engine_config(ReputationEngineConfig {
+            success_weight: 50,
+            ..ReputationEngineConfig::default()
+        });
+        complete_task(&mut contract, true);
+        assert_eq!(contract.get_agent_reputation(&accounts(1)), Some(50));
+    }
+
+    #[test]
+    #[should_panic(expected = "Reputation is computed locally")]
+    fn test_local_mode_has_nothing_to_sync() {
+        let mut contract = local_registry();
+        contract.sync_reputation(accounts(1));
+    }
+}
@@ -0,0 +1,99 @@
+use near_sdk::{env, near_bindgen, AccountId};
+
+pub use intellex_types::reputation::{
+    compact_reputation_history, reputation_history_range, REPUTATION_HISTORY_DETAIL_WINDOW,
+    REPUTATION_SNAPSHOT_INTERVAL,
+};
+
+use crate::pagination::{decode_cursor, page_limit, paginate, CursorKey, Page};
+use crate::{AgentInfo, AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// `(timestamp, reputation)` points from `from_timestamp` to
+    /// `to_timestamp` inclusive, oldest first; either bound may be left
+    /// open. Points older than 30 days are daily snapshots. History is
+    /// compacted when it is stored, and again here, so an agent without
+    /// recent updates still reads as daily snapshots.
+    pub fn get_agent_reputation_history(
+        &self,
+        agent_id: &AccountId,
+        from_timestamp: Option<u64>,
+        to_timestamp: Option<u64>,
+        cursor: Option<String>,
+        limit: Option<u64>,
+    ) -> Page<(u64, u64)> {
+        let after = match decode_cursor("get_agent_reputation_history", cursor) {
+            None => None,
+            Some(CursorKey::Point { timestamp, index }) => Some((timestamp, index)),
+            Some(_) => env::panic_str("Invalid cursor"),
+        };
+        let Some(agent) = self.agents.get(agent_id) else {
+            return Page::default();
+        };
+        let mut history = agent.reputation_info.reputation_history;
+        compact_reputation_history(&mut history, env::block_timestamp());
+        let points = reputation_history_range(&history, from_timestamp.unwrap_or(0), to_timestamp.unwrap_or(u64::MAX), after)
+            .map(|((timestamp, index), point)| (CursorKey::Point { timestamp, index }, point));
+        paginate("get_agent_reputation_history", points, page_limit(limit))
+    }
+}
+
+/// Compacts the reputation history of `info` before it is stored: points
+/// older than 30 days are thinned to the last one of each day.
+pub(crate) fn compact_history(info: &mut AgentInfo) {
+    compact_reputation_history(&mut info.reputation_history, env::block_timestamp());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    const DAY: u64 = REPUTATION_SNAPSHOT_INTERVAL;
+
+    #[test]
+    fn test_pushed_history_is_compacted_and_queried_by_range() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+
+        let reputation_history = vec![(DAY, 1), (DAY + 1, 2), (2 * DAY, 3), (40 * DAY, 4), (40 * DAY + 1, 5)];
+        let info = AgentInfo { reputation: 5, task_history: Vec::new(), reputation_history };
+        testing_env!(get_context(accounts(0)).block_timestamp(41 * DAY).build());
+        contract.update_agent_reputation(accounts(1), info, String::new(), None);
+
+        let all = contract.get_agent_reputation_history(&accounts(1), None, None, None, None);
+        assert_eq!(all.items, vec![(DAY + 1, 2), (2 * DAY, 3), (40 * DAY, 4), (40 * DAY + 1, 5)]);
+        let page = contract.get_agent_reputation_history(&accounts(1), Some(2 * DAY), None, None, Some(2));
+        assert_eq!(page.items, vec![(2 * DAY, 3), (40 * DAY, 4)]);
+        let rest = contract.get_agent_reputation_history(&accounts(1), Some(2 * DAY), None, page.next_cursor, Some(2));
+        assert_eq!((rest.items, rest.next_cursor), (vec![(40 * DAY + 1, 5)], None));
+        assert!(contract.get_agent_reputation_history(&accounts(1), None, Some(DAY), None, None).items.is_empty());
+
+        // Read later without another write, the view compacts on its own
+        testing_env!(get_context(accounts(0)).block_timestamp(71 * DAY).build());
+        let all = contract.get_agent_reputation_history(&accounts(1), None, None, None, None);
+        assert_eq!(all.items, vec![(DAY + 1, 2), (2 * DAY, 3), (40 * DAY + 1, 5)]);
+    }
+
+    #[test]
+    fn test_pages_split_points_sharing_a_timestamp() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+
+        let info = AgentInfo { reputation: 3, task_history: Vec::new(), reputation_history: vec![(5, 1), (5, 2), (5, 3)] };
+        testing_env!(get_context(accounts(0)).block_timestamp(10).build());
+        contract.update_agent_reputation(accounts(1), info, String::new(), None);
+
+        let page = contract.get_agent_reputation_history(&accounts(1), None, None, None, Some(2));
+        assert_eq!(page.items, vec![(5, 1), (5, 2)]);
+        let rest = contract.get_agent_reputation_history(&accounts(1), None, None, page.next_cursor, Some(2));
+        assert_eq!((rest.items, rest.next_cursor), (vec![(5, 3)], None));
+    }
+}
@@ -0,0 +1,165 @@
+use near_sdk::{env, near_bindgen, require, AccountId};
+
+use crate::continuity::{IncrementalChange, ReputationPush};
+use crate::reputation_history::compact_history;
+use crate::{Agent, AgentRegistration, AgentRegistrationExt, TaskResult};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Appends one task result to an agent's history, without resending
+    /// the whole `AgentInfo`. `tip_hash` is the `history_tip_hash` of the
+    /// reputation contract's `AgentInfo` with the result added, which the
+    /// registry cannot compute from its pruned copy. Reputation contract only.
+    pub fn record_task_result(&mut self, agent_id: AccountId, result: TaskResult, nonce: u64, tip_hash: String) {
+        self.push_incremental(agent_id, IncrementalChange::TaskResult(result), nonce, tip_hash);
+    }
+
+    /// Sets an agent's reputation and appends it to the reputation history.
+    /// `tip_hash` is as for `record_task_result`. Reputation contract only.
+    pub fn record_reputation_change(&mut self, agent_id: AccountId, new_value: u64, nonce: u64, tip_hash: String) {
+        self.push_incremental(agent_id, IncrementalChange::Reputation(new_value), nonce, tip_hash);
+    }
+
+    /// Nonce of the last incremental push applied for an agent, 0 before
+    /// any. The next push must carry a greater one.
+    pub fn get_reputation_push_nonce(&self, agent_id: AccountId) -> u64 {
+        self.reputation_push_nonces.get(&agent_id).unwrap_or_default()
+    }
+}
+
+impl AgentRegistration {
+    /// Applies an incremental push, or holds it behind whole-state pushes
+    /// while updates are paused or held ones await replay, so the two
+    /// kinds never reorder.
+    fn push_incremental(&mut self, agent_id: AccountId, change: IncrementalChange, nonce: u64, tip_hash: String) {
+        require!(
+            self.reputation_contract() == Some(&env::predecessor_account_id()),
+            "Only reputation contract can update reputation"
+        );
+        if self.paused || self.reputation_updates_paused || self.held_updates_head < self.held_updates_tail {
+            self.hold_reputation_update(agent_id, ReputationPush::Incremental { change, nonce, tip_hash });
+            return;
+        }
+        if let Err(reason) = self.apply_incremental_push(&agent_id, change, nonce, tip_hash) {
+            env::panic_str(&reason);
+        }
+    }
+
+    /// Checks an incremental push, records its nonce and applies it, or
+    /// says why it was rejected: an unknown agent, a stale nonce, or an
+    /// agent awaiting reconciliation.
+    pub(crate) fn apply_incremental_push(
+        &mut self,
+        agent_id: &AccountId,
+        change: IncrementalChange,
+        nonce: u64,
+        tip_hash: String,
+    ) -> Result<(), String> {
+        if self.reconciliation_queue.contains_key(agent_id) {
+            return Err("Agent reputation awaits reconciliation".to_string());
+        }
+        let mut agent = self.agents.get(agent_id).ok_or("Agent not found")?;
+        let last_nonce = self.reputation_push_nonces.get(agent_id).unwrap_or_default();
+        if nonce <= last_nonce {
+            return Err(format!("Stale reputation push: nonce must exceed {}", last_nonce));
+        }
+        self.reputation_push_nonces.insert(agent_id, &nonce);
+        self.move_task_history(agent_id, &mut agent.reputation_info);
+        match change {
+            IncrementalChange::TaskResult(result) => self.append_task_results(agent_id, vec![result]),
+            IncrementalChange::Reputation(new_value) => {
+                self.rerank_agent(agent_id, Some(agent.reputation_info.reputation), Some(new_value));
+                agent.reputation_info.reputation = new_value;
+                agent.reputation_info.reputation_history.push((env::block_timestamp(), new_value));
+            }
+        }
+        self.store_pushed_reputation(agent_id, agent, tip_hash);
+        Ok(())
+    }
+
+    /// Stores the agent with its pushed reputation. `tip_hash` becomes the
+    /// new history tip, so the tip is always the hash of the state the
+    /// reputation contract holds, whichever way it was delivered.
+    fn store_pushed_reputation(&mut self, agent_id: &AccountId, mut agent: Agent, tip_hash: String) {
+        compact_history(&mut agent.reputation_info);
+        self.advance_history_tip(agent_id, tip_hash);
+        agent.version += 1;
+        self.agents.insert(agent_id, &agent);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::continuity::history_tip_hash;
+    use crate::AgentInfo;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    fn setup() -> AgentRegistration {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        testing_env!(get_context(accounts(0)).block_timestamp(500).build());
+        contract
+    }
+
+    #[test]
+    fn test_incremental_pushes_advance_the_tip() {
+        let mut contract = setup();
+        // The reputation contract's own copy of the agent's state
+        let mut remote = AgentInfo { reputation: 0, task_history: Vec::new(), reputation_history: vec![(0, 0)] };
+        let result = TaskResult { task_id: "t1".to_string(), success: true, timestamp: 400, details: String::new() };
+        remote.task_history.push(result.clone());
+        contract.record_task_result(accounts(1), result, 1, history_tip_hash(&remote));
+        remote.reputation = 15;
+        remote.reputation_history.push((500, 15));
+        contract.record_reputation_change(accounts(1), 15, 2, history_tip_hash(&remote));
+
+        let info = contract.get_agent(&accounts(1)).unwrap().reputation_info;
+        assert_eq!(info.reputation, 15);
+        assert_eq!(contract.get_agent_task_history(&accounts(1), None, None).items[0].task_id, "t1");
+        assert_eq!(info.reputation_history.last(), Some(&(500, 15)));
+        assert_eq!(contract.get_history_tip(accounts(1)), history_tip_hash(&remote));
+        assert_eq!(contract.get_reputation_push_nonce(accounts(1)), 2);
+
+        // A whole-state push that follows chains from the reputation
+        // contract's own hash of what it delivered
+        let delivered = history_tip_hash(&remote);
+        remote.reputation = 20;
+        remote.reputation_history.push((500, 20));
+        remote.task_history.push(TaskResult { task_id: "t2".to_string(), success: false, timestamp: 450, details: String::new() });
+        contract.update_agent_reputation(accounts(1), remote.clone(), delivered, None);
+        assert!(contract.get_reconciliation_queue(None, None).items.is_empty());
+        assert_eq!(contract.get_agent_reputation(&accounts(1)), Some(20));
+        assert_eq!(contract.get_history_tip(accounts(1)), history_tip_hash(&remote));
+        assert_eq!(contract.get_agent_task_stats(accounts(1)).total, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Stale reputation push: nonce must exceed 5")]
+    fn test_delayed_push_is_rejected() {
+        let mut contract = setup();
+        contract.record_reputation_change(accounts(1), 30, 5, String::new());
+        contract.record_reputation_change(accounts(1), 20, 4, String::new());
+    }
+
+    #[test]
+    fn test_pushes_held_while_paused_are_replayed_in_order() {
+        let mut contract = setup();
+        contract.pause_reputation_updates();
+        contract.record_reputation_change(accounts(1), 30, 2, String::new());
+        // Checked on replay: stale behind the push held before it
+        contract.record_reputation_change(accounts(1), 10, 1, String::new());
+        assert_eq!(contract.get_held_reputation_update_count(), 2);
+        assert_eq!(contract.get_agent_reputation(&accounts(1)), Some(0));
+
+        contract.resume_reputation_updates();
+        contract.record_reputation_change(accounts(1), 40, 3, String::new());
+        assert_eq!(contract.replay_held_reputation_updates(None), 3);
+        assert_eq!(contract.get_agent_reputation(&accounts(1)), Some(40));
+        assert_eq!(contract.get_reputation_push_nonce(accounts(1)), 3);
+    }
+}
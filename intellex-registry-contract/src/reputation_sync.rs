@@ -0,0 +1,250 @@
+use near_sdk::{env, near_bindgen, require, AccountId, Gas, Promise, PromiseResult};
+
+pub use intellex_types::reputation::{
+    reputation_cache_ttl, ReputationSyncResult, ReputationWithTtl, StaleReputation, REPUTATION_CACHE_TTL,
+};
+
+use crate::continuity::history_tip_hash;
+use crate::ext::ext_reputation;
+use crate::gas_config::{tgas, GasConfig};
+use crate::pagination::page_limit;
+use crate::reputation_history::compact_history;
+use crate::{AgentInfo, AgentRegistration, AgentRegistrationExt};
+
+/// Most agents one `sync_reputation_batch` call can refresh.
+pub const MAX_REPUTATION_SYNC_BATCH: usize = 20;
+/// Gas kept for scheduling the promises themselves.
+const GAS_FOR_BATCH_SCHEDULING: Gas = Gas::from_tgas(5);
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// `sync_reputation` for up to 20 agents at once. Every `get_agent_info`
+    /// call gets at least the configured reputation call budget, and one
+    /// callback applies the results, returning an outcome per agent. Gas
+    /// left over is split evenly between the calls and the callback. Fails
+    /// up front when the attached gas cannot cover the whole batch.
+    pub fn sync_reputation_batch(&mut self, agent_ids: Vec<AccountId>) -> Promise {
+        when_not_paused!(self);
+        require!(!agent_ids.is_empty(), "No agents to sync");
+        require!(
+            agent_ids.len() <= MAX_REPUTATION_SYNC_BATCH,
+            "Cannot sync more than 20 agents at once"
+        );
+        let required = batch_gas(&self.gas_config, agent_ids.len());
+        let available = env::prepaid_gas().saturating_sub(env::used_gas());
+        if available < required {
+            env::panic_str(&format!(
+                "Syncing {} agents needs at least {} TGas",
+                agent_ids.len(),
+                required.as_tgas()
+            ));
+        }
+
+        let reputation_contract_id = self.expect_reputation_contract();
+        let calls = agent_ids
+            .iter()
+            .map(|agent_id| {
+                ext_reputation::ext(reputation_contract_id.clone())
+                    .with_static_gas(tgas(self.gas_config.reputation_call_tgas))
+                    .with_unused_gas_weight(1)
+                    .get_agent_info(agent_id.clone())
+            })
+            .reduce(Promise::and)
+            .unwrap();
+        calls.then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(callback_gas(&self.gas_config, agent_ids.len()))
+                .with_unused_gas_weight(1)
+                .on_reputation_batch_synced(agent_ids),
+        )
+    }
+
+    /// Applies every successful `get_agent_info` result of a batch, in the
+    /// order the agents were given.
+    #[private]
+    pub fn on_reputation_batch_synced(&mut self, agent_ids: Vec<AccountId>) -> Vec<ReputationSyncResult> {
+        agent_ids
+            .into_iter()
+            .enumerate()
+            .map(|(i, agent_id)| {
+                let error = self.apply_synced_reputation(&agent_id, env::promise_result(i as u64)).err();
+                ReputationSyncResult { agent_id, synced: error.is_none(), error }
+            })
+            .collect()
+    }
+
+    /// Agents whose reputation was last pushed or pulled more than
+    /// `max_age_ns` ago, oldest first; candidates for `sync_reputation_batch`.
+    /// Registration counts as the first sync. `limit` defaults to 50, at
+    /// most 100.
+    pub fn get_agents_with_stale_reputation(&self, max_age_ns: u64, limit: Option<u64>) -> Vec<StaleReputation> {
+        let now = env::block_timestamp();
+        let mut stale: Vec<StaleReputation> = self
+            .reputation_synced_at
+            .iter()
+            .filter(|(_, synced_at)| now.saturating_sub(**synced_at) > max_age_ns)
+            .map(|(agent_id, synced_at)| StaleReputation { agent_id: agent_id.clone(), last_synced: *synced_at })
+            .collect();
+        stale.sort_by(|a, b| (a.last_synced, &a.agent_id).cmp(&(b.last_synced, &b.agent_id)));
+        stale.truncate(page_limit(limit));
+        stale
+    }
+
+    /// The cached reputation with the time it was last synced, so callers
+    /// can decide whether to trust it or sync first.
+    pub fn get_agent_reputation_with_sync_time(&self, agent_id: AccountId) -> Option<(u64, u64)> {
+        let reputation = self.agents.get(&agent_id)?.reputation_info.reputation;
+        Some((reputation, self.reputation_synced_at.get(&agent_id).copied().unwrap_or_default()))
+    }
+
+    /// Reputation for consumer contracts, with how long to cache it: no
+    /// caching while the agent's pushes await reconciliation or pushes are
+    /// being held, `REPUTATION_CACHE_TTL` otherwise. Called through
+    /// `intellex_types::ext::ext_agent_registry`.
+    pub fn get_reputation_with_ttl(&self, agent_id: AccountId) -> Option<ReputationWithTtl> {
+        let reputation = self.agents.get(&agent_id)?.reputation_info.reputation;
+        let update_pending = self.reconciliation_queue.contains_key(&agent_id)
+            || self.held_updates_head < self.held_updates_tail;
+        Some(ReputationWithTtl {
+            reputation,
+            synced_at: self.reputation_synced_at.get(&agent_id).copied().unwrap_or_default(),
+            cache_ttl_ns: reputation_cache_ttl(update_pending),
+        })
+    }
+}
+
+impl AgentRegistration {
+    /// Replaces an agent's reputation with the `AgentInfo` returned by the
+    /// reputation contract's `get_agent_info`, or says why it could not.
+    pub(crate) fn apply_synced_reputation(&mut self, agent_id: &AccountId, result: PromiseResult) -> Result<(), String> {
+        let mut reputation_info = match result {
+            PromiseResult::Successful(value) => match serde_json::from_slice::<Option<AgentInfo>>(&value) {
+                Ok(Some(info)) => info,
+                Ok(None) => return Err("skipped: unknown to reputation contract".to_string()),
+                Err(_) => return Err("failed: malformed AgentInfo".to_string()),
+            },
+            PromiseResult::Failed => return Err("failed: get_agent_info call failed".to_string()),
+        };
+
+        let Some(mut agent) = self.agents.get(agent_id) else {
+            return Err("skipped: agent not registered".to_string());
+        };
+        // The pulled state is authoritative, so it re-anchors the tip
+        self.advance_history_tip(agent_id, history_tip_hash(&reputation_info));
+        self.move_task_history(agent_id, &mut reputation_info);
+        compact_history(&mut reputation_info);
+        self.rerank_agent(agent_id, Some(agent.reputation_info.reputation), Some(reputation_info.reputation));
+        agent.reputation_info = reputation_info;
+        agent.version += 1;
+        self.agents.insert(agent_id, &agent);
+        Ok(())
+    }
+}
+
+fn callback_gas(config: &GasConfig, agents: usize) -> Gas {
+    tgas(config.batch_callback_tgas)
+        .saturating_add(tgas(config.batch_callback_per_agent_tgas).saturating_mul(agents as u64))
+}
+
+/// Least gas a batch of `agents` needs: one reputation call each, the
+/// callback and scheduling.
+fn batch_gas(config: &GasConfig, agents: usize) -> Gas {
+    tgas(config.reputation_call_tgas)
+        .saturating_mul(agents as u64)
+        .saturating_add(callback_gas(config, agents))
+        .saturating_add(GAS_FOR_BATCH_SCHEDULING)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn test_batch_callback_reports_each_agent() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        register(&mut contract, &mut context, sample_metadata());
+
+        let info = AgentInfo { reputation: 42, task_history: Vec::new(), reputation_history: vec![(10, 42)] };
+        testing_env!(
+            get_context(accounts(0)).build(),
+            near_sdk::test_vm_config(),
+            near_sdk::RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Successful(serde_json::to_vec(&info).unwrap()), PromiseResult::Failed],
+        );
+        let results = contract.on_reputation_batch_synced(vec![accounts(1), accounts(2)]);
+
+        assert!(results[0].synced);
+        assert_eq!(results[1].error.as_deref(), Some("failed: get_agent_info call failed"));
+        assert_eq!(contract.get_agent_reputation(&accounts(1)), Some(42));
+        assert_eq!(contract.get_agent_reputation(&accounts(2)), Some(0));
+    }
+
+    #[test]
+    fn test_stale_agents_are_listed_oldest_first() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.block_timestamp(100).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        testing_env!(context.predecessor_account_id(accounts(2)).block_timestamp(200).build());
+        register(&mut contract, &mut context, sample_metadata());
+
+        let info = AgentInfo { reputation: 7, task_history: Vec::new(), reputation_history: vec![(300, 7)] };
+        testing_env!(get_context(accounts(0)).block_timestamp(300).build());
+        contract.update_agent_reputation(accounts(1), info, String::new(), None);
+
+        testing_env!(get_context(accounts(0)).block_timestamp(1_000).build());
+        let stale = contract.get_agents_with_stale_reputation(500, None);
+        assert_eq!(
+            stale,
+            vec![
+                StaleReputation { agent_id: accounts(2), last_synced: 200 },
+                StaleReputation { agent_id: accounts(1), last_synced: 300 },
+            ]
+        );
+        assert_eq!(contract.get_agents_with_stale_reputation(750, None).len(), 1);
+        assert_eq!(contract.get_agent_reputation_with_sync_time(accounts(1)), Some((7, 300)));
+        assert_eq!(contract.get_agent_reputation_with_sync_time(accounts(3)), None);
+    }
+
+    #[test]
+    fn test_reputation_ttl_drops_while_update_pending() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.block_timestamp(100).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        let read = contract.get_reputation_with_ttl(accounts(1)).unwrap();
+        assert_eq!(read, ReputationWithTtl { reputation: 0, synced_at: 100, cache_ttl_ns: REPUTATION_CACHE_TTL });
+
+        // A push that skips a state waits for reconciliation
+        let info = AgentInfo { reputation: 5, task_history: Vec::new(), reputation_history: Vec::new() };
+        testing_env!(get_context(accounts(0)).build());
+        contract.update_agent_reputation(accounts(1), info, "ab".repeat(32), None);
+        assert_eq!(contract.get_reputation_with_ttl(accounts(1)).unwrap().cache_ttl_ns, 0);
+        assert_eq!(contract.get_reputation_with_ttl(accounts(2)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Syncing 20 agents needs at least")]
+    fn test_batch_requires_gas_for_every_call() {
+        testing_env!(get_context(accounts(1)).prepaid_gas(Gas::from_tgas(100)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.sync_reputation_batch(vec![accounts(2); MAX_REPUTATION_SYNC_BATCH]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Syncing 2 agents needs at least 125 TGas")]
+    fn test_batch_requirement_follows_gas_config() {
+        testing_env!(get_context(accounts(0)).prepaid_gas(Gas::from_tgas(100)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.set_gas_config(GasConfig { reputation_call_tgas: 50, ..Default::default() });
+        contract.sync_reputation_batch(vec![accounts(2), accounts(3)]);
+    }
+}
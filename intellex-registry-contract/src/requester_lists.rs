@@ -0,0 +1,206 @@
+use near_sdk::store::IterableSet;
+use near_sdk::{env, near_bindgen, require, AccountId};
+
+pub use intellex_types::requester_lists::{
+    blocklist_entry_preimage, MAX_BLOCKLIST_SIZE, MAX_WATCHLIST_SIZE,
+};
+
+use crate::audit_log::{from_hex, to_hex};
+use crate::pagination::Page;
+use crate::search::{rank_key, AgentSummary, SortOrder};
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+/// Blocklist entry for `agent_id` on `requester`'s list:
+/// hex(sha256("{requester}:{agent_id}")).
+pub fn blocklist_entry_hash(requester: &AccountId, agent_id: &AccountId) -> String {
+    to_hex(&env::sha256(&blocklist_entry_preimage(requester.as_str(), agent_id.as_str())))
+}
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Adds a hashed entry to the caller's private blocklist. Compute the
+    /// hash off-chain (or with the `blocklist_entry_hash` view) so the
+    /// blocked account never appears in transaction arguments.
+    pub fn block_agent(&mut self, entry_hash: String) {
+        when_not_paused!(self);
+        require!(
+            from_hex(&entry_hash).is_some_and(|bytes| bytes.len() == 32),
+            "Blocklist entry must be 32 bytes of hex"
+        );
+        let requester = env::predecessor_account_id();
+        let mut blocklist = self
+            .blocklists
+            .get(&requester)
+            .unwrap_or_else(|| IterableSet::new(format!("B_{}", requester).into_bytes()));
+        let entry_hash = entry_hash.to_lowercase();
+        if !blocklist.contains(&entry_hash) {
+            require!(blocklist.len() < MAX_BLOCKLIST_SIZE as u32, "Blocklist is full");
+            blocklist.insert(entry_hash);
+        }
+        self.blocklists.insert(&requester, &blocklist);
+    }
+
+    pub fn unblock_agent(&mut self, entry_hash: String) {
+        when_not_paused!(self);
+        let requester = env::predecessor_account_id();
+        let Some(mut blocklist) = self.blocklists.get(&requester) else {
+            return;
+        };
+        blocklist.remove(&entry_hash.to_lowercase());
+        if blocklist.is_empty() {
+            self.blocklists.remove(&requester);
+        } else {
+            self.blocklists.insert(&requester, &blocklist);
+        }
+    }
+
+    /// Adds a registered agent to the caller's public watchlist.
+    pub fn watch_agent(&mut self, agent_id: AccountId) {
+        when_not_paused!(self);
+        require!(self.agents.contains_key(&agent_id), "Agent not registered");
+        let requester = env::predecessor_account_id();
+        let mut watchlist = self
+            .watchlists
+            .get(&requester)
+            .unwrap_or_else(|| IterableSet::new(format!("W_{}", requester).into_bytes()));
+        if !watchlist.contains(&agent_id) {
+            require!(watchlist.len() < MAX_WATCHLIST_SIZE as u32, "Watchlist is full");
+            watchlist.insert(agent_id);
+        }
+        self.watchlists.insert(&requester, &watchlist);
+    }
+
+    pub fn unwatch_agent(&mut self, agent_id: AccountId) {
+        when_not_paused!(self);
+        let requester = env::predecessor_account_id();
+        let Some(mut watchlist) = self.watchlists.get(&requester) else {
+            return;
+        };
+        watchlist.remove(&agent_id);
+        if watchlist.is_empty() {
+            self.watchlists.remove(&requester);
+        } else {
+            self.watchlists.insert(&requester, &watchlist);
+        }
+    }
+
+    pub fn blocklist_entry_hash(&self, requester: AccountId, agent_id: AccountId) -> String {
+        blocklist_entry_hash(&requester, &agent_id)
+    }
+
+    /// Hashed entries only; the blocked accounts are not recoverable.
+    pub fn get_blocklist(&self, requester: AccountId) -> Vec<String> {
+        self.blocklists
+            .get(&requester)
+            .map(|blocklist| blocklist.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn get_watchlist(&self, requester: AccountId) -> Vec<AccountId> {
+        self.watchlists
+            .get(&requester)
+            .map(|watchlist| watchlist.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// `search_agents_by_skill` as seen by `requester`: agents on their
+    /// blocklist are left out and watchlisted agents are listed first, each
+    /// group in `sort` order.
+    pub fn search_agents_for_requester(
+        &self,
+        requester: AccountId,
+        skill: String,
+        cursor: Option<String>,
+        limit: u64,
+        sort: Option<SortOrder>,
+        require_system_skills: Option<Vec<String>>,
+    ) -> Page<AgentSummary> {
+        let blocklist = self.blocklists.get(&requester);
+        let watchlist = self.watchlists.get(&requester);
+        let is_watched = |agent_id: &AccountId| watchlist.as_ref().is_some_and(|set| set.contains(agent_id));
+
+        let sort = sort.unwrap_or_default();
+        let mut agents: Vec<_> = self
+            .sorted_skill_agents(&skill, sort, &require_system_skills.unwrap_or_default())
+            .into_iter()
+            .filter(|(agent_id, _)| {
+                blocklist
+                    .as_ref()
+                    .is_none_or(|set| !set.contains(&blocklist_entry_hash(&requester, agent_id)))
+            })
+            .collect();
+        // Stable, so the requested order holds within each group
+        agents.sort_by_key(|(agent_id, _)| !is_watched(agent_id));
+
+        let agents = agents.into_iter().map(|(agent_id, agent)| {
+            let group = if is_watched(&agent_id) { 0 } else { 1 };
+            (rank_key(sort, group, &agent_id, &agent), agent_id, agent)
+        });
+        self.agent_page("search_agents_for_requester", sort, agents, cursor, limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use crate::AgentInfo;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn test_requester_lists_shape_search() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        for (i, reputation) in [(1, 20), (2, 95), (3, 50)] {
+            testing_env!(context.predecessor_account_id(accounts(i)).build());
+            register(&mut contract, &mut context, sample_metadata());
+            testing_env!(get_context(accounts(0)).build());
+            contract.update_agent_reputation(
+                accounts(i),
+                AgentInfo { reputation, task_history: Vec::new(), reputation_history: Vec::new() },
+                String::new(),
+                None,
+            );
+        }
+
+        testing_env!(context.predecessor_account_id(accounts(4)).build());
+        contract.block_agent(blocklist_entry_hash(&accounts(4), &accounts(2)));
+        contract.watch_agent(accounts(1));
+        assert_eq!(contract.get_watchlist(accounts(4)), vec![accounts(1)]);
+        assert_ne!(contract.get_blocklist(accounts(4))[0], blocklist_entry_hash(&accounts(5), &accounts(2)));
+
+        let ids = |page: Page<AgentSummary>| -> Vec<AccountId> {
+            page.items.into_iter().map(|summary| summary.agent_id).collect()
+        };
+        assert_eq!(
+            ids(contract.search_agents_for_requester(accounts(4), "Rust".to_string(), None, 10, None, None)),
+            vec![accounts(1), accounts(3)]
+        );
+        // Other requesters are unaffected
+        assert_eq!(
+            ids(contract.search_agents_for_requester(accounts(5), "Rust".to_string(), None, 10, None, None)),
+            vec![accounts(2), accounts(3), accounts(1)]
+        );
+
+        contract.unblock_agent(blocklist_entry_hash(&accounts(4), &accounts(2)));
+        contract.unwatch_agent(accounts(1));
+        assert!(contract.get_blocklist(accounts(4)).is_empty());
+        assert_eq!(
+            ids(contract.search_agents_for_requester(accounts(4), "Rust".to_string(), None, 10, None, None)),
+            vec![accounts(2), accounts(3), accounts(1)]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Blocklist is full")]
+    fn test_blocklist_size_cap() {
+        let context = get_context(accounts(4));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        for i in 0..=MAX_BLOCKLIST_SIZE {
+            contract.block_agent(to_hex(&env::sha256(&i.to_le_bytes())));
+        }
+    }
+}
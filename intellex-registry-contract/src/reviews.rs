@@ -0,0 +1,239 @@
+use near_sdk::store::Vector;
+use near_sdk::{env, near_bindgen, require, AccountId};
+use serde_json::json;
+
+pub use intellex_types::reviews::{
+    Review, ReviewStats, MAX_REVIEW_COMMENT_LENGTH, MAX_REVIEW_RATING, MIN_REVIEW_RATING,
+};
+
+use crate::events;
+use crate::pagination::{page_limit, paginate, sequence_start, CursorKey, Page};
+use crate::tasks::TaskStatus;
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Rates `agent_id` for `task_id`, a completed task the caller created
+    /// and the agent delivered. One review per task. The attached deposit,
+    /// plus any storage balance, pays for the review.
+    #[payable]
+    pub fn submit_review(&mut self, agent_id: AccountId, task_id: u64, rating: u8, comment: String) -> Review {
+        when_not_paused!(self);
+        self.mirror_call("submit_review", || {
+            json!({ "agent_id": agent_id, "task_id": task_id, "rating": rating, "comment": comment })
+        });
+        require!(
+            (MIN_REVIEW_RATING..=MAX_REVIEW_RATING).contains(&rating),
+            "Rating must be between 1 and 5"
+        );
+        require!(comment.len() <= MAX_REVIEW_COMMENT_LENGTH, "Review comment exceeds 512 bytes");
+        let reviewer = env::predecessor_account_id();
+        let task = self.get_task(task_id).unwrap_or_else(|| env::panic_str("Task not found"));
+        require!(task.creator == reviewer, "Only the task creator can review it");
+        require!(task.agent_id.as_ref() == Some(&agent_id), "Agent did not work on this task");
+        // An agent since moved or deregistered left this account behind
+        require!(self.agents.contains_key(&agent_id), "Agent not registered");
+        require!(task.status == TaskStatus::Completed, "Only completed tasks can be reviewed");
+        require!(!self.task_reviews.contains_key(&task_id), "Task already reviewed");
+        self.credit_storage_deposit(&reviewer);
+        let initial_usage = env::storage_usage();
+
+        let review = Review {
+            task_id,
+            reviewer: reviewer.clone(),
+            rating,
+            comment,
+            submitted_at: env::block_timestamp(),
+        };
+        let mut reviews = match self.agent_reviews.get(&agent_id) {
+            Some(existing) => existing,
+            None => Vector::new(self.agent_collection_prefix('m', &agent_id)),
+        };
+        self.task_reviews.insert(&task_id, &reviews.len());
+        reviews.push(review.clone());
+        self.agent_reviews.insert(&agent_id, &reviews);
+
+        let mut stats = self.review_stats.get(&agent_id).unwrap_or_default();
+        stats.review_count += 1;
+        stats.rating_total += u64::from(rating);
+        self.review_stats.insert(&agent_id, &stats);
+        // Flush the pushed review before measuring what it took
+        drop(reviews);
+        self.settle_storage(&reviewer, initial_usage);
+
+        events::emit(
+            "review_submitted",
+            json!({ "agent_id": agent_id, "task_id": task_id, "reviewer": reviewer, "rating": rating }),
+        );
+        review
+    }
+
+    /// An agent's reviews, oldest first.
+    pub fn get_agent_reviews(&self, agent_id: AccountId, cursor: Option<String>, limit: Option<u64>) -> Page<Review> {
+        let start = sequence_start("get_agent_reviews", cursor);
+
+        self.agent_reviews
+            .get(&agent_id)
+            .map(|reviews| {
+                let reviews = reviews
+                    .iter()
+                    .enumerate()
+                    .skip(start as usize)
+                    .map(|(i, review)| (CursorKey::Sequence(i as u64), review.clone()));
+                paginate("get_agent_reviews", reviews, page_limit(limit))
+            })
+            .unwrap_or_default()
+    }
+
+    /// The review of `task_id`, also once the task has been compacted.
+    /// `None` once the agent has moved to another account, which lists the
+    /// review in its `get_agent_reviews`, or has been deregistered.
+    pub fn get_task_review(&self, task_id: u64) -> Option<Review> {
+        let agent_id = match self.get_task(task_id) {
+            Some(task) => task.agent_id,
+            None => self.get_archived_task(task_id)?.agent_id,
+        };
+        let index = self.task_reviews.get(&task_id)?;
+        self.agent_reviews
+            .get(agent_id.as_ref()?)?
+            .get(index)
+            .filter(|review| review.task_id == task_id)
+            .cloned()
+    }
+
+    pub fn get_review_stats(&self, agent_id: AccountId) -> ReviewStats {
+        self.review_stats.get(&agent_id).unwrap_or_default()
+    }
+}
+
+impl AgentRegistration {
+    /// Hands `agent_id`'s reviews and rating to `new_owner`. Only the list's
+    /// header moves; its entries stay under the agent's collection prefix.
+    pub(crate) fn move_reviews(&mut self, agent_id: &AccountId, new_owner: &AccountId) {
+        if let Some(reviews) = self.agent_reviews.remove(agent_id) {
+            self.agent_reviews.insert(new_owner, &reviews);
+        }
+        if let Some(stats) = self.review_stats.remove(agent_id) {
+            self.review_stats.insert(new_owner, &stats);
+        }
+    }
+
+    /// Drops `agent_id`'s reviews and rating. The entries are left behind
+    /// unreachable rather than cleared, which could exceed one call's gas;
+    /// a later registration of the account lists under a fresh prefix.
+    pub(crate) fn clear_reviews(&mut self, agent_id: &AccountId) {
+        self.agent_reviews.remove(agent_id);
+        self.review_stats.remove(agent_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::storage_cost;
+    use crate::staking::MIN_REGISTRATION_STAKE;
+    use crate::tests::{deposit_stake, get_context, register, sample_metadata};
+    use near_sdk::json_types::U128;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+    use near_sdk::NearToken;
+
+    /// Registers accounts(1) and runs a task from accounts(2) to completion.
+    fn completed_task(contract: &mut AgentRegistration) -> u64 {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        register(contract, &mut context, sample_metadata());
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(2)).build());
+        let task = contract.create_task("Summarize a paper".to_string(), Vec::new(), U128(10u128.pow(24)));
+        contract.assign_task(task.task_id, accounts(1));
+        testing_env!(context.build());
+        contract.submit_result(task.task_id, "Done".to_string());
+        testing_env!(get_context(accounts(2)).build());
+        contract.approve_task(task.task_id);
+        task.task_id
+    }
+
+    fn locked_storage(contract: &AgentRegistration) -> u128 {
+        let balance = contract.storage_balance_of(accounts(2)).unwrap();
+        balance.total - balance.available
+    }
+
+    #[test]
+    fn test_reviews_feed_summary_average() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        let task_id = completed_task(&mut contract);
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        let (locked, initial_usage) = (locked_storage(&contract), env::storage_usage());
+        let review = contract.submit_review(accounts(1), task_id, 4, "Solid work".to_string());
+        assert_eq!(locked_storage(&contract) - locked, storage_cost(env::storage_usage() - initial_usage));
+
+        assert_eq!(contract.get_agent_reviews(accounts(1), None, None).items, vec![review.clone()]);
+        assert_eq!(contract.get_task_review(task_id), Some(review));
+        let summary = contract.get_agent_summary(accounts(1)).unwrap();
+        assert_eq!(summary.review_count, 1);
+        assert_eq!(summary.average_rating_x100, Some(400));
+    }
+
+    #[test]
+    fn test_reviews_follow_a_transferred_agent() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        let task_id = completed_task(&mut contract);
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        let review = contract.submit_review(accounts(1), task_id, 4, String::new());
+
+        testing_env!(get_context(accounts(1)).build());
+        contract.transfer_agent_ownership(accounts(3));
+        deposit_stake(&mut contract, accounts(3), MIN_REGISTRATION_STAKE);
+        testing_env!(get_context(accounts(3)).attached_deposit(NearToken::from_near(1)).build());
+        contract.accept_agent_transfer(accounts(1));
+        assert_eq!(contract.get_agent_reviews(accounts(3), None, None).items, vec![review]);
+        assert_eq!(contract.get_review_stats(accounts(3)).review_count, 1);
+
+        // The old account starts over when it registers again
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        register(&mut contract, &mut context, sample_metadata());
+        assert!(contract.get_agent_reviews(accounts(1), None, None).items.is_empty());
+        assert_eq!(contract.get_review_stats(accounts(1)), ReviewStats::default());
+        assert_eq!(contract.get_task_review(task_id), None);
+    }
+
+    #[test]
+    fn test_deregistration_drops_reviews() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        let task_id = completed_task(&mut contract);
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        contract.submit_review(accounts(1), task_id, 2, String::new());
+
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        contract.deregister_agent();
+        register(&mut contract, &mut context, sample_metadata());
+        assert!(contract.get_agent_reviews(accounts(1), None, None).items.is_empty());
+        assert_eq!(contract.get_review_stats(accounts(1)), ReviewStats::default());
+    }
+
+    #[test]
+    #[should_panic(expected = "Task already reviewed")]
+    fn test_one_review_per_task() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        let task_id = completed_task(&mut contract);
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        contract.submit_review(accounts(1), task_id, 5, String::new());
+        contract.submit_review(accounts(1), task_id, 1, String::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the task creator can review it")]
+    fn test_only_creator_reviews() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        let task_id = completed_task(&mut contract);
+        testing_env!(get_context(accounts(3)).attached_deposit(NearToken::from_near(1)).build());
+        contract.submit_review(accounts(1), task_id, 5, String::new());
+    }
+}
@@ -0,0 +1,183 @@
+use std::cmp::Ordering;
+
+use near_sdk::{env, near_bindgen, AccountId};
+
+pub use intellex_types::summary::{AgentSummary, SortOrder};
+
+use crate::bands::band_for_reputation;
+use crate::pagination::{decode_cursor, paginate, CursorKey, Page};
+use crate::{Agent, AgentRegistration, AgentRegistrationExt};
+
+pub const MAX_SEARCH_LIMIT: u64 = 100;
+
+/// Group, sort value and account id of an agent in a ranked listing.
+pub(crate) type RankKey = (u8, u64, AccountId);
+
+pub(crate) fn rank_key(sort: SortOrder, group: u8, agent_id: &AccountId, agent: &Agent) -> RankKey {
+    let rank = match sort {
+        SortOrder::ReputationDesc | SortOrder::ReputationAsc => agent.reputation_info.reputation,
+        SortOrder::NewestFirst | SortOrder::OldestFirst => agent.registered_at,
+    };
+    (group, rank, agent_id.clone())
+}
+
+/// Lower groups first, then by sort value in `sort`'s direction, then by
+/// account id so pages are stable.
+fn compare_ranked(sort: SortOrder, a: &RankKey, b: &RankKey) -> Ordering {
+    let by_rank = match sort {
+        SortOrder::ReputationDesc | SortOrder::NewestFirst => b.1.cmp(&a.1),
+        SortOrder::ReputationAsc | SortOrder::OldestFirst => a.1.cmp(&b.1),
+    };
+    a.0.cmp(&b.0).then(by_rank).then_with(|| a.2.cmp(&b.2))
+}
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Agents listing `skill`, ordered by `sort` (reputation descending by
+    /// default) and paginated. `require_system_skills` keeps only agents
+    /// that also hold every listed `system:` skill.
+    pub fn search_agents_by_skill(
+        &self,
+        skill: String,
+        cursor: Option<String>,
+        limit: u64,
+        sort: Option<SortOrder>,
+        require_system_skills: Option<Vec<String>>,
+    ) -> Page<AgentSummary> {
+        let sort = sort.unwrap_or_default();
+        let agents = self
+            .sorted_skill_agents(&skill, sort, &require_system_skills.unwrap_or_default())
+            .into_iter()
+            .map(|(agent_id, agent)| (rank_key(sort, 0, &agent_id, &agent), agent_id, agent));
+        self.agent_page("search_agents_by_skill", sort, agents, cursor, limit)
+    }
+
+    pub fn get_agent_summary(&self, agent_id: AccountId) -> Option<AgentSummary> {
+        self.agents
+            .get(&agent_id)
+            .map(|agent| self.agent_summary(&agent_id, &agent))
+    }
+}
+
+impl AgentRegistration {
+    /// Agents listing `skill` and every skill in `also_required`, with their
+    /// records, in `sort` order. Ties are broken by account id so pages are
+    /// stable.
+    pub(crate) fn sorted_skill_agents(
+        &self,
+        skill: &str,
+        sort: SortOrder,
+        also_required: &[String],
+    ) -> Vec<(AccountId, Agent)> {
+        let Some(skill_agents) = self.skill_agents(skill) else {
+            return Vec::new();
+        };
+        let mut agents: Vec<(AccountId, Agent)> = skill_agents
+            .iter()
+            .filter_map(|agent_id| self.agents.get(agent_id).map(|agent| (agent_id.clone(), agent)))
+            .filter(|(_, agent)| Self::has_skills(agent, also_required))
+            .collect();
+
+        agents.sort_by(|(a_id, a), (b_id, b)| {
+            compare_ranked(sort, &rank_key(sort, 0, a_id, a), &rank_key(sort, 0, b_id, b))
+        });
+        agents
+    }
+
+    /// Pages through agents already in ranked order, resuming after the
+    /// cursor's key so agents joining or leaving earlier pages do not shift
+    /// the next one.
+    pub(crate) fn agent_page(
+        &self,
+        list: &str,
+        sort: SortOrder,
+        agents: impl Iterator<Item = (RankKey, AccountId, Agent)>,
+        cursor: Option<String>,
+        limit: u64,
+    ) -> Page<AgentSummary> {
+        let after = match decode_cursor(list, cursor) {
+            None => None,
+            Some(CursorKey::Ranked { group, rank, agent_id }) => Some((group, rank, agent_id)),
+            Some(_) => env::panic_str("Invalid cursor"),
+        };
+        let agents = agents
+            .skip_while(|(key, _, _)| after.as_ref().is_some_and(|after| compare_ranked(sort, key, after) != Ordering::Greater))
+            .map(|((group, rank, _), agent_id, agent)| {
+                let summary = self.agent_summary(&agent_id, &agent);
+                (CursorKey::Ranked { group, rank, agent_id }, summary)
+            });
+        paginate(list, agents, limit.min(MAX_SEARCH_LIMIT) as usize)
+    }
+
+    pub(crate) fn agent_summary(&self, agent_id: &AccountId, agent: &Agent) -> AgentSummary {
+        let stats = self.review_stats.get(agent_id).unwrap_or_default();
+        AgentSummary {
+            agent_id: agent_id.clone(),
+            name: agent.metadata.name.clone(),
+            skills: agent.metadata.skills.clone(),
+            reputation: agent.reputation_info.reputation,
+            status: agent.status,
+            band: band_for_reputation(agent.reputation_info.reputation),
+            handle: self.handle_holders.get(agent_id).filter(|handle| {
+                self.get_agent_by_handle(handle.clone()).as_ref() == Some(agent_id)
+            }),
+            social_profile: self.social_links.get(agent_id).map(|link| link.social_account_id),
+            online_now: self.online_at(agent_id, env::block_timestamp()),
+            review_count: stats.review_count,
+            average_rating_x100: stats.average_rating_x100(),
+            registered_at: agent.registered_at,
+            version: agent.version,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bands::ReputationBand;
+    use crate::tests::{get_context, register, sample_metadata};
+    use crate::AgentInfo;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn test_search_sorts_and_paginates() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        for (i, reputation) in [(1, 20), (2, 95), (3, 50)] {
+            testing_env!(context.predecessor_account_id(accounts(i)).block_timestamp(i as u64).build());
+            register(&mut contract, &mut context, sample_metadata());
+            testing_env!(get_context(accounts(0)).build());
+            contract.update_agent_reputation(
+                accounts(i),
+                AgentInfo { reputation, task_history: Vec::new(), reputation_history: Vec::new() },
+                String::new(),
+                None,
+            );
+        }
+
+        let ids = |page: Page<AgentSummary>| -> Vec<AccountId> {
+            page.items.into_iter().map(|summary| summary.agent_id).collect()
+        };
+        let top = contract.search_agents_by_skill("Rust".to_string(), None, 2, None, None);
+        assert_eq!(top.items[0].band, ReputationBand::Elite);
+        let cursor = top.next_cursor.clone();
+        assert_eq!(ids(top), vec![accounts(2), accounts(3)]);
+
+        // An agent joining the first page does not shift the second
+        testing_env!(context.predecessor_account_id(accounts(4)).block_timestamp(4).build());
+        register(&mut contract, &mut context, sample_metadata());
+        testing_env!(get_context(accounts(0)).build());
+        let info = AgentInfo { reputation: 99, task_history: Vec::new(), reputation_history: Vec::new() };
+        contract.update_agent_reputation(accounts(4), info, String::new(), None);
+        let rest = contract.search_agents_by_skill("Rust".to_string(), cursor, 10, None, None);
+        assert_eq!(rest.next_cursor, None);
+        assert_eq!(ids(rest), vec![accounts(1)]);
+        assert_eq!(
+            ids(contract.search_agents_by_skill("Rust".to_string(), None, 10, Some(SortOrder::NewestFirst), None)),
+            vec![accounts(4), accounts(3), accounts(2), accounts(1)]
+        );
+        assert!(contract.search_agents_by_skill("Go".to_string(), None, 10, None, None).items.is_empty());
+    }
+}
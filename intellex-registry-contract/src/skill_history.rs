@@ -0,0 +1,82 @@
+use near_sdk::store::Vector;
+use near_sdk::{env, near_bindgen, AccountId};
+
+pub use intellex_types::skill_history::{skills_at, SkillChange, SkillChangeKind};
+
+use crate::pagination::{page_limit, paginate, sequence_start, CursorKey, Page};
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Every skill an agent has added or dropped, oldest first. Kept after
+    /// deregistration so past engagements can still be audited.
+    pub fn get_skill_history(&self, agent_id: AccountId, cursor: Option<String>, limit: Option<u64>) -> Page<SkillChange> {
+        let start = sequence_start("get_skill_history", cursor);
+
+        self.skill_history
+            .get(&agent_id)
+            .map(|history| {
+                let changes = history
+                    .iter()
+                    .enumerate()
+                    .skip(start as usize)
+                    .map(|(i, change)| (CursorKey::Sequence(i as u64), change.clone()));
+                paginate("get_skill_history", changes, page_limit(limit))
+            })
+            .unwrap_or_default()
+    }
+
+    /// Skills the agent listed at `timestamp` (nanoseconds), reconstructed
+    /// from its skill history.
+    pub fn get_agent_skills_at(&self, agent_id: AccountId, timestamp: u64) -> Vec<String> {
+        self.skill_history
+            .get(&agent_id)
+            .map(|history| skills_at(history.iter().cloned(), timestamp))
+            .unwrap_or_default()
+    }
+}
+
+impl AgentRegistration {
+    pub(crate) fn record_skill_change(&mut self, agent_id: &AccountId, skill: &str, kind: SkillChangeKind) {
+        let mut history = match self.skill_history.get(agent_id) {
+            Some(existing) => existing,
+            None => Vector::new(format!("y_{}", agent_id).into_bytes()),
+        };
+        history.push(SkillChange {
+            skill: skill.to_string(),
+            kind,
+            changed_at: env::block_timestamp(),
+        });
+        self.skill_history.insert(agent_id, &history);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn test_skill_history_reconstructs_past_claims() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.block_timestamp(100).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+
+        testing_env!(context.block_timestamp(200).build());
+        let mut metadata = sample_metadata();
+        metadata.skills = vec!["Python".to_string()];
+        contract.update_agent_metadata(metadata);
+
+        testing_env!(context.block_timestamp(300).build());
+        contract.deregister_agent();
+
+        assert_eq!(contract.get_skill_history(accounts(1), None, None).items.len(), 4);
+        assert!(contract.get_agent_skills_at(accounts(1), 99).is_empty());
+        assert_eq!(contract.get_agent_skills_at(accounts(1), 150), vec!["Rust".to_string()]);
+        assert_eq!(contract.get_agent_skills_at(accounts(1), 250), vec!["Python".to_string()]);
+        assert!(contract.get_agent_skills_at(accounts(1), 300).is_empty());
+    }
+}
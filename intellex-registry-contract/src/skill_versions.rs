@@ -0,0 +1,115 @@
+use near_sdk::store::IterableSet;
+use near_sdk::{env, near_bindgen, AccountId};
+
+pub use intellex_types::skill_versions::{split_versioned_skill, Version, VersionReq, SKILL_VERSION_SEPARATOR};
+
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Returns agents listing `skill@<version>` for any version satisfying
+    /// `semver_req` (e.g. `"^5"`, `">=1.2, <2"`).
+    pub fn get_agents_by_skill_version(&self, skill: String, semver_req: String) -> Vec<AccountId> {
+        let req = VersionReq::parse(&semver_req)
+            .unwrap_or_else(|| env::panic_str("Invalid version requirement"));
+        let Some(versioned_skills) = self.skill_versions.get(&skill) else {
+            return Vec::new();
+        };
+
+        let mut agents: Vec<AccountId> = Vec::new();
+        for versioned_skill in versioned_skills.iter() {
+            let Some((_, version)) = split_versioned_skill(versioned_skill) else {
+                continue;
+            };
+            if !req.matches(&version) {
+                continue;
+            }
+            if let Some(skill_agents) = self.skill_agents(versioned_skill) {
+                for agent_id in skill_agents.iter() {
+                    if !agents.contains(agent_id) {
+                        agents.push(agent_id.clone());
+                    }
+                }
+            }
+        }
+        agents
+    }
+
+    /// Lists the versioned entries (e.g. `near-sdk@5.1`) known for a skill.
+    pub fn get_skill_versions(&self, skill: String) -> Vec<String> {
+        self.skill_versions
+            .get(&skill)
+            .map(|versions| versions.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+impl AgentRegistration {
+    /// Records `skill` under its base name if it carries a version suffix.
+    pub(crate) fn index_skill_version(&mut self, skill: &str) {
+        let Some((name, _)) = split_versioned_skill(skill) else {
+            return;
+        };
+        let name = name.to_string();
+        let mut versions = match self.skill_versions.get(&name) {
+            Some(existing_set) => existing_set,
+            None => IterableSet::new(format!("v_{}", name).into_bytes()),
+        };
+        versions.insert(skill.to_string());
+        self.skill_versions.insert(&name, &versions);
+    }
+
+    /// Forgets a versioned skill once no agent lists it any more.
+    pub(crate) fn unindex_skill_version(&mut self, skill: &str) {
+        let Some((name, _)) = split_versioned_skill(skill) else {
+            return;
+        };
+        let name = name.to_string();
+        if let Some(mut versions) = self.skill_versions.get(&name) {
+            versions.remove(skill);
+            if versions.is_empty() {
+                self.skill_versions.remove(&name);
+            } else {
+                self.skill_versions.insert(&name, &versions);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn test_get_agents_by_skill_version() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+
+        let mut metadata = sample_metadata();
+        metadata.skills = vec!["near-sdk@4.1".to_string(), "Rust".to_string()];
+        register(&mut contract, &mut context, metadata);
+
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        let mut metadata = sample_metadata();
+        metadata.skills = vec!["near-sdk@5.2.0".to_string()];
+        register(&mut contract, &mut context, metadata);
+
+        assert_eq!(
+            contract.get_agents_by_skill_version("near-sdk".to_string(), "^5".to_string()),
+            vec![accounts(2)]
+        );
+        assert_eq!(
+            contract
+                .get_agents_by_skill_version("near-sdk".to_string(), ">=4".to_string())
+                .len(),
+            2
+        );
+        assert!(contract
+            .get_agents_by_skill_version("Rust".to_string(), "*".to_string())
+            .is_empty());
+    }
+}
@@ -0,0 +1,200 @@
+use near_sdk::{env, near_bindgen, require};
+use serde_json::json;
+
+pub use intellex_types::skills_registry::{skill_lookup_key, SkillInfo, MAX_SKILL_ALIASES};
+
+use crate::events;
+use crate::pagination::page_limit;
+use crate::skill_versions::{split_versioned_skill, SKILL_VERSION_SEPARATOR};
+use crate::system_skills::is_system_skill;
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Adds `skill` to the canonical taxonomy, or replaces its aliases.
+    /// Registrations listing the skill or an alias, in any case, are
+    /// rewritten to `skill`. Owner only.
+    pub fn set_canonical_skill(&mut self, skill: String, aliases: Vec<String>) {
+        self.assert_owner();
+        require!(
+            !skill.is_empty() && skill.trim() == skill,
+            "Skill must be non-empty without surrounding whitespace"
+        );
+        require!(
+            !is_system_skill(&skill) && !skill.contains(SKILL_VERSION_SEPARATOR),
+            "Canonical skills cannot be system skills or carry a version"
+        );
+        require!(aliases.len() <= MAX_SKILL_ALIASES, "A skill can have at most 16 aliases");
+
+        if let Some(old_aliases) = self.canonical_skills.remove(&skill) {
+            for alias in &old_aliases {
+                self.skill_aliases.remove(&skill_lookup_key(alias));
+            }
+        }
+        for name in std::iter::once(&skill).chain(&aliases) {
+            let key = skill_lookup_key(name);
+            require!(!key.is_empty(), "Aliases must not be empty");
+            if let Some(existing) = self.skill_aliases.get(&key) {
+                require!(existing == skill, format!("{} already names the skill {}", name, existing));
+            }
+            self.skill_aliases.insert(&key, &skill);
+        }
+        self.canonical_skills.insert(skill.clone(), aliases.clone());
+        events::emit("canonical_skill_set", json!({ "skill": skill, "aliases": aliases }));
+    }
+
+    /// Drops `skill` and its aliases from the taxonomy. Agents keep listing
+    /// it. Owner only.
+    pub fn remove_canonical_skill(&mut self, skill: String) {
+        self.assert_owner();
+        let aliases = self
+            .canonical_skills
+            .remove(&skill)
+            .unwrap_or_else(|| env::panic_str("Skill is not in the taxonomy"));
+        for name in std::iter::once(&skill).chain(&aliases) {
+            self.skill_aliases.remove(&skill_lookup_key(name));
+        }
+        events::emit("canonical_skill_removed", json!({ "skill": skill }));
+    }
+
+    /// When strict, registrations and metadata updates may only list skills
+    /// in the taxonomy. Owner only.
+    pub fn set_strict_skill_taxonomy(&mut self, strict: bool) {
+        self.assert_owner();
+        self.strict_skill_taxonomy = strict;
+        events::emit("strict_skill_taxonomy_changed", json!({ "strict": strict }));
+    }
+
+    pub fn is_skill_taxonomy_strict(&self) -> bool {
+        self.strict_skill_taxonomy
+    }
+
+    /// The canonical taxonomy with per-skill agent counts, from
+    /// `from_index`. `limit` defaults to 50, at most 100. Removing a skill
+    /// moves the last one into its place, so indexes are only stable while
+    /// skills are added.
+    pub fn get_all_skills(&self, from_index: u64, limit: Option<u64>) -> Vec<SkillInfo> {
+        self.canonical_skills
+            .iter()
+            .skip(from_index as usize)
+            .take(page_limit(limit))
+            .map(|(skill, aliases)| SkillInfo {
+                skill: skill.clone(),
+                aliases: aliases.clone(),
+                agent_count: self.skill_agents(skill).map_or(0, |agents| agents.len() as u64),
+            })
+            .collect()
+    }
+
+    /// What `register_agent` would list `skill` as; `None` if it is not in
+    /// the taxonomy.
+    pub fn normalize_skill(&self, skill: String) -> Option<String> {
+        let (name, version) = split_skill_version(&skill);
+        self.skill_aliases
+            .get(&skill_lookup_key(name))
+            .map(|canonical| format!("{}{}", canonical, version))
+    }
+}
+
+impl AgentRegistration {
+    /// Rewrites `skills` to their canonical names and drops the duplicates
+    /// that leaves, including ones differing only in case or spacing. Unknown skills are kept as written unless the taxonomy
+    /// is strict; `system:` skills are left alone.
+    pub(crate) fn normalize_skills(&self, skills: Vec<String>) -> Vec<String> {
+        let mut normalized: Vec<String> = Vec::with_capacity(skills.len());
+        for skill in skills {
+            let skill = if is_system_skill(&skill) {
+                skill
+            } else {
+                self.normalize_skill(skill.clone()).unwrap_or_else(|| {
+                    require!(!self.strict_skill_taxonomy, format!("Unknown skill {}", skill));
+                    skill
+                })
+            };
+            if !lists_skill(&normalized, &skill) {
+                normalized.push(skill);
+            }
+        }
+        normalized
+    }
+}
+
+/// True if `skills` holds `skill` under any case or spacing.
+pub(crate) fn lists_skill(skills: &[String], skill: &str) -> bool {
+    let key = skill_lookup_key(skill);
+    skills.iter().any(|listed| skill_lookup_key(listed) == key)
+}
+
+/// Splits `skill` into its name and the `@version` suffix, if any.
+fn split_skill_version(skill: &str) -> (&str, &str) {
+    match split_versioned_skill(skill) {
+        Some((name, _)) => skill.split_at(name.len()),
+        None => (skill, ""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn test_registration_uses_canonical_skills() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.set_canonical_skill("Rust".to_string(), vec!["rustlang".to_string()]);
+        contract.set_canonical_skill("near-sdk".to_string(), Vec::new());
+
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        let mut metadata = sample_metadata();
+        metadata.skills = vec!["RUST".to_string(), "rustlang".to_string(), "NEAR-SDK@5.1.0".to_string()];
+        register(&mut contract, &mut context, metadata);
+
+        let skills = contract.get_agent(&accounts(1)).unwrap().metadata.skills;
+        assert_eq!(skills, vec!["Rust".to_string(), "near-sdk@5.1.0".to_string()]);
+        let all = contract.get_all_skills(0, None);
+        assert_eq!(all[0], SkillInfo { skill: "Rust".to_string(), aliases: vec!["rustlang".to_string()], agent_count: 1 });
+        assert_eq!(contract.get_all_skills(1, Some(1)).len(), 1);
+    }
+
+    #[test]
+    fn test_skill_index_ignores_case_and_spacing() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        let mut metadata = sample_metadata();
+        metadata.skills = vec!["Machine  Learning".to_string(), "machine learning".to_string()];
+        register(&mut contract, &mut context, metadata);
+
+        assert_eq!(contract.get_agent(&accounts(1)).unwrap().metadata.skills, vec!["Machine  Learning".to_string()]);
+        assert_eq!(contract.get_agents_by_skill(" MACHINE learning".to_string(), None), vec![accounts(1)]);
+        assert_eq!(contract.match_agents(vec!["machine learning".to_string()], 0, 10)[0].matched_skills.len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown skill Cobol")]
+    fn test_strict_taxonomy_rejects_unknown_skills() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.set_canonical_skill("Rust".to_string(), Vec::new());
+        contract.set_strict_skill_taxonomy(true);
+
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        let mut metadata = sample_metadata();
+        metadata.skills.push("Cobol".to_string());
+        register(&mut contract, &mut context, metadata);
+    }
+
+    #[test]
+    #[should_panic(expected = "rust already names the skill Rust")]
+    fn test_alias_cannot_name_two_skills() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.set_canonical_skill("Rust".to_string(), Vec::new());
+        contract.set_canonical_skill("Rust-lang".to_string(), vec!["rust".to_string()]);
+    }
+}
@@ -0,0 +1,131 @@
+use near_sdk::json_types::U128;
+use near_sdk::store::Vector;
+use near_sdk::{env, near_bindgen, require, AccountId};
+use serde_json::json;
+
+pub use intellex_types::slashing::{SlashEvent, MAX_SLASH_REASON_LENGTH};
+
+use crate::events;
+use crate::pagination::{page_limit, paginate, sequence_start, CursorKey, Page};
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Takes up to `amount` ITLX from an agent's stake for a failed task or
+    /// proven fraud. Stake in unstake cooldown is taken once the staked
+    /// balance runs out, so unstaking does not dodge a pending penalty.
+    /// Slashed ITLX stays locked in the registry. Reputation contract only,
+    /// or the owner when reputation is computed locally.
+    pub fn slash_agent(&mut self, agent_id: AccountId, amount: U128, reason: String) -> SlashEvent {
+        when_not_paused!(self);
+        let caller = env::predecessor_account_id();
+        require!(self.is_reputation_authority(&caller), "Only reputation contract can slash agents");
+        require!(amount.0 > 0, "Amount must be positive");
+        require!(
+            !reason.is_empty() && reason.len() <= MAX_SLASH_REASON_LENGTH,
+            "Slash reason must be between 1 and 256 bytes"
+        );
+
+        let mut stake = self.stakes.get(&agent_id).unwrap_or_default();
+        let from_staked = amount.0.min(stake.staked_balance);
+        let from_unstaking = (amount.0 - from_staked).min(stake.unstaking_balance);
+        stake.staked_balance -= from_staked;
+        stake.unstaking_balance -= from_unstaking;
+        if stake.unstaking_balance == 0 {
+            stake.unstake_available_at = None;
+        }
+        if stake.is_empty() {
+            self.stakes.remove(&agent_id);
+        } else {
+            self.stakes.insert(&agent_id, &stake);
+        }
+
+        let event = SlashEvent {
+            amount: from_staked + from_unstaking,
+            reason,
+            slashed_by: caller,
+            slashed_at: env::block_timestamp(),
+        };
+        self.total_slashed += event.amount;
+        let mut history = match self.slash_history.get(&agent_id) {
+            Some(existing) => existing,
+            None => Vector::new(format!("x_{}", agent_id).into_bytes()),
+        };
+        history.push(event.clone());
+        self.slash_history.insert(&agent_id, &history);
+
+        events::emit(
+            "agent_slashed",
+            json!({
+                "agent_id": agent_id,
+                "amount": U128(event.amount),
+                "requested_amount": amount,
+                "reason": event.reason,
+            }),
+        );
+        event
+    }
+
+    pub fn get_slash_history(&self, agent_id: AccountId, cursor: Option<String>, limit: Option<u64>) -> Page<SlashEvent> {
+        let start = sequence_start("get_slash_history", cursor);
+
+        self.slash_history
+            .get(&agent_id)
+            .map(|history| {
+                let events = history
+                    .iter()
+                    .enumerate()
+                    .skip(start as usize)
+                    .map(|(i, event)| (CursorKey::Sequence(i as u64), event.clone()));
+                paginate("get_slash_history", events, page_limit(limit))
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn get_total_slashed(&self) -> U128 {
+        U128(self.total_slashed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::staking::MIN_REGISTRATION_STAKE;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn test_slash_takes_staked_then_unstaking() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        contract.deregister_agent();
+        contract.unstake(U128(MIN_REGISTRATION_STAKE - 10));
+
+        testing_env!(get_context(accounts(0)).build());
+        let event = contract.slash_agent(accounts(1), U128(15), "Task abandoned".to_string());
+        assert_eq!(event.amount, 15);
+        let stake = contract.get_stake(accounts(1));
+        assert_eq!(stake.staked_balance, 0);
+        assert_eq!(stake.unstaking_balance, MIN_REGISTRATION_STAKE - 15);
+
+        // Capped at what is left
+        let event = contract.slash_agent(accounts(1), U128(MIN_REGISTRATION_STAKE), "Fraud".to_string());
+        assert_eq!(event.amount, MIN_REGISTRATION_STAKE - 15);
+        assert_eq!(contract.get_stake(accounts(1)).unstake_available_at, None);
+        assert_eq!(contract.get_total_slashed().0, MIN_REGISTRATION_STAKE);
+        assert_eq!(contract.get_slash_history(accounts(1), None, None).items.len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only reputation contract can slash agents")]
+    fn test_slash_requires_reputation_contract() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        contract.slash_agent(accounts(1), U128(1), "Self-inflicted".to_string());
+    }
+}
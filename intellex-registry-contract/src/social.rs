@@ -0,0 +1,150 @@
+use near_sdk::{env, near_bindgen, require, AccountId, Promise, PromiseResult};
+use serde_json::json;
+
+pub use intellex_types::social::{social_link_key, SocialLink, SocialLinkProof};
+
+use crate::audit_log::to_hex;
+use crate::events;
+use crate::ext::ext_social_db;
+use crate::gas_config::tgas;
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+const SOCIAL_DB_CONTRACT: &str = "social.near";
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Value the social account must write at `social_link_key` before
+    /// `link_social_profile`. Changes after every successful link.
+    pub fn get_social_link_nonce(&self, agent_id: AccountId, social_account_id: AccountId) -> String {
+        let count = self.social_link_counts.get(&agent_id).unwrap_or(0);
+        let preimage = format!("{}:{}:{}", agent_id, social_account_id, count);
+        to_hex(&env::sha256(preimage.as_bytes()))
+    }
+
+    /// Links the caller's agent to a near.social profile once SocialDB
+    /// shows the profile account wrote the expected nonce for this agent.
+    pub fn link_social_profile(&mut self, proof: SocialLinkProof) -> Promise {
+        when_not_paused!(self);
+        let agent_id = env::predecessor_account_id();
+        require!(self.agents.contains_key(&agent_id), "Agent not registered");
+        require!(
+            proof.nonce == self.get_social_link_nonce(agent_id.clone(), proof.social_account_id.clone()),
+            "Stale or invalid social link nonce"
+        );
+
+        let key = social_link_key(proof.social_account_id.as_str(), agent_id.as_str());
+        ext_social_db::ext(SOCIAL_DB_CONTRACT.parse().unwrap())
+            .with_static_gas(tgas(self.gas_config.social_get_tgas))
+            .get(vec![key])
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(tgas(self.gas_config.social_callback_tgas))
+                    .on_social_profile_read(agent_id, proof),
+            )
+    }
+
+    /// Stores the link if SocialDB's `get` result holds the proof's nonce.
+    /// Returns whether the profile was linked.
+    #[private]
+    pub fn on_social_profile_read(&mut self, agent_id: AccountId, proof: SocialLinkProof) -> bool {
+        let written = match env::promise_result(0) {
+            PromiseResult::Successful(value) => serde_json::from_slice::<serde_json::Value>(&value)
+                .ok()
+                .and_then(|tree| {
+                    tree.get(proof.social_account_id.as_str())?
+                        .get("intellex")?
+                        .get("link")?
+                        .get(agent_id.as_str())?
+                        .as_str()
+                        .map(str::to_string)
+                }),
+            PromiseResult::Failed => None,
+        };
+        if written.as_deref() != Some(proof.nonce.as_str()) {
+            env::log_str(&format!(
+                "Social link for {} rejected: nonce not found at {}",
+                agent_id,
+                social_link_key(proof.social_account_id.as_str(), agent_id.as_str())
+            ));
+            return false;
+        }
+        // The agent may have deregistered or linked again meanwhile
+        if !self.agents.contains_key(&agent_id)
+            || proof.nonce != self.get_social_link_nonce(agent_id.clone(), proof.social_account_id.clone())
+        {
+            return false;
+        }
+
+        let count = self.social_link_counts.get(&agent_id).unwrap_or(0);
+        self.social_link_counts.insert(&agent_id, &(count + 1));
+        self.social_links.insert(
+            &agent_id,
+            &SocialLink {
+                social_account_id: proof.social_account_id.clone(),
+                linked_at: env::block_timestamp(),
+            },
+        );
+        self.bump_agent_version(&agent_id);
+        events::emit(
+            "social_profile_linked",
+            json!({ "agent_id": agent_id, "social_account_id": proof.social_account_id }),
+        );
+        true
+    }
+
+    pub fn unlink_social_profile(&mut self) {
+        when_not_paused!(self);
+        let agent_id = env::predecessor_account_id();
+        require!(self.social_links.remove(&agent_id).is_some(), "No social profile linked");
+        self.bump_agent_version(&agent_id);
+        events::emit("social_profile_unlinked", json!({ "agent_id": agent_id }));
+    }
+
+    pub fn get_social_link(&self, agent_id: AccountId) -> Option<SocialLink> {
+        self.social_links.get(&agent_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    fn resolve_social_read(contract: &mut AgentRegistration, proof: SocialLinkProof, result: PromiseResult) -> bool {
+        testing_env!(
+            get_context(accounts(0)).build(),
+            near_sdk::test_vm_config(),
+            near_sdk::RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![result],
+        );
+        contract.on_social_profile_read(accounts(1), proof)
+    }
+
+    #[test]
+    fn test_link_social_profile_checks_written_nonce() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+
+        let nonce = contract.get_social_link_nonce(accounts(1), accounts(2));
+        let proof = SocialLinkProof { social_account_id: accounts(2), nonce: nonce.clone() };
+        contract.link_social_profile(proof.clone());
+
+        let wrong = json!({ "charlie": { "intellex": { "link": { "bob": "other" } } } });
+        let wrong = PromiseResult::Successful(serde_json::to_vec(&wrong).unwrap());
+        assert!(!resolve_social_read(&mut contract, proof.clone(), wrong));
+        assert_eq!(contract.get_social_link(accounts(1)), None);
+
+        let written = serde_json::to_vec(&json!({ "charlie": { "intellex": { "link": { "bob": nonce } } } })).unwrap();
+        assert!(resolve_social_read(&mut contract, proof.clone(), PromiseResult::Successful(written.clone())));
+        assert_eq!(contract.get_agent_summary(accounts(1)).unwrap().social_profile, Some(accounts(2)));
+
+        // The nonce moves on, so the same proof cannot be replayed later
+        assert_ne!(contract.get_social_link_nonce(accounts(1), accounts(2)), proof.nonce);
+        assert!(!resolve_social_read(&mut contract, proof, PromiseResult::Successful(written)));
+    }
+}
@@ -0,0 +1,219 @@
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, require, AccountId, NearToken, Promise, PromiseOrValue, PromiseResult};
+use serde_json::json;
+
+pub use intellex_types::staking::{StakeInfo, MIN_REGISTRATION_STAKE, UNSTAKE_COOLDOWN_NS};
+
+use crate::events;
+use crate::ext::ext_ft;
+use crate::gas_config::tgas;
+use crate::{AgentRegistration, AgentRegistrationExt, ITLX_TOKEN_CONTRACT};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// NEP-141 receiver for ITLX deposits. `msg` is empty or `"stake"` to
+    /// stake, `"fund_task:<task_id>"` to escrow a task reward, or
+    /// `"fund_curation"` to top up the flag bounty pool; anything else, or a
+    /// token other than ITLX, is refunded in full.
+    pub fn ft_on_transfer(&mut self, sender_id: AccountId, amount: U128, msg: String) -> PromiseOrValue<U128> {
+        when_not_paused!(self);
+        require!(
+            env::predecessor_account_id().as_str() == ITLX_TOKEN_CONTRACT,
+            "Only ITLX can be staked"
+        );
+        if let Some(task_id) = msg.strip_prefix("fund_task:") {
+            let task_id = task_id.parse().unwrap_or_else(|_| env::panic_str("Invalid task id"));
+            self.fund_task(&sender_id, task_id, amount.0);
+            return PromiseOrValue::Value(U128(0));
+        }
+        if msg == "fund_curation" {
+            self.fund_curation_pool(&sender_id, amount.0);
+            return PromiseOrValue::Value(U128(0));
+        }
+        if !msg.is_empty() && msg != "stake" {
+            env::log_str(&format!("Refunding {} to {}: unknown msg {:?}", amount.0, sender_id, msg));
+            return PromiseOrValue::Value(amount);
+        }
+
+        let mut stake = self.stakes.get(&sender_id).unwrap_or_default();
+        stake.staked_balance += amount.0;
+        self.stakes.insert(&sender_id, &stake);
+        events::emit(
+            "stake_deposited",
+            json!({ "account_id": sender_id, "amount": amount, "staked_balance": U128(stake.staked_balance) }),
+        );
+        PromiseOrValue::Value(U128(0))
+    }
+
+    /// Moves `amount` from the caller's stake into cooldown. Registered
+    /// agents must keep at least `MIN_REGISTRATION_STAKE` staked.
+    pub fn unstake(&mut self, amount: U128) -> StakeInfo {
+        when_not_paused!(self);
+        let account_id = env::predecessor_account_id();
+        let mut stake = self.stakes.get(&account_id).unwrap_or_default();
+        require!(amount.0 > 0, "Amount must be positive");
+        require!(amount.0 <= stake.staked_balance, "Amount exceeds staked balance");
+        let remaining = stake.staked_balance - amount.0;
+        if self.agents.contains_key(&account_id) {
+            require!(
+                remaining >= MIN_REGISTRATION_STAKE,
+                "Registered agents must keep the minimum stake; deregister first"
+            );
+        }
+
+        let available_at = env::block_timestamp() + UNSTAKE_COOLDOWN_NS;
+        stake.staked_balance = remaining;
+        stake.unstaking_balance += amount.0;
+        stake.unstake_available_at = Some(available_at);
+        self.stakes.insert(&account_id, &stake);
+        events::emit(
+            "stake_unstaked",
+            json!({ "account_id": account_id, "amount": amount, "available_at": available_at }),
+        );
+        stake
+    }
+
+    /// Sends the caller's unstaked ITLX back once the cooldown has passed.
+    pub fn withdraw(&mut self) -> Promise {
+        when_not_paused!(self);
+        let account_id = env::predecessor_account_id();
+        let mut stake = self.stakes.get(&account_id).unwrap_or_default();
+        require!(stake.unstaking_balance > 0, "Nothing to withdraw");
+        require!(
+            stake.unstake_available_at.is_some_and(|at| at <= env::block_timestamp()),
+            "Unstake cooldown has not passed"
+        );
+
+        let amount = U128(stake.unstaking_balance);
+        stake.unstaking_balance = 0;
+        stake.unstake_available_at = None;
+        self.save_stake(&account_id, &stake);
+
+        ext_ft::ext(ITLX_TOKEN_CONTRACT.parse().unwrap())
+            .with_attached_deposit(NearToken::from_yoctonear(1))
+            .with_static_gas(tgas(self.gas_config.ft_transfer_tgas))
+            .ft_transfer(account_id.clone(), amount, None)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(tgas(self.gas_config.ft_callback_tgas))
+                    .on_withdrawn(account_id, amount),
+            )
+    }
+
+    /// Restores the withdrawn amount as withdrawable if `ft_transfer` failed.
+    /// Returns whether the transfer went through.
+    #[private]
+    pub fn on_withdrawn(&mut self, account_id: AccountId, amount: U128) -> bool {
+        if let PromiseResult::Successful(_) = env::promise_result(0) {
+            events::emit("stake_withdrawn", json!({ "account_id": account_id, "amount": amount }));
+            return true;
+        }
+        env::log_str(&format!("Withdrawal of {} to {} failed; restored", amount.0, account_id));
+        let mut stake = self.stakes.get(&account_id).unwrap_or_default();
+        stake.unstaking_balance += amount.0;
+        stake.unstake_available_at.get_or_insert(env::block_timestamp());
+        self.stakes.insert(&account_id, &stake);
+        false
+    }
+
+    pub fn get_stake(&self, account_id: AccountId) -> StakeInfo {
+        self.stakes.get(&account_id).unwrap_or_default()
+    }
+
+    pub fn get_staked_balance(&self, account_id: AccountId) -> U128 {
+        U128(self.stakes.get(&account_id).map_or(0, |stake| stake.staked_balance))
+    }
+}
+
+impl AgentRegistration {
+    pub(crate) fn assert_min_stake(&self, account_id: &AccountId) {
+        let staked = self.stakes.get(account_id).map_or(0, |stake| stake.staked_balance);
+        require!(staked >= MIN_REGISTRATION_STAKE, "Insufficient ITLX stake to register");
+    }
+
+    fn save_stake(&mut self, account_id: &AccountId, stake: &StakeInfo) {
+        if stake.is_empty() {
+            self.stakes.remove(account_id);
+        } else {
+            self.stakes.insert(account_id, stake);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{deposit_stake, get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn test_stake_unstake_withdraw() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        deposit_stake(&mut contract, accounts(1), MIN_REGISTRATION_STAKE + 50);
+        assert_eq!(contract.get_staked_balance(accounts(1)).0, MIN_REGISTRATION_STAKE + 50);
+
+        testing_env!(context.attached_deposit(NearToken::from_near(1)).build());
+        contract.register_agent(sample_metadata(), None);
+        testing_env!(context.attached_deposit(NearToken::from_yoctonear(0)).build());
+        let stake = contract.unstake(U128(50));
+        assert_eq!(stake.staked_balance, MIN_REGISTRATION_STAKE);
+        assert_eq!(stake.unstaking_balance, 50);
+        assert_eq!(stake.unstake_available_at, Some(UNSTAKE_COOLDOWN_NS));
+
+        testing_env!(context.block_timestamp(UNSTAKE_COOLDOWN_NS).build());
+        contract.withdraw();
+        assert_eq!(contract.get_stake(accounts(1)).unstaking_balance, 0);
+
+        // A failed transfer makes the amount withdrawable again
+        testing_env!(
+            get_context(accounts(0)).build(),
+            near_sdk::test_vm_config(),
+            near_sdk::RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Failed],
+        );
+        assert!(!contract.on_withdrawn(accounts(1), U128(50)));
+        assert_eq!(contract.get_stake(accounts(1)).unstaking_balance, 50);
+    }
+
+    #[test]
+    fn test_unknown_msg_is_refunded() {
+        testing_env!(get_context(ITLX_TOKEN_CONTRACT.parse().unwrap()).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        let refund = contract.ft_on_transfer(accounts(1), U128(10), "buy".to_string());
+        assert!(matches!(refund, PromiseOrValue::Value(U128(10))));
+        assert_eq!(contract.get_staked_balance(accounts(1)).0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only ITLX can be staked")]
+    fn test_other_tokens_rejected() {
+        testing_env!(get_context(accounts(2)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.ft_on_transfer(accounts(1), U128(10), String::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "Insufficient ITLX stake to register")]
+    fn test_registration_requires_stake() {
+        testing_env!(get_context(accounts(1)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        deposit_stake(&mut contract, accounts(1), MIN_REGISTRATION_STAKE - 1);
+        contract.register_agent(sample_metadata(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unstake cooldown has not passed")]
+    fn test_withdraw_waits_for_cooldown() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        contract.deregister_agent();
+        contract.unstake(U128(MIN_REGISTRATION_STAKE));
+        contract.withdraw();
+    }
+}
@@ -0,0 +1,105 @@
+use near_sdk::{env, near_bindgen, require, AccountId};
+use serde_json::json;
+
+use crate::events;
+use crate::{AgentRegistration, AgentRegistrationExt, AgentStatus};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Moves the caller's agent between `Active` and `Inactive`, or retires
+    /// it for good. Suspension is left to the registry owner.
+    pub fn set_status(&mut self, status: AgentStatus) {
+        when_not_paused!(self);
+        let agent_id = env::predecessor_account_id();
+        let current = self.get_agent_status(agent_id.clone()).unwrap_or_else(|| env::panic_str("Agent not registered"));
+        require!(status != AgentStatus::Suspended, "Only the registry owner can suspend agents");
+        require!(current != AgentStatus::Suspended, "Agent is suspended");
+        require!(current != AgentStatus::Retired, "Agent is retired");
+        self.change_status(&agent_id, status);
+    }
+
+    /// Takes an agent out of routing, e.g. while a fraud report is
+    /// investigated. Owner only.
+    pub fn suspend_agent(&mut self, agent_id: AccountId) {
+        self.assert_owner();
+        let current = self.get_agent_status(agent_id.clone()).unwrap_or_else(|| env::panic_str("Agent not registered"));
+        require!(current != AgentStatus::Suspended, "Agent is already suspended");
+        self.change_status(&agent_id, AgentStatus::Suspended);
+    }
+
+    /// Lifts a suspension. The agent comes back `Inactive`, so its owner
+    /// decides when it takes tasks again. A banned agent must be unbanned
+    /// first. Owner only.
+    pub fn reinstate_agent(&mut self, agent_id: AccountId) {
+        self.assert_owner();
+        let current = self.get_agent_status(agent_id.clone()).unwrap_or_else(|| env::panic_str("Agent not registered"));
+        require!(current == AgentStatus::Suspended, "Agent is not suspended");
+        require!(!self.is_banned(&agent_id), "Agent is banned");
+        self.change_status(&agent_id, AgentStatus::Inactive);
+    }
+
+    pub fn get_agent_status(&self, agent_id: AccountId) -> Option<AgentStatus> {
+        self.agents.get(&agent_id).map(|agent| agent.status)
+    }
+}
+
+impl AgentRegistration {
+    pub(crate) fn is_agent_active(&self, agent_id: &AccountId) -> bool {
+        self.agents.get(agent_id).is_some_and(|agent| agent.status == AgentStatus::Active)
+    }
+
+    pub(crate) fn change_status(&mut self, agent_id: &AccountId, status: AgentStatus) {
+        let mut agent = self.agents.get(agent_id).unwrap();
+        let old_status = std::mem::replace(&mut agent.status, status);
+        agent.updated_at = env::block_timestamp();
+        agent.version += 1;
+        self.agents.insert(agent_id, &agent);
+        events::emit(
+            "agent_status_changed",
+            json!({ "agent_id": agent_id, "old_status": old_status, "new_status": status }),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn test_status_filters_skill_listing() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        register(&mut contract, &mut context, sample_metadata());
+
+        contract.set_status(AgentStatus::Inactive);
+        let rust = "Rust".to_string();
+        assert_eq!(contract.get_agents_by_skill(rust.clone(), Some(true)), vec![accounts(1)]);
+        assert_eq!(contract.get_agents_by_skill(rust.clone(), None).len(), 2);
+
+        testing_env!(get_context(accounts(0)).build());
+        contract.suspend_agent(accounts(1));
+        assert!(contract.get_agents_by_skill(rust.clone(), Some(true)).is_empty());
+        contract.reinstate_agent(accounts(1));
+        assert_eq!(contract.get_agent_status(accounts(1)), Some(AgentStatus::Inactive));
+    }
+
+    #[test]
+    #[should_panic(expected = "Agent is suspended")]
+    fn test_suspended_agent_cannot_reactivate() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+
+        testing_env!(get_context(accounts(0)).build());
+        contract.suspend_agent(accounts(1));
+        testing_env!(context.build());
+        contract.set_status(AgentStatus::Active);
+    }
+}
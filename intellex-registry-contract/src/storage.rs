@@ -0,0 +1,221 @@
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, require, AccountId, NearToken, Promise};
+use serde_json::json;
+
+pub use intellex_types::storage::{StorageAccount, StorageBalance, StorageBalanceBounds};
+
+use crate::events;
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+/// NEP-145 storage management. Registering an agent, and growing its
+/// metadata later, locks NEAR for the bytes written; deregistering unlocks
+/// it and refunds the account's whole storage balance.
+#[near_bindgen]
+impl AgentRegistration {
+    /// Credits the attached deposit to `account_id` (the caller by default).
+    /// No minimum applies, so `registration_only` refunds the whole deposit.
+    #[payable]
+    pub fn storage_deposit(&mut self, account_id: Option<AccountId>, registration_only: Option<bool>) -> StorageBalance {
+        when_not_paused!(self);
+        let account_id = account_id.unwrap_or_else(env::predecessor_account_id);
+        let amount = env::attached_deposit().as_yoctonear();
+        let mut account = self.storage_accounts.get(&account_id).unwrap_or_default();
+
+        if registration_only.unwrap_or(false) {
+            if amount > 0 {
+                Promise::new(env::predecessor_account_id()).transfer(NearToken::from_yoctonear(amount));
+            }
+        } else {
+            account.total += amount;
+            events::emit("storage_deposit", json!({ "account_id": account_id, "amount": U128(amount) }));
+        }
+        self.storage_accounts.insert(&account_id, &account);
+        storage_balance(&account)
+    }
+
+    /// Sends up to `amount` (all of it by default) of the caller's available
+    /// storage balance back. Requires exactly 1 yoctoNEAR attached.
+    #[payable]
+    pub fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
+        when_not_paused!(self);
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let mut account = self
+            .storage_accounts
+            .get(&account_id)
+            .unwrap_or_else(|| env::panic_str("Account has no storage balance"));
+        let available = storage_balance(&account).available;
+        let amount = amount.map_or(available, |amount| amount.0);
+        require!(amount <= available, "Amount exceeds available storage balance");
+
+        if amount > 0 {
+            account.total -= amount;
+            self.storage_accounts.insert(&account_id, &account);
+            Promise::new(account_id.clone()).transfer(NearToken::from_yoctonear(amount));
+            events::emit("storage_withdraw", json!({ "account_id": account_id, "amount": U128(amount) }));
+        }
+        storage_balance(&account)
+    }
+
+    /// Closes the caller's storage account and refunds its balance. Fails
+    /// while the caller is registered; `force` cannot remove an agent, so
+    /// it is accepted only for NEP-145 compatibility.
+    #[payable]
+    pub fn storage_unregister(&mut self, force: Option<bool>) -> bool {
+        when_not_paused!(self);
+        assert_one_yocto();
+        let _ = force;
+        let account_id = env::predecessor_account_id();
+        require!(!self.agents.contains_key(&account_id), "Deregister the agent before unregistering storage");
+        let Some(account) = self.storage_accounts.remove(&account_id) else {
+            return false;
+        };
+        if account.total > 0 {
+            Promise::new(account_id).transfer(NearToken::from_yoctonear(account.total));
+        }
+        true
+    }
+
+    pub fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+        StorageBalanceBounds { min: 0, max: None }
+    }
+
+    pub fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
+        self.storage_accounts.get(&account_id).map(|account| storage_balance(&account))
+    }
+}
+
+impl AgentRegistration {
+    /// Adds the attached deposit of a payable agent method to the caller's
+    /// storage balance.
+    pub(crate) fn credit_storage_deposit(&mut self, account_id: &AccountId) {
+        self.credit_storage(account_id, env::attached_deposit().as_yoctonear());
+    }
+
+    pub(crate) fn credit_storage(&mut self, account_id: &AccountId, amount: u128) {
+        if amount == 0 {
+            return;
+        }
+        let mut account = self.storage_accounts.get(account_id).unwrap_or_default();
+        account.total += amount;
+        self.storage_accounts.insert(account_id, &account);
+    }
+
+    /// Locks or unlocks storage for the bytes written or freed since
+    /// `initial_usage`, panicking if the balance cannot cover the growth.
+    pub(crate) fn settle_storage(&mut self, account_id: &AccountId, initial_usage: u64) {
+        let usage = env::storage_usage();
+        if usage == initial_usage {
+            return;
+        }
+        let mut account = self.storage_accounts.get(account_id).unwrap_or_default();
+        if usage > initial_usage {
+            account.locked_bytes += usage - initial_usage;
+            assert_storage_covered(&account);
+        } else {
+            account.locked_bytes = account.locked_bytes.saturating_sub(initial_usage - usage);
+        }
+        self.storage_accounts.insert(account_id, &account);
+    }
+
+    /// Unlocks everything `account_id` locked and refunds its whole storage
+    /// balance. History kept after deregistration (skill and slash history)
+    /// is left for the registry to pay for.
+    pub(crate) fn release_storage(&mut self, account_id: &AccountId) {
+        let Some(account) = self.storage_accounts.remove(account_id) else {
+            return;
+        };
+        if account.total > 0 {
+            Promise::new(account_id.clone()).transfer(NearToken::from_yoctonear(account.total));
+            events::emit("storage_refund", json!({ "account_id": account_id, "amount": U128(account.total) }));
+        }
+    }
+
+    /// Moves the storage `from` has locked over to `to`, which must be able to
+    /// cover it, and refunds `from`.
+    pub(crate) fn transfer_locked_storage(&mut self, from: &AccountId, to: &AccountId) {
+        let locked_bytes = self.storage_accounts.get(from).map_or(0, |account| account.locked_bytes);
+        self.release_storage(from);
+        let mut account = self.storage_accounts.get(to).unwrap_or_default();
+        account.locked_bytes += locked_bytes;
+        assert_storage_covered(&account);
+        self.storage_accounts.insert(to, &account);
+    }
+}
+
+fn assert_storage_covered(account: &StorageAccount) {
+    let required = storage_cost(account.locked_bytes);
+    if required > account.total {
+        env::panic_str(&format!(
+            "Insufficient storage deposit: attach at least {} more yoctoNEAR",
+            required - account.total
+        ));
+    }
+}
+
+pub(crate) fn storage_cost(bytes: u64) -> u128 {
+    env::storage_byte_cost().as_yoctonear() * bytes as u128
+}
+
+fn storage_balance(account: &StorageAccount) -> StorageBalance {
+    StorageBalance {
+        total: account.total,
+        available: account.total.saturating_sub(storage_cost(account.locked_bytes)),
+    }
+}
+
+pub(crate) fn assert_one_yocto() {
+    require!(
+        env::attached_deposit() == NearToken::from_yoctonear(1),
+        "Requires attached deposit of exactly 1 yoctoNEAR"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::staking::MIN_REGISTRATION_STAKE;
+    use crate::tests::{deposit_stake, get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn test_registration_locks_storage_and_deregistration_refunds() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+
+        let balance = contract.storage_balance_of(accounts(1)).unwrap();
+        assert!(balance.available < balance.total);
+        let locked = contract.storage_accounts.get(&accounts(1)).unwrap().locked_bytes;
+        assert_eq!(balance.total - balance.available, storage_cost(locked));
+
+        testing_env!(context.attached_deposit(NearToken::from_yoctonear(0)).build());
+        contract.deregister_agent();
+        assert_eq!(contract.storage_balance_of(accounts(1)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Insufficient storage deposit")]
+    fn test_registration_requires_storage_deposit() {
+        testing_env!(get_context(accounts(1)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        deposit_stake(&mut contract, accounts(1), MIN_REGISTRATION_STAKE);
+        contract.register_agent(sample_metadata(), None);
+    }
+
+    #[test]
+    fn test_storage_deposit_and_withdraw() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.attached_deposit(NearToken::from_yoctonear(100)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        assert_eq!(contract.storage_deposit(None, None).available, 100);
+
+        testing_env!(context.attached_deposit(NearToken::from_yoctonear(1)).build());
+        let balance = contract.storage_withdraw(Some(U128(40)));
+        assert_eq!((balance.total, balance.available), (60, 60));
+        assert!(contract.storage_unregister(None));
+        assert_eq!(contract.storage_balance_of(accounts(1)), None);
+    }
+}
@@ -0,0 +1,299 @@
+use std::collections::HashMap;
+
+use near_sdk::borsh;
+use near_sdk::store::{IterableSet, Vector};
+use near_sdk::{env, near_bindgen, AccountId};
+
+pub use intellex_types::storage::UpdateCostEstimate;
+
+use crate::skill_history::{SkillChange, SkillChangeKind};
+use crate::skill_versions::split_versioned_skill;
+use crate::skills_registry::{lists_skill, skill_lookup_key};
+use crate::storage::storage_cost;
+use crate::{Agent, AgentMetadata, AgentRegistration, AgentRegistrationExt};
+
+/// Bytes NEAR charges per state record on top of its key and value.
+const STORAGE_RECORD_OVERHEAD: u64 = 40;
+/// Key length of `store::LookupMap`, which hashes keys with SHA-256.
+const HASHED_KEY_LEN: usize = 32;
+
+/// Bytes taken up by one state record.
+pub(crate) fn record_bytes(key_len: usize, value_len: usize) -> u64 {
+    (key_len + value_len) as u64 + STORAGE_RECORD_OVERHEAD
+}
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Works out what `update_agent_metadata(metadata)` would write and free
+    /// for `agent_id` in its current state, and how much to attach so the
+    /// call does not fail on storage. The estimate goes stale if the skill
+    /// indexes change before the update lands.
+    pub fn estimate_update_cost(&self, agent_id: AccountId, metadata: AgentMetadata) -> UpdateCostEstimate {
+        let agent = self
+            .agents
+            .get(&agent_id)
+            .unwrap_or_else(|| env::panic_str("Agent not registered"));
+        let metadata = self.normalize_metadata(metadata);
+        Self::assert_system_skills_unchanged(&metadata, &agent.metadata.skills);
+
+        let mut estimate = UpdateEstimator::new(self, &agent_id);
+        for skill in &agent.metadata.skills {
+            if !lists_skill(&metadata.skills, skill) {
+                estimate.unindex_agent_skill(skill);
+                estimate.record_skill_change(skill, SkillChangeKind::Removed);
+            }
+        }
+        for skill in &metadata.skills {
+            if !lists_skill(&agent.metadata.skills, skill) {
+                estimate.index_agent_skill(skill);
+                estimate.record_skill_change(skill, SkillChangeKind::Added);
+            }
+        }
+        estimate.write_agent(Agent { metadata, ..agent });
+
+        let (bytes_added, bytes_freed) = (estimate.added, estimate.freed);
+        let account = self.storage_accounts.get(&agent_id).unwrap_or_default();
+        let required_deposit = if bytes_added > bytes_freed {
+            storage_cost(account.locked_bytes + bytes_added - bytes_freed).saturating_sub(account.total)
+        } else {
+            0
+        };
+        UpdateCostEstimate { bytes_added, bytes_freed, required_deposit }
+    }
+}
+
+/// Replays the writes of a metadata update against a read-only view of the
+/// state, tracking how the touched sets would grow and shrink.
+struct UpdateEstimator<'a> {
+    contract: &'a AgentRegistration,
+    agent_id: &'a AccountId,
+    added: u64,
+    freed: u64,
+    // Skill lookup key -> (bucket size, whether the agent is in it)
+    skill_buckets: HashMap<String, (u64, bool)>,
+    // Skill name -> (number of versions listed, versions known to be listed)
+    version_sets: HashMap<String, (u64, HashMap<String, bool>)>,
+    history_exists: Option<bool>,
+}
+
+impl<'a> UpdateEstimator<'a> {
+    fn new(contract: &'a AgentRegistration, agent_id: &'a AccountId) -> Self {
+        Self {
+            contract,
+            agent_id,
+            added: 0,
+            freed: 0,
+            skill_buckets: HashMap::new(),
+            version_sets: HashMap::new(),
+            history_exists: None,
+        }
+    }
+
+    fn resize(&mut self, old_bytes: u64, new_bytes: u64) {
+        if new_bytes > old_bytes {
+            self.added += new_bytes - old_bytes;
+        } else {
+            self.freed += old_bytes - new_bytes;
+        }
+    }
+
+    fn skill_bucket(&mut self, key: &String) -> (u64, bool) {
+        let (contract, agent_id) = (self.contract, self.agent_id);
+        *self.skill_buckets.entry(key.clone()).or_insert_with(|| {
+            contract
+                .skills_index
+                .get(key)
+                .map_or((0, false), |agents| (agents.len() as u64, agents.contains(agent_id)))
+        })
+    }
+
+    /// Mirrors `AgentRegistration::index_agent_skill`.
+    fn index_agent_skill(&mut self, skill: &str) {
+        let key = skill_lookup_key(skill);
+        let (len, member) = self.skill_bucket(&key);
+        let prefix = format!("s_{}", key).into_bytes();
+        if len == 0 {
+            self.added += set_entry_bytes(b"s", &key, prefix.clone());
+        }
+        if !member {
+            self.added += set_element_bytes(&prefix, self.agent_id);
+            self.skill_buckets.insert(key, (len + 1, true));
+        }
+        self.index_skill_version(skill);
+    }
+
+    /// Mirrors `AgentRegistration::unindex_agent_skill`.
+    fn unindex_agent_skill(&mut self, skill: &str) {
+        let key = skill_lookup_key(skill);
+        let (len, member) = self.skill_bucket(&key);
+        if len == 0 || !member {
+            return;
+        }
+        let prefix = format!("s_{}", key).into_bytes();
+        self.freed += set_element_bytes(&prefix, self.agent_id);
+        self.skill_buckets.insert(key.clone(), (len - 1, false));
+        if len == 1 {
+            self.freed += set_entry_bytes(b"s", &key, prefix);
+            self.unindex_skill_version(skill);
+        }
+    }
+
+    fn version_set(&mut self, name: &str, skill: &str) -> (u64, bool) {
+        let contract = self.contract;
+        let (len, listed) = self.version_sets.entry(name.to_string()).or_insert_with(|| {
+            let len = contract.skill_versions.get(&name.to_string()).map_or(0, |versions| versions.len());
+            (len as u64, HashMap::new())
+        });
+        let listed = *listed.entry(skill.to_string()).or_insert_with(|| {
+            contract
+                .skill_versions
+                .get(&name.to_string())
+                .is_some_and(|versions| versions.contains(skill))
+        });
+        (*len, listed)
+    }
+
+    fn set_version_listed(&mut self, name: &str, skill: &str, len: u64, listed: bool) {
+        let entry = self.version_sets.get_mut(name).unwrap();
+        entry.0 = len;
+        entry.1.insert(skill.to_string(), listed);
+    }
+
+    /// Mirrors `AgentRegistration::index_skill_version`.
+    fn index_skill_version(&mut self, skill: &str) {
+        let Some((name, _)) = split_versioned_skill(skill) else {
+            return;
+        };
+        let (len, listed) = self.version_set(name, skill);
+        let prefix = format!("v_{}", name).into_bytes();
+        if len == 0 {
+            self.added += set_entry_bytes(b"v", &name.to_string(), prefix.clone());
+        }
+        if !listed {
+            self.added += set_element_bytes(&prefix, &skill.to_string());
+            self.set_version_listed(name, skill, len + 1, true);
+        }
+    }
+
+    /// Mirrors `AgentRegistration::unindex_skill_version`.
+    fn unindex_skill_version(&mut self, skill: &str) {
+        let Some((name, _)) = split_versioned_skill(skill) else {
+            return;
+        };
+        let (len, listed) = self.version_set(name, skill);
+        if len == 0 || !listed {
+            return;
+        }
+        let prefix = format!("v_{}", name).into_bytes();
+        self.freed += set_element_bytes(&prefix, &skill.to_string());
+        self.set_version_listed(name, skill, len - 1, false);
+        if len == 1 {
+            self.freed += set_entry_bytes(b"v", &name.to_string(), prefix);
+        }
+    }
+
+    /// Mirrors `AgentRegistration::record_skill_change`.
+    fn record_skill_change(&mut self, skill: &str, kind: SkillChangeKind) {
+        let agent_id = self.agent_id;
+        let prefix = format!("y_{}", agent_id).into_bytes();
+        let exists = *self
+            .history_exists
+            .get_or_insert_with(|| self.contract.skill_history.contains_key(agent_id));
+        if !exists {
+            let history: Vector<SkillChange> = Vector::new(prefix.clone());
+            self.added += record_bytes(1 + borsh_len(agent_id), borsh_len(&history));
+            self.history_exists = Some(true);
+        }
+        let change = SkillChange { skill: skill.to_string(), kind, changed_at: env::block_timestamp() };
+        self.added += record_bytes(prefix.len() + 4, borsh_len(&change));
+    }
+
+    /// Mirrors `AgentStore::insert`.
+    fn write_agent(&mut self, agent: Agent) {
+        let new_bytes = self.contract.agents.record_bytes(self.agent_id, &agent);
+        match self.contract.agents.stored_record_bytes(self.agent_id) {
+            Some((old_bytes, false)) => self.resize(old_bytes, new_bytes),
+            Some((old_bytes, true)) => {
+                self.freed += old_bytes;
+                self.added += new_bytes;
+            }
+            None => self.added += new_bytes,
+        }
+    }
+}
+
+fn borsh_len<T: borsh::BorshSerialize + ?Sized>(value: &T) -> usize {
+    borsh::to_vec(value).unwrap().len()
+}
+
+/// Bytes of a top-level map entry holding a new, empty `IterableSet`.
+fn set_entry_bytes(map_prefix: &[u8], key: &String, set_prefix: Vec<u8>) -> u64 {
+    let set: IterableSet<String> = IterableSet::new(set_prefix);
+    record_bytes(map_prefix.len() + borsh_len(key), borsh_len(&set))
+}
+
+/// Bytes one element adds to an `IterableSet`: its slot in the element
+/// vector plus its entry in the hashed index.
+fn set_element_bytes<T: borsh::BorshSerialize>(set_prefix: &[u8], element: &T) -> u64 {
+    record_bytes(set_prefix.len() + 1 + 4, borsh_len(element)) + record_bytes(HASHED_KEY_LEN, 4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+    use near_sdk::NearToken;
+
+    fn assert_estimate_matches_update(contract: &mut AgentRegistration, metadata: AgentMetadata) {
+        let estimate = contract.estimate_update_cost(accounts(1), metadata.clone());
+        let initial_usage = env::storage_usage();
+        contract.update_agent_metadata(metadata);
+        assert_eq!(
+            env::storage_usage() as i64 - initial_usage as i64,
+            estimate.bytes_added as i64 - estimate.bytes_freed as i64
+        );
+    }
+
+    #[test]
+    fn test_estimate_matches_storage_written() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        register(&mut contract, &mut context, sample_metadata());
+
+        testing_env!(get_context(accounts(1)).attached_deposit(NearToken::from_near(1)).build());
+        let mut metadata = sample_metadata();
+        metadata.description = "A much longer description than the sample one".to_string();
+        metadata.skills = vec!["Rust".to_string(), "near-sdk@5.1.0".to_string(), "near-sdk@4.0.0".to_string()];
+        assert_estimate_matches_update(&mut contract, metadata);
+
+        // Drops the shared bucket's member and empties the version set
+        let mut metadata = sample_metadata();
+        metadata.skills = vec!["Go".to_string(), "Go".to_string()];
+        assert_estimate_matches_update(&mut contract, metadata);
+    }
+
+    #[test]
+    fn test_estimate_reports_missing_deposit() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        let available = contract.storage_balance_of(accounts(1)).unwrap().available;
+        testing_env!(get_context(accounts(0)).build());
+        let config = crate::registry_config::RegistryConfig { max_description_length: 200_000, ..Default::default() };
+        contract.set_registry_config(config);
+
+        let mut metadata = sample_metadata();
+        metadata.description = "x".repeat(200_000);
+        let estimate = contract.estimate_update_cost(accounts(1), metadata);
+        assert!(estimate.bytes_added > estimate.bytes_freed);
+        let growth = storage_cost(estimate.bytes_added - estimate.bytes_freed);
+        assert_eq!(estimate.required_deposit, growth - available);
+        assert_eq!(contract.estimate_update_cost(accounts(1), sample_metadata()).required_deposit, 0);
+    }
+}
@@ -0,0 +1,117 @@
+use near_sdk::{env, near_bindgen, require, AccountId};
+use serde_json::json;
+
+pub use intellex_types::system_skills::{is_system_skill, SYSTEM_SKILL_PREFIX};
+
+use crate::events;
+use crate::skill_history::SkillChangeKind;
+use crate::skills_registry::lists_skill;
+use crate::{Agent, AgentMetadata, AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Adds a `system:` skill to an agent. Owner only.
+    pub fn grant_system_skill(&mut self, agent_id: AccountId, skill: String) {
+        self.assert_owner();
+        require!(
+            is_system_skill(&skill) && skill.len() > SYSTEM_SKILL_PREFIX.len(),
+            "Not a system skill"
+        );
+        let mut agent = self.agents.get(&agent_id).unwrap_or_else(|| env::panic_str("Agent not registered"));
+        require!(!agent.metadata.skills.contains(&skill), "Agent already has this system skill");
+
+        agent.metadata.skills.push(skill.clone());
+        self.index_agent_skill(&skill, &agent_id);
+        self.record_skill_change(&agent_id, &skill, SkillChangeKind::Added);
+        self.save_system_skill_change(&agent_id, agent);
+        events::emit("system_skill_granted", json!({ "agent_id": agent_id, "skill": skill }));
+    }
+
+    pub fn revoke_system_skill(&mut self, agent_id: AccountId, skill: String) {
+        self.assert_owner();
+        require!(is_system_skill(&skill), "Not a system skill");
+        let mut agent = self.agents.get(&agent_id).unwrap_or_else(|| env::panic_str("Agent not registered"));
+        require!(agent.metadata.skills.contains(&skill), "Agent does not have this system skill");
+
+        agent.metadata.skills.retain(|existing| existing != &skill);
+        self.unindex_agent_skill(&skill, &agent_id);
+        self.record_skill_change(&agent_id, &skill, SkillChangeKind::Removed);
+        self.save_system_skill_change(&agent_id, agent);
+        events::emit("system_skill_revoked", json!({ "agent_id": agent_id, "skill": skill }));
+    }
+}
+
+impl AgentRegistration {
+    /// Panics unless `metadata` lists exactly the system skills in `current`
+    /// (none for a new agent), so agents can neither claim nor drop them.
+    pub(crate) fn assert_system_skills_unchanged(metadata: &AgentMetadata, current: &[String]) {
+        let mut proposed: Vec<&String> = metadata.skills.iter().filter(|skill| is_system_skill(skill)).collect();
+        let mut held: Vec<&String> = current.iter().filter(|skill| is_system_skill(skill)).collect();
+        proposed.sort();
+        held.sort();
+        require!(proposed == held, "system: skills can only be changed by the registry owner");
+    }
+
+    /// True if the agent lists every skill in `required`, ignoring case and
+    /// spacing.
+    pub(crate) fn has_skills(agent: &Agent, required: &[String]) -> bool {
+        required.iter().all(|skill| lists_skill(&agent.metadata.skills, skill))
+    }
+
+    fn save_system_skill_change(&mut self, agent_id: &AccountId, mut agent: Agent) {
+        agent.updated_at = env::block_timestamp();
+        agent.version += 1;
+        self.agents.insert(agent_id, &agent);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn test_granted_system_skill_filters_search() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        register(&mut contract, &mut context, sample_metadata());
+
+        testing_env!(get_context(accounts(0)).build());
+        contract.grant_system_skill(accounts(1), "system:verified-oracle".to_string());
+        let oracles = contract.search_agents_by_skill(
+            "Rust".to_string(),
+            None,
+            10,
+            None,
+            Some(vec!["system:verified-oracle".to_string()]),
+        ).items;
+        assert_eq!(oracles.len(), 1);
+        assert_eq!(oracles[0].agent_id, accounts(1));
+
+        // The agent keeps the skill through its own metadata updates
+        let mut metadata = sample_metadata();
+        metadata.skills.push("system:verified-oracle".to_string());
+        testing_env!(get_context(accounts(1)).build());
+        contract.update_agent_metadata(metadata);
+
+        testing_env!(get_context(accounts(0)).build());
+        contract.revoke_system_skill(accounts(1), "system:verified-oracle".to_string());
+        assert!(contract.get_agents_by_skill("system:verified-oracle".to_string(), None).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "system: skills can only be changed by the registry owner")]
+    fn test_agents_cannot_claim_system_skills() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        let mut metadata = sample_metadata();
+        metadata.skills.push("system:protocol-keeper".to_string());
+        register(&mut contract, &mut context, metadata);
+    }
+}
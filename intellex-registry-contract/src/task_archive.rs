@@ -0,0 +1,129 @@
+use near_sdk::{borsh, env, near_bindgen};
+use serde_json::json;
+
+pub use intellex_types::tasks::{ArchivedTask, MAX_TASK_COMPACTION_BATCH, TASK_RETENTION_PERIOD};
+
+use crate::audit_log::to_hex;
+use crate::events;
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Compacts up to `limit` tasks, at most 100, settled more than
+    /// `TASK_RETENTION_PERIOD` ago into `ArchivedTask` summaries, oldest
+    /// settlement first. Freed bytes are unlocked for the accounts that
+    /// paid for them: the result for the agent, the rest for the creator.
+    /// Callable by anyone, e.g. a keeper. Returns how many were compacted.
+    pub fn compact_settled_tasks(&mut self, limit: u64) -> u64 {
+        when_not_paused!(self);
+        let now = env::block_timestamp();
+        let mut compacted = Vec::new();
+        while (compacted.len() as u64) < limit.min(MAX_TASK_COMPACTION_BATCH)
+            && self.settled_queue_head < self.settled_queue_tail
+        {
+            let task_id = self.settled_tasks.get(&self.settled_queue_head).unwrap();
+            let Some(task) = self.get_task(task_id) else {
+                self.pop_settled_task();
+                continue;
+            };
+            if task.updated_at.saturating_add(TASK_RETENTION_PERIOD) > now {
+                break;
+            }
+            self.pop_settled_task();
+            self.compact_task(task);
+            compacted.push(task_id);
+        }
+        if !compacted.is_empty() {
+            events::emit("tasks_compacted", json!({ "task_ids": compacted }));
+        }
+        compacted.len() as u64
+    }
+
+    pub fn get_archived_task(&self, task_id: u64) -> Option<ArchivedTask> {
+        self.archived_tasks.get(&task_id)
+    }
+
+    /// Settled tasks still holding their full record.
+    pub fn get_compaction_backlog(&self) -> u64 {
+        self.settled_queue_tail - self.settled_queue_head
+    }
+}
+
+impl AgentRegistration {
+    /// Queues a task that just settled for compaction once its retention
+    /// period has passed.
+    pub(crate) fn queue_settled_task(&mut self, task_id: u64) {
+        self.settled_tasks.insert(&self.settled_queue_tail, &task_id);
+        self.settled_queue_tail += 1;
+    }
+
+    fn pop_settled_task(&mut self) {
+        self.settled_tasks.remove(&self.settled_queue_head);
+        self.settled_queue_head += 1;
+    }
+
+    fn compact_task(&mut self, mut task: crate::tasks::Task) {
+        let record_hash = to_hex(&env::sha256(&borsh::to_vec(&task).unwrap()));
+        if let (Some(agent_id), Some(_)) = (task.agent_id.clone(), task.result.take()) {
+            let initial_usage = env::storage_usage();
+            self.tasks.insert(&task.task_id, &task);
+            self.settle_storage(&agent_id, initial_usage);
+        }
+
+        let initial_usage = env::storage_usage();
+        self.tasks.remove(&task.task_id);
+        self.archived_tasks.insert(
+            &task.task_id,
+            &ArchivedTask {
+                task_id: task.task_id,
+                record_hash,
+                creator: task.creator.clone(),
+                agent_id: task.agent_id,
+                status: task.status,
+                reward: task.reward,
+                itlx_reward: task.itlx_reward,
+                fee_bps: task.fee_bps,
+                created_at: task.created_at,
+                settled_at: task.updated_at,
+            },
+        );
+        // A task with a short description can take less room than its
+        // summary; the registry pays for that growth rather than the creator.
+        if env::storage_usage() < initial_usage {
+            self.settle_storage(&task.creator, initial_usage);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::{Task, TaskStatus};
+    use crate::tests::get_context;
+    use near_sdk::json_types::U128;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+    use near_sdk::NearToken;
+
+    #[test]
+    fn test_compacts_tasks_past_retention() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(2)).build());
+        let cancelled = contract.create_task("Summarize a paper. ".repeat(20), Vec::new(), U128(1));
+        contract.create_task("Translate a paper".to_string(), Vec::new(), U128(1));
+        let cancelled: Task = contract.cancel_task(cancelled.task_id);
+        let locked = contract.storage_balance_of(accounts(2)).unwrap();
+
+        assert_eq!(contract.compact_settled_tasks(10), 0);
+        testing_env!(get_context(accounts(3)).block_timestamp(cancelled.updated_at + TASK_RETENTION_PERIOD).build());
+        assert_eq!(contract.compact_settled_tasks(10), 1);
+        assert_eq!(contract.get_compaction_backlog(), 0);
+
+        assert!(contract.get_task(cancelled.task_id).is_none());
+        let archived = contract.get_archived_task(cancelled.task_id).unwrap();
+        assert_eq!(archived.status, TaskStatus::Cancelled);
+        assert_eq!(archived.record_hash, to_hex(&env::sha256(&borsh::to_vec(&cancelled).unwrap())));
+        assert!(contract.storage_balance_of(accounts(2)).unwrap().available > locked.available);
+    }
+}
@@ -0,0 +1,209 @@
+use std::collections::HashSet;
+
+use near_sdk::store::Vector;
+use near_sdk::{near_bindgen, require, AccountId};
+use serde_json::json;
+
+pub use intellex_types::task_history::{TaskStats, DEFAULT_TASK_HISTORY_WINDOW, MAX_TASK_HISTORY_WINDOW};
+
+use crate::events;
+use crate::pagination::{page_limit, paginate, sequence_start, CursorKey, Page};
+use crate::{AgentInfo, AgentRegistration, AgentRegistrationExt, TaskResult};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Sets how many of each agent's latest task results are kept. Older
+    /// ones are pruned as new results arrive; `get_agent_task_stats` keeps
+    /// counting them. Owner only.
+    pub fn set_task_history_window(&mut self, window: u32) {
+        self.assert_owner();
+        require!(
+            window > 0 && window <= MAX_TASK_HISTORY_WINDOW,
+            "Task history window must be between 1 and 100"
+        );
+        self.task_history_window = window;
+        events::emit("task_history_window_changed", json!({ "window": window }));
+    }
+
+    pub fn get_task_history_window(&self) -> u32 {
+        self.task_history_window
+    }
+
+    /// The agent's latest task results, oldest first, at most
+    /// `get_task_history_window()` of them. Cursors are positions in the
+    /// whole history, so one stays valid while results are pruned.
+    pub fn get_agent_task_history(&self, agent_id: &AccountId, cursor: Option<String>, limit: Option<u64>) -> Page<TaskResult> {
+        let start = sequence_start("get_agent_task_history", cursor);
+
+        let Some(history) = self.task_histories.get(agent_id) else {
+            // Agents stored before task history moved out of the record
+            return self
+                .agents
+                .get(agent_id)
+                .map(|agent| {
+                    let results = agent.reputation_info.task_history
+                        .into_iter()
+                        .enumerate()
+                        .skip(start as usize)
+                        .map(|(i, result)| (CursorKey::Sequence(i as u64), result));
+                    paginate("get_agent_task_history", results, page_limit(limit))
+                })
+                .unwrap_or_default();
+        };
+        let total = self.get_agent_task_stats(agent_id.clone()).total;
+        let first_kept = total - history.len() as u64;
+        let first_shown = start.max(total.saturating_sub(self.task_history_window as u64));
+        let results = history
+            .iter()
+            .enumerate()
+            .skip(first_shown.saturating_sub(first_kept) as usize)
+            .map(|(i, result)| (CursorKey::Sequence(first_kept + i as u64), result.clone()));
+        paginate("get_agent_task_history", results, page_limit(limit))
+    }
+
+    /// Results recorded for the agent over its whole history, including
+    /// those pruned from the window, from counters kept as results arrive.
+    /// Zeroes for agents without results.
+    pub fn get_agent_task_stats(&self, agent_id: AccountId) -> TaskStats {
+        self.task_stats.get(&agent_id).unwrap_or_else(|| {
+            self.agents
+                .get(&agent_id)
+                .map(|agent| agent.reputation_info.task_history.iter().collect())
+                .unwrap_or_default()
+        })
+    }
+}
+
+impl AgentRegistration {
+    /// Appends `results` to the agent's task history and counts them.
+    /// Pruning happens in batches: once twice the window has piled up,
+    /// everything but the latest window is dropped.
+    pub(crate) fn append_task_results(&mut self, agent_id: &AccountId, results: Vec<TaskResult>) {
+        if results.is_empty() {
+            return;
+        }
+        let mut history = match self.task_histories.get(agent_id) {
+            Some(existing) => existing,
+            None => Vector::new(format!("%_{}", agent_id).into_bytes()),
+        };
+        let mut stats = self.get_agent_task_stats(agent_id.clone());
+        for result in results {
+            stats.record(&result);
+            history.push(result);
+        }
+        let window = self.task_history_window;
+        if history.len() >= window.saturating_mul(2) {
+            history.drain(..history.len() - window);
+        }
+        self.task_histories.insert(agent_id, &history);
+        self.task_stats.insert(agent_id, &stats);
+    }
+
+    /// Moves the task history carried in `info` to the agent's own task
+    /// history, leaving `info.task_history` empty. Whole-state pushes may
+    /// resend results already recorded, all of them or only the latest:
+    /// results whose task id is still in the kept history, or that are
+    /// older than everything kept, are skipped.
+    pub(crate) fn move_task_history(&mut self, agent_id: &AccountId, info: &mut AgentInfo) {
+        let results = std::mem::take(&mut info.task_history);
+        let (oldest_kept, mut recorded) = match self.task_histories.get(agent_id) {
+            Some(history) => (
+                history.get(0).map_or(0, |result| result.timestamp),
+                history.iter().map(|result| result.task_id.clone()).collect(),
+            ),
+            None => (0, HashSet::new()),
+        };
+        let new_results = results
+            .into_iter()
+            .filter(|result| result.timestamp >= oldest_kept && recorded.insert(result.task_id.clone()))
+            .collect();
+        self.append_task_results(agent_id, new_results);
+    }
+
+    /// Re-keys the agent's task history under `new_owner`. Only the window
+    /// is copied; the counts carry over whole.
+    pub(crate) fn move_task_results(&mut self, agent_id: &AccountId, new_owner: &AccountId) {
+        if let Some(mut history) = self.task_histories.remove(agent_id) {
+            let window = self.task_history_window.min(history.len());
+            let mut moved = Vector::new(format!("%_{}", new_owner).into_bytes());
+            moved.extend(history.iter().skip((history.len() - window) as usize).cloned());
+            history.clear();
+            self.task_histories.insert(new_owner, &moved);
+        }
+        if let Some(stats) = self.task_stats.remove(agent_id) {
+            self.task_stats.insert(new_owner, &stats);
+        }
+    }
+
+    pub(crate) fn clear_task_results(&mut self, agent_id: &AccountId) {
+        if let Some(mut history) = self.task_histories.remove(agent_id) {
+            history.clear();
+        }
+        self.task_stats.remove(agent_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    fn result(i: u64) -> TaskResult {
+        TaskResult { task_id: format!("task{}", i), success: !i.is_multiple_of(3), timestamp: i, details: String::new() }
+    }
+
+    #[test]
+    fn test_history_keeps_a_window_and_counts_everything() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        testing_env!(get_context(accounts(0)).build());
+        contract.set_task_history_window(4);
+
+        let task_history: Vec<TaskResult> = (0..6).map(result).collect();
+        let info = AgentInfo { reputation: 10, task_history, reputation_history: Vec::new() };
+        contract.update_agent_reputation(accounts(1), info.clone(), String::new(), None);
+        assert!(contract.get_agent(&accounts(1)).unwrap().reputation_info.task_history.is_empty());
+        // A later whole-state push only adds the results not seen yet
+        let mut next = info.clone();
+        next.task_history.extend((6..9).map(result));
+        contract.update_agent_reputation(accounts(1), next, crate::continuity::history_tip_hash(&info), None);
+
+        let page = contract.get_agent_task_history(&accounts(1), None, Some(2));
+        assert_eq!(page.items[0].task_id, "task5");
+        let rest = contract.get_agent_task_history(&accounts(1), page.next_cursor, None);
+        assert_eq!(rest.items.iter().map(|r| r.timestamp).collect::<Vec<_>>(), vec![7, 8]);
+        let stats = contract.get_agent_task_stats(accounts(1));
+        assert_eq!(stats, TaskStats { total: 9, succeeded: 6, failed: 3, success_rate_bps: 6_666, last_task_at: 8 });
+    }
+
+    #[test]
+    fn test_partial_resend_only_adds_new_results() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        testing_env!(get_context(accounts(0)).build());
+
+        let info = AgentInfo { reputation: 10, task_history: (0..6).map(result).collect(), reputation_history: Vec::new() };
+        contract.update_agent_reputation(accounts(1), info.clone(), String::new(), None);
+        // The reputation contract resends only its latest results
+        let next = AgentInfo { task_history: (4..8).map(result).collect(), ..info.clone() };
+        contract.update_agent_reputation(accounts(1), next, crate::continuity::history_tip_hash(&info), None);
+
+        assert_eq!(contract.get_agent_task_stats(accounts(1)).total, 8);
+        let history = contract.get_agent_task_history(&accounts(1), None, None);
+        assert_eq!(history.items.iter().map(|r| r.timestamp).collect::<Vec<_>>(), (0..8).collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[should_panic(expected = "Task history window must be between 1 and 100")]
+    fn test_window_is_bounded() {
+        testing_env!(get_context(accounts(0)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        contract.set_task_history_window(MAX_TASK_HISTORY_WINDOW + 1);
+    }
+}
@@ -0,0 +1,433 @@
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, require, AccountId, NearToken, Promise, PromiseResult};
+use serde_json::json;
+
+pub use intellex_types::tasks::{
+    ArchivedTask, ProtocolFee, Task, TaskStatus, MAX_PROTOCOL_FEE_BPS, MAX_TASK_DESCRIPTION_LENGTH, MAX_TASK_RESULT_LENGTH,
+    MAX_TASK_SKILLS,
+};
+
+use crate::disputes::FULL_AGENT_SHARE_BPS;
+use crate::events;
+use crate::ext::ext_ft;
+use crate::gas_config::tgas;
+use crate::pagination::{page_limit, paginate, sequence_start, CursorKey, Page};
+use crate::{AgentRegistration, AgentRegistrationExt, AgentStatus, TaskResult, ITLX_TOKEN_CONTRACT};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Posts a task. `reward` yoctoNEAR of the attached deposit is held in
+    /// escrow; the rest goes to the caller's storage balance, which pays
+    /// for the task record. An ITLX reward can be added afterwards with
+    /// `ft_transfer_call` and msg `"fund_task:<task_id>"`.
+    #[payable]
+    pub fn create_task(&mut self, description: String, required_skills: Vec<String>, reward: U128) -> Task {
+        when_not_paused!(self);
+        self.mirror_call("create_task", || {
+            json!({ "description": description, "required_skills": required_skills, "reward": reward })
+        });
+        require!(
+            !description.is_empty() && description.len() <= MAX_TASK_DESCRIPTION_LENGTH,
+            "Task description must be between 1 and 1024 bytes"
+        );
+        require!(required_skills.len() <= MAX_TASK_SKILLS, "Too many required skills");
+        let attached = env::attached_deposit().as_yoctonear();
+        require!(attached >= reward.0, "Attached deposit must cover the reward");
+
+        let creator = env::predecessor_account_id();
+        let mut account = self.storage_accounts.get(&creator).unwrap_or_default();
+        account.total += attached - reward.0;
+        self.storage_accounts.insert(&creator, &account);
+        let initial_usage = env::storage_usage();
+
+        let now = env::block_timestamp();
+        let task = Task {
+            task_id: self.next_task_id,
+            creator: creator.clone(),
+            description,
+            required_skills,
+            reward: reward.0,
+            itlx_reward: 0,
+            fee_bps: self.protocol_fee_bps,
+            status: TaskStatus::Open,
+            agent_id: None,
+            result: None,
+            created_at: now,
+            updated_at: now,
+        };
+        self.next_task_id += 1;
+        self.tasks.insert(&task.task_id, &task);
+        self.settle_storage(&creator, initial_usage);
+
+        events::emit(
+            "task_created",
+            json!({ "task_id": task.task_id, "creator": creator, "reward": reward }),
+        );
+        task
+    }
+
+    /// Hands an open task to an active agent that lists every required
+    /// skill. Creator only.
+    pub fn assign_task(&mut self, task_id: u64, agent_id: AccountId) -> Task {
+        when_not_paused!(self);
+        self.mirror_call("assign_task", || json!({ "task_id": task_id, "agent_id": agent_id }));
+        let mut task = self.creator_task(task_id);
+        require!(task.status == TaskStatus::Open, "Task is not open");
+        let agent = self
+            .agents
+            .get(&agent_id)
+            .unwrap_or_else(|| env::panic_str("Agent not registered"));
+        require!(agent.status == AgentStatus::Active, "Agent is not active");
+        require!(
+            Self::has_skills(&agent, &task.required_skills),
+            "Agent lacks a required skill"
+        );
+
+        task.status = TaskStatus::Assigned;
+        task.agent_id = Some(agent_id.clone());
+        self.save_task(&mut task);
+        let open_tasks = self.agent_open_tasks.get(&agent_id).unwrap_or(0);
+        self.agent_open_tasks.insert(&agent_id, &(open_tasks + 1));
+        self.record_skill_assignments(&task.required_skills);
+        events::emit("task_assigned", json!({ "task_id": task_id, "agent_id": agent_id }));
+        task
+    }
+
+    /// Hands in the assigned agent's result for the creator to review;
+    /// callable by the agent or one of its operators. The result's bytes are
+    /// charged to the agent's storage balance, topped up by the attached
+    /// deposit.
+    #[payable]
+    pub fn submit_result(&mut self, task_id: u64, details: String) -> Task {
+        when_not_paused!(self);
+        self.mirror_call("submit_result", || json!({ "task_id": task_id, "details": details }));
+        require!(details.len() <= MAX_TASK_RESULT_LENGTH, "Task result exceeds 1024 bytes");
+        let mut task = self.get_task(task_id).unwrap_or_else(|| env::panic_str("Task not found"));
+        require!(task.status == TaskStatus::Assigned, "Task is not awaiting a result");
+        let caller = env::predecessor_account_id();
+        let agent_id = task.agent_id.clone().unwrap();
+        require!(
+            caller == agent_id || self.is_agent_operator(&agent_id, &caller),
+            "Only the assigned agent or its operators can submit a result"
+        );
+
+        self.credit_storage_deposit(&agent_id);
+        let initial_usage = env::storage_usage();
+        task.status = TaskStatus::Submitted;
+        task.result = Some(details);
+        self.save_task(&mut task);
+        self.settle_storage(&agent_id, initial_usage);
+        events::emit("task_submitted", json!({ "task_id": task_id, "agent_id": agent_id }));
+        task
+    }
+
+    /// Accepts the submitted result: pays the rewards to the agent, less
+    /// the protocol fee, which goes to the treasury, and records a
+    /// successful `TaskResult` with the reputation contract. Creator only.
+    pub fn approve_task(&mut self, task_id: u64) -> Task {
+        when_not_paused!(self);
+        self.mirror_call("approve_task", || json!({ "task_id": task_id }));
+        let mut task = self.creator_task(task_id);
+        require!(task.status == TaskStatus::Submitted, "Task has no result to approve");
+        let agent_id = task.agent_id.clone().unwrap();
+
+        task.status = TaskStatus::Completed;
+        self.save_task(&mut task);
+        self.queue_settled_task(task_id);
+        self.release_agent_task(&agent_id);
+        self.pay_task_rewards(&task, FULL_AGENT_SHARE_BPS);
+        self.report_task_result(&task, true);
+        events::emit(
+            "task_completed",
+            json!({
+                "task_id": task_id,
+                "agent_id": agent_id,
+                "reward": U128(task.reward),
+                "itlx_reward": U128(task.itlx_reward),
+                "fee_bps": task.fee_bps,
+            }),
+        );
+        task
+    }
+
+    /// Withdraws an open task and refunds both rewards in full. Creator only.
+    pub fn cancel_task(&mut self, task_id: u64) -> Task {
+        when_not_paused!(self);
+        self.mirror_call("cancel_task", || json!({ "task_id": task_id }));
+        let mut task = self.creator_task(task_id);
+        require!(task.status == TaskStatus::Open, "Only open tasks can be cancelled");
+
+        task.status = TaskStatus::Cancelled;
+        self.save_task(&mut task);
+        self.queue_settled_task(task_id);
+        self.pay_near(&task.creator, task.reward);
+        self.pay_itlx(&task.creator, task.itlx_reward);
+        events::emit("task_cancelled", json!({ "task_id": task_id }));
+        task
+    }
+
+    /// Records the outcome of an ITLX payout. A failed `ft_transfer` leaves
+    /// the amount owed to `receiver_id`, to collect with
+    /// `claim_itlx_payout`. Returns whether the transfer went through.
+    #[private]
+    pub fn on_itlx_paid(&mut self, receiver_id: AccountId, amount: U128) -> bool {
+        if let PromiseResult::Successful(_) = env::promise_result(0) {
+            return true;
+        }
+        env::log_str(&format!("ITLX payout of {} to {} failed; owed", amount.0, receiver_id));
+        let owed = self.itlx_payouts_owed.get(&receiver_id).unwrap_or(0);
+        self.itlx_payouts_owed.insert(&receiver_id, &(owed + amount.0));
+        false
+    }
+
+    /// Retries the caller's failed ITLX payouts.
+    pub fn claim_itlx_payout(&mut self) {
+        when_not_paused!(self);
+        let account_id = env::predecessor_account_id();
+        let owed = self
+            .itlx_payouts_owed
+            .remove(&account_id)
+            .unwrap_or_else(|| env::panic_str("No ITLX payout owed"));
+        self.pay_itlx(&account_id, owed);
+    }
+
+    pub fn get_itlx_payout_owed(&self, account_id: AccountId) -> U128 {
+        U128(self.itlx_payouts_owed.get(&account_id).unwrap_or(0))
+    }
+
+    pub fn get_task(&self, task_id: u64) -> Option<Task> {
+        self.tasks.get(&task_id)
+    }
+
+    /// Tasks in creation order.
+    pub fn get_tasks(&self, cursor: Option<String>, limit: Option<u64>) -> Page<Task> {
+        let start = sequence_start("get_tasks", cursor);
+        let tasks = (start..self.next_task_id)
+            .filter_map(|task_id| self.tasks.get(&task_id).map(|task| (CursorKey::Sequence(task_id), task)));
+        paginate("get_tasks", tasks, page_limit(limit))
+    }
+}
+
+impl AgentRegistration {
+    /// Adds ITLX received through `ft_on_transfer` to an open task's reward.
+    pub(crate) fn fund_task(&mut self, sender_id: &AccountId, task_id: u64, amount: u128) {
+        let mut task = self.get_task(task_id).unwrap_or_else(|| env::panic_str("Task not found"));
+        require!(&task.creator == sender_id, "Only the task creator can fund it");
+        require!(task.status == TaskStatus::Open, "Only open tasks can be funded");
+
+        task.itlx_reward += amount;
+        self.save_task(&mut task);
+        events::emit(
+            "task_funded",
+            json!({ "task_id": task_id, "amount": U128(amount), "itlx_reward": U128(task.itlx_reward) }),
+        );
+    }
+
+    /// Pays `agent_share_bps` of a task's escrowed rewards to its agent,
+    /// less the protocol fee, which goes to the treasury, and refunds the
+    /// rest to the creator.
+    pub(crate) fn pay_task_rewards(&self, task: &Task, agent_share_bps: u16) {
+        let agent_id = task.agent_id.as_ref().unwrap();
+        let near_share = share_of(task.reward, agent_share_bps);
+        let itlx_share = share_of(task.itlx_reward, agent_share_bps);
+        let near_fee = protocol_fee(near_share, task.fee_bps);
+        let itlx_fee = protocol_fee(itlx_share, task.fee_bps);
+        self.pay_near(agent_id, near_share - near_fee);
+        self.pay_near(&self.treasury_id, near_fee);
+        self.pay_near(&task.creator, task.reward - near_share);
+        self.pay_itlx(agent_id, itlx_share - itlx_fee);
+        self.pay_itlx(&self.treasury_id, itlx_fee);
+        self.pay_itlx(&task.creator, task.itlx_reward - itlx_share);
+    }
+
+    /// Sends the outcome of a task to the reputation contract's
+    /// `add_task_result`, which pushes the new score back through
+    /// `update_agent_reputation`. In `ReputationMode::Local` the registry
+    /// scores it itself.
+    pub(crate) fn report_task_result(&mut self, task: &Task, success: bool) {
+        let task_result = TaskResult {
+            task_id: task.task_id.to_string(),
+            success,
+            timestamp: task.updated_at,
+            details: task.result.clone().unwrap_or_default(),
+        };
+        match self.reputation_contract() {
+            Some(_) => {
+                let agent_id = task.agent_id.as_ref().unwrap();
+                self.call_reputation_contract("add_task_result", agent_id, None, |reputation| {
+                    reputation.add_task_result(agent_id.clone(), task_result)
+                });
+            }
+            #[cfg(feature = "reputation-engine")]
+            None => self.record_local_task_result(task.agent_id.as_ref().unwrap(), task_result),
+            #[cfg(not(feature = "reputation-engine"))]
+            None => env::panic_str(crate::LOCAL_REPUTATION_UNAVAILABLE),
+        }
+    }
+
+    pub(crate) fn pay_near(&self, receiver_id: &AccountId, amount: u128) {
+        if amount > 0 {
+            Promise::new(receiver_id.clone()).transfer(NearToken::from_yoctonear(amount));
+        }
+    }
+
+    pub(crate) fn pay_itlx(&self, receiver_id: &AccountId, amount: u128) {
+        if amount == 0 {
+            return;
+        }
+        ext_ft::ext(ITLX_TOKEN_CONTRACT.parse().unwrap())
+            .with_attached_deposit(NearToken::from_yoctonear(1))
+            .with_static_gas(tgas(self.gas_config.ft_transfer_tgas))
+            .ft_transfer(receiver_id.clone(), U128(amount), None)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(tgas(self.gas_config.ft_callback_tgas))
+                    .on_itlx_paid(receiver_id.clone(), U128(amount)),
+            );
+    }
+
+    fn creator_task(&self, task_id: u64) -> Task {
+        let task = self.get_task(task_id).unwrap_or_else(|| env::panic_str("Task not found"));
+        require!(
+            task.creator == env::predecessor_account_id(),
+            "Only the task creator can call this method"
+        );
+        task
+    }
+
+    /// Counts one of `agent_id`'s tasks as settled.
+    pub(crate) fn release_agent_task(&mut self, agent_id: &AccountId) {
+        match self.agent_open_tasks.get(agent_id).unwrap_or(0) {
+            0 | 1 => self.agent_open_tasks.remove(agent_id),
+            open_tasks => self.agent_open_tasks.insert(agent_id, &(open_tasks - 1)),
+        };
+    }
+
+    pub(crate) fn save_task(&mut self, task: &mut Task) {
+        task.updated_at = env::block_timestamp();
+        self.tasks.insert(&task.task_id, task);
+    }
+}
+
+fn share_of(amount: u128, share_bps: u16) -> u128 {
+    amount * share_bps as u128 / 10_000
+}
+
+fn protocol_fee(amount: u128, fee_bps: u16) -> u128 {
+    share_of(amount, fee_bps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    const REWARD: u128 = 5_000_000_000_000_000_000_000_000;
+
+    fn create(contract: &mut AgentRegistration, required_skills: Vec<String>) -> Task {
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_yoctonear(REWARD + 10u128.pow(24))).build());
+        contract.create_task("Summarize a paper".to_string(), required_skills, U128(REWARD))
+    }
+
+    #[test]
+    fn test_task_lifecycle() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+
+        let task = create(&mut contract, vec!["Rust".to_string()]);
+        let balance = contract.storage_balance_of(accounts(2)).unwrap();
+        assert_eq!(balance.total, 10u128.pow(24));
+        assert!(balance.available < balance.total);
+
+        testing_env!(get_context(accounts(2)).build());
+        contract.assign_task(task.task_id, accounts(1));
+        testing_env!(context.build());
+        contract.submit_result(task.task_id, "Done".to_string());
+        testing_env!(get_context(accounts(2)).build());
+        let task = contract.approve_task(task.task_id);
+
+        assert_eq!(task.status, TaskStatus::Completed);
+        assert_eq!(task.result.as_deref(), Some("Done"));
+        assert_eq!(contract.get_tasks(None, None).items, vec![task]);
+    }
+
+    #[test]
+    fn test_itlx_reward_escrow_and_fee() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        testing_env!(get_context(accounts(0)).build());
+        contract.set_protocol_fee(250, accounts(3));
+
+        let task = create(&mut contract, Vec::new());
+        assert_eq!(task.fee_bps, 250);
+        testing_env!(get_context(ITLX_TOKEN_CONTRACT.parse().unwrap()).build());
+        contract.ft_on_transfer(accounts(2), U128(1_000), format!("fund_task:{}", task.task_id));
+        assert_eq!(contract.get_task(task.task_id).unwrap().itlx_reward, 1_000);
+        assert_eq!(protocol_fee(1_000, task.fee_bps), 25);
+
+        testing_env!(get_context(accounts(2)).build());
+        contract.assign_task(task.task_id, accounts(1));
+        testing_env!(context.build());
+        contract.submit_result(task.task_id, "Done".to_string());
+        testing_env!(get_context(accounts(2)).build());
+        contract.approve_task(task.task_id);
+
+        // A failed ft_transfer leaves the payout claimable
+        testing_env!(
+            get_context(accounts(0)).build(),
+            near_sdk::test_vm_config(),
+            near_sdk::RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Failed],
+        );
+        assert!(!contract.on_itlx_paid(accounts(1), U128(975)));
+        assert_eq!(contract.get_itlx_payout_owed(accounts(1)).0, 975);
+        testing_env!(get_context(accounts(1)).build());
+        contract.claim_itlx_payout();
+        assert_eq!(contract.get_itlx_payout_owed(accounts(1)).0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the task creator can fund it")]
+    fn test_only_creator_funds_task() {
+        testing_env!(get_context(accounts(1)).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        let task = create(&mut contract, Vec::new());
+        testing_env!(get_context(ITLX_TOKEN_CONTRACT.parse().unwrap()).build());
+        contract.ft_on_transfer(accounts(1), U128(1_000), format!("fund_task:{}", task.task_id));
+    }
+
+    #[test]
+    #[should_panic(expected = "Agent lacks a required skill")]
+    fn test_assign_requires_skills() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+
+        let task = create(&mut contract, vec!["Python".to_string()]);
+        testing_env!(get_context(accounts(2)).build());
+        contract.assign_task(task.task_id, accounts(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the assigned agent or its operators can submit a result")]
+    fn test_submit_requires_assigned_agent() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+
+        let task = create(&mut contract, Vec::new());
+        testing_env!(get_context(accounts(2)).build());
+        contract.assign_task(task.task_id, accounts(1));
+        testing_env!(get_context(accounts(3)).build());
+        contract.submit_result(task.task_id, "Mine".to_string());
+    }
+}
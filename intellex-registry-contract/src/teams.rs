@@ -0,0 +1,399 @@
+use near_sdk::store::IterableSet;
+use near_sdk::{env, near_bindgen, require, AccountId};
+use serde_json::json;
+
+pub use intellex_types::teams::{
+    Team, TeamMetadata, TeamReputation, TeamView, MAX_AGENT_TEAMS, MAX_TEAM_DESCRIPTION_LENGTH,
+    MAX_TEAM_MEMBERS, MAX_TEAM_NAME_LENGTH,
+};
+
+use crate::events;
+use crate::tasks::Task;
+use crate::{AgentRegistration, AgentRegistrationExt, AgentStatus};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Creates a team led by the caller's agent, which becomes its first
+    /// member. The attached deposit, plus any storage balance, pays for the
+    /// team. Returns the new team's id.
+    #[payable]
+    pub fn create_team(&mut self, name: String, metadata: TeamMetadata) -> u64 {
+        when_not_paused!(self);
+        let lead = env::predecessor_account_id();
+        self.owned_agent(&lead);
+        assert_team_metadata(&name, &metadata);
+        require!(self.team_count(&lead) < MAX_AGENT_TEAMS, "An agent can belong to at most 10 teams");
+        self.credit_storage_deposit(&lead);
+        let initial_usage = env::storage_usage();
+
+        let team_id = self.next_team_id;
+        self.next_team_id += 1;
+        let team = Team { team_id, name: name.clone(), metadata, lead: lead.clone(), created_at: env::block_timestamp() };
+        self.teams.insert(&team_id, &team);
+        self.attach_member(team_id, &lead);
+        self.settle_storage(&lead, initial_usage);
+
+        events::emit("team_created", json!({ "team_id": team_id, "name": name, "lead": lead }));
+        team_id
+    }
+
+    /// Invites `agent_id` to the team; it joins once it accepts. Team lead
+    /// only; the attached deposit, plus any storage balance, pays for the
+    /// invite.
+    #[payable]
+    pub fn add_member(&mut self, team_id: u64, agent_id: AccountId) {
+        when_not_paused!(self);
+        let team = self.led_team(team_id);
+        require!(self.agents.contains_key(&agent_id), "Agent not registered");
+        require!(!self.is_team_member(team_id, &agent_id), "Agent is already a member");
+        let members = self.team_members.get(&team_id).map_or(0, |members| members.len());
+        let invites = self.team_invites.get(&team_id).map_or(0, |invites| invites.len());
+        require!(members + invites < MAX_TEAM_MEMBERS, "A team can have at most 20 members");
+        self.credit_storage_deposit(&team.lead);
+        let initial_usage = env::storage_usage();
+
+        let mut invites = self
+            .team_invites
+            .get(&team_id)
+            .unwrap_or_else(|| IterableSet::new(format!("0_{}", team_id).into_bytes()));
+        require!(invites.insert(agent_id.clone()), "Agent is already invited");
+        self.team_invites.insert(&team_id, &invites);
+        // Flush the invite before measuring what it took
+        drop(invites);
+        self.settle_storage(&team.lead, initial_usage);
+        events::emit("team_member_invited", json!({ "team_id": team_id, "agent_id": agent_id }));
+    }
+
+    /// Joins a team the caller's agent was invited to. The invite's storage
+    /// goes back to the lead; the attached deposit, plus any storage
+    /// balance, pays for the membership.
+    #[payable]
+    pub fn accept_team_invite(&mut self, team_id: u64) {
+        when_not_paused!(self);
+        let agent_id = env::predecessor_account_id();
+        self.owned_agent(&agent_id);
+        let team = self.teams.get(&team_id).unwrap_or_else(|| env::panic_str("Team not found"));
+        require!(self.team_count(&agent_id) < MAX_AGENT_TEAMS, "An agent can belong to at most 10 teams");
+        self.withdraw_invite(&team, &agent_id);
+
+        self.credit_storage_deposit(&agent_id);
+        let initial_usage = env::storage_usage();
+        self.attach_member(team_id, &agent_id);
+        self.settle_storage(&agent_id, initial_usage);
+        events::emit("team_member_joined", json!({ "team_id": team_id, "agent_id": agent_id }));
+    }
+
+    /// Turns down an invite to the team.
+    pub fn decline_team_invite(&mut self, team_id: u64) {
+        when_not_paused!(self);
+        let team = self.teams.get(&team_id).unwrap_or_else(|| env::panic_str("Team not found"));
+        self.withdraw_invite(&team, &env::predecessor_account_id());
+    }
+
+    /// Removes `agent_id` from the team, or withdraws its pending invite.
+    /// Callable by the lead for any member but itself, and by a member for
+    /// itself; a lead leaves by disbanding the team.
+    pub fn remove_member(&mut self, team_id: u64, agent_id: AccountId) {
+        when_not_paused!(self);
+        let caller = env::predecessor_account_id();
+        let team = self.teams.get(&team_id).unwrap_or_else(|| env::panic_str("Team not found"));
+        require!(
+            caller == team.lead || caller == agent_id,
+            "Only the team lead or the member itself can remove a member"
+        );
+        require!(agent_id != team.lead, "The team lead cannot leave; disband the team instead");
+        if !self.is_team_member(team_id, &agent_id) {
+            self.withdraw_invite(&team, &agent_id);
+            return;
+        }
+        let initial_usage = env::storage_usage();
+        self.detach_member(team_id, &agent_id);
+        self.settle_storage(&agent_id, initial_usage);
+        events::emit("team_member_removed", json!({ "team_id": team_id, "agent_id": agent_id }));
+    }
+
+    /// Dissolves the team, refunding each member its membership storage.
+    /// Team lead only.
+    pub fn disband_team(&mut self, team_id: u64) {
+        when_not_paused!(self);
+        self.led_team(team_id);
+        self.dissolve_team(team_id);
+    }
+
+    /// The team with its members' aggregated reputation.
+    pub fn get_team(&self, team_id: u64) -> Option<TeamView> {
+        let team = self.teams.get(&team_id)?;
+        let reputation = TeamReputation::from_scores(
+            self.team_member_ids(team_id)
+                .iter()
+                .filter_map(|agent_id| self.agents.get(agent_id))
+                .map(|agent| agent.reputation_info.reputation),
+        );
+        Some(TeamView { team, reputation })
+    }
+
+    /// The team's members, highest reputation first.
+    pub fn get_team_agents(&self, team_id: u64) -> Vec<AccountId> {
+        let mut members: Vec<(u64, AccountId)> = self
+            .team_member_ids(team_id)
+            .into_iter()
+            .map(|agent_id| {
+                let reputation = self.agents.get(&agent_id).map_or(0, |agent| agent.reputation_info.reputation);
+                (reputation, agent_id)
+            })
+            .collect();
+        members.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+        members.into_iter().map(|(_, agent_id)| agent_id).collect()
+    }
+
+    pub fn get_team_invites(&self, team_id: u64) -> Vec<AccountId> {
+        self.team_invites
+            .get(&team_id)
+            .map(|invites| invites.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Ids of the teams `agent_id` belongs to.
+    pub fn get_agent_teams(&self, agent_id: AccountId) -> Vec<u64> {
+        self.agent_teams
+            .get(&agent_id)
+            .map(|teams| teams.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// `assign_task` to the team's highest-reputation active member that
+    /// lists every required skill. Creator only.
+    pub fn assign_task_to_team(&mut self, task_id: u64, team_id: u64) -> Task {
+        require!(self.teams.contains_key(&team_id), "Team not found");
+        let task = self.get_task(task_id).unwrap_or_else(|| env::panic_str("Task not found"));
+        let agent_id = self
+            .get_team_agents(team_id)
+            .into_iter()
+            .find(|agent_id| {
+                self.agents.get(agent_id).is_some_and(|agent| {
+                    agent.status == AgentStatus::Active && Self::has_skills(&agent, &task.required_skills)
+                })
+            })
+            .unwrap_or_else(|| env::panic_str("No active team member has the required skills"));
+        self.assign_task(task_id, agent_id)
+    }
+}
+
+impl AgentRegistration {
+    fn led_team(&self, team_id: u64) -> Team {
+        let team = self.teams.get(&team_id).unwrap_or_else(|| env::panic_str("Team not found"));
+        require!(env::predecessor_account_id() == team.lead, "Only the team lead can manage the team");
+        team
+    }
+
+    fn is_team_member(&self, team_id: u64, agent_id: &AccountId) -> bool {
+        self.team_members
+            .get(&team_id)
+            .is_some_and(|members| members.contains(agent_id))
+    }
+
+    fn team_member_ids(&self, team_id: u64) -> Vec<AccountId> {
+        self.team_members
+            .get(&team_id)
+            .map(|members| members.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn team_count(&self, agent_id: &AccountId) -> u32 {
+        self.agent_teams.get(agent_id).map_or(0, |teams| teams.len())
+    }
+
+    /// Drops `agent_id`'s invite to `team`, unlocking its storage for the
+    /// lead.
+    fn withdraw_invite(&mut self, team: &Team, agent_id: &AccountId) {
+        let mut invites = self
+            .team_invites
+            .get(&team.team_id)
+            .unwrap_or_else(|| env::panic_str("No pending invite"));
+        let initial_usage = env::storage_usage();
+        require!(invites.remove(agent_id), "No pending invite");
+        if invites.is_empty() {
+            self.team_invites.remove(&team.team_id);
+        } else {
+            self.team_invites.insert(&team.team_id, &invites);
+        }
+        drop(invites);
+        self.settle_storage(&team.lead, initial_usage);
+    }
+
+    /// Writes `agent_id` into the team's members and its own team list.
+    /// Storage is left for the caller to settle.
+    fn attach_member(&mut self, team_id: u64, agent_id: &AccountId) {
+        let mut members = self
+            .team_members
+            .get(&team_id)
+            .unwrap_or_else(|| IterableSet::new(format!("b_{}", team_id).into_bytes()));
+        members.insert(agent_id.clone());
+        self.team_members.insert(&team_id, &members);
+        let mut teams = self
+            .agent_teams
+            .get(agent_id)
+            .unwrap_or_else(|| IterableSet::new(format!("1_{}", agent_id).into_bytes()));
+        teams.insert(team_id);
+        self.agent_teams.insert(agent_id, &teams);
+    }
+
+    /// Undoes `attach_member`. Storage is left for the caller to settle.
+    fn detach_member(&mut self, team_id: u64, agent_id: &AccountId) {
+        if let Some(mut members) = self.team_members.get(&team_id) {
+            members.remove(agent_id);
+            if members.is_empty() {
+                self.team_members.remove(&team_id);
+            } else {
+                self.team_members.insert(&team_id, &members);
+            }
+        }
+        if let Some(mut teams) = self.agent_teams.get(agent_id) {
+            teams.remove(&team_id);
+            if teams.is_empty() {
+                self.agent_teams.remove(agent_id);
+            } else {
+                self.agent_teams.insert(agent_id, &teams);
+            }
+        }
+    }
+
+    /// Removes the team, its members and invites, settling each member's
+    /// storage and the lead's.
+    fn dissolve_team(&mut self, team_id: u64) {
+        let team = self.teams.get(&team_id).unwrap();
+        for agent_id in self.team_member_ids(team_id) {
+            let initial_usage = env::storage_usage();
+            self.detach_member(team_id, &agent_id);
+            self.settle_storage(&agent_id, initial_usage);
+        }
+        let initial_usage = env::storage_usage();
+        if let Some(mut invites) = self.team_invites.remove(&team_id) {
+            invites.clear();
+        }
+        self.teams.remove(&team_id);
+        self.settle_storage(&team.lead, initial_usage);
+        events::emit("team_disbanded", json!({ "team_id": team_id, "lead": team.lead }));
+    }
+
+    /// Takes `agent_id` out of every team it belongs to, disbanding the ones
+    /// it leads, with storage settled. Called before the agent is
+    /// deregistered or transferred.
+    pub(crate) fn leave_all_teams(&mut self, agent_id: &AccountId) {
+        for team_id in self.get_agent_teams(agent_id.clone()) {
+            let team = self.teams.get(&team_id).unwrap();
+            if &team.lead == agent_id {
+                self.dissolve_team(team_id);
+            } else {
+                let initial_usage = env::storage_usage();
+                self.detach_member(team_id, agent_id);
+                self.settle_storage(agent_id, initial_usage);
+                events::emit("team_member_removed", json!({ "team_id": team_id, "agent_id": agent_id }));
+            }
+        }
+    }
+}
+
+fn assert_team_metadata(name: &str, metadata: &TeamMetadata) {
+    require!(
+        !name.trim().is_empty() && name.len() <= MAX_TEAM_NAME_LENGTH,
+        "Team name must be between 1 and 64 bytes"
+    );
+    require!(
+        metadata.description.len() <= MAX_TEAM_DESCRIPTION_LENGTH,
+        "Team description must be at most 512 bytes"
+    );
+    require!(
+        !name.chars().any(char::is_control) && !metadata.description.chars().any(|c| c.is_control() && c != '\n'),
+        "Team name and description must not contain control characters"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::storage_cost;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::json_types::U128;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+    use near_sdk::NearToken;
+
+    fn setup() -> AgentRegistration {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        register(&mut contract, &mut context, sample_metadata());
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        register(&mut contract, &mut context, sample_metadata());
+        contract
+    }
+
+    fn team_of_two(contract: &mut AgentRegistration) -> u64 {
+        testing_env!(get_context(accounts(1)).attached_deposit(NearToken::from_near(1)).build());
+        let metadata = TeamMetadata { description: "Translators".to_string(), skills: Vec::new() };
+        let team_id = contract.create_team("Babel".to_string(), metadata);
+        contract.add_member(team_id, accounts(2));
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        contract.accept_team_invite(team_id);
+        team_id
+    }
+
+    #[test]
+    fn test_members_join_and_team_takes_tasks() {
+        let mut contract = setup();
+        let team_id = team_of_two(&mut contract);
+        assert!(contract.get_team_invites(team_id).is_empty());
+        let mut agents = contract.get_team_agents(team_id);
+        agents.sort();
+        assert_eq!(agents, vec![accounts(1), accounts(2)]);
+        let view = contract.get_team(team_id).unwrap();
+        assert_eq!(view.reputation.member_count, 2);
+        assert_eq!(contract.get_agent_teams(accounts(2)), vec![team_id]);
+
+        testing_env!(get_context(accounts(3)).attached_deposit(NearToken::from_near(1)).build());
+        let task = contract.create_task("Translate a paper".to_string(), Vec::new(), U128(1));
+        let task = contract.assign_task_to_team(task.task_id, team_id);
+        assert!(agents.contains(task.agent_id.as_ref().unwrap()));
+    }
+
+    #[test]
+    fn test_membership_storage_is_charged_and_refunded() {
+        let mut contract = setup();
+        testing_env!(get_context(accounts(2)).attached_deposit(NearToken::from_near(1)).build());
+        contract.storage_deposit(None, None);
+        let locked = |contract: &AgentRegistration| {
+            let balance = contract.storage_balance_of(accounts(2)).unwrap();
+            balance.total - balance.available
+        };
+        let locked_before = locked(&contract);
+        testing_env!(get_context(accounts(1)).attached_deposit(NearToken::from_near(1)).build());
+        let team_id = contract.create_team("Babel".to_string(), TeamMetadata::default());
+        contract.add_member(team_id, accounts(2));
+
+        let lead_locked = contract.storage_balance_of(accounts(1)).unwrap();
+        testing_env!(get_context(accounts(2)).build());
+        let initial_usage = env::storage_usage();
+        contract.accept_team_invite(team_id);
+        // The freed invite goes back to the lead, the membership to the member
+        let lead_locked_after = contract.storage_balance_of(accounts(1)).unwrap();
+        let invite_cost = lead_locked_after.available - lead_locked.available;
+        assert!(invite_cost > 0);
+        let growth = storage_cost(env::storage_usage() - initial_usage);
+        assert_eq!(locked(&contract) - locked_before, growth + invite_cost);
+
+        contract.remove_member(team_id, accounts(2));
+        assert_eq!(locked(&contract), locked_before);
+        assert!(contract.get_agent_teams(accounts(2)).is_empty());
+    }
+
+    #[test]
+    fn test_deregistering_the_lead_disbands_the_team() {
+        let mut contract = setup();
+        let team_id = team_of_two(&mut contract);
+        testing_env!(get_context(accounts(1)).build());
+        contract.deregister_agent();
+        assert!(contract.get_team(team_id).is_none());
+        assert!(contract.get_agent_teams(accounts(2)).is_empty());
+    }
+}
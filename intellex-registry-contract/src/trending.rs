@@ -0,0 +1,103 @@
+use std::collections::BTreeSet;
+
+use near_sdk::{env, near_bindgen};
+
+pub use intellex_types::trending::{trending_day, SkillActivity, TrendWindow, TrendingSkill, TRENDING_BUCKET_LENGTH};
+
+use crate::pagination::page_limit;
+use crate::{AgentRegistration, AgentRegistrationExt};
+
+#[near_bindgen]
+impl AgentRegistration {
+    /// Skills with the most registrations plus task assignments over the
+    /// last `window` days, busiest first, ties broken by name. `limit`
+    /// defaults to 50, at most 100. Reads every tracked skill, so gas grows
+    /// with the number of skills.
+    pub fn get_trending_skills(&self, window: TrendWindow, limit: Option<u64>) -> Vec<TrendingSkill> {
+        let oldest = trending_day(env::block_timestamp()).saturating_sub(window.days() - 1);
+        let mut trending: Vec<TrendingSkill> = self
+            .skill_activity
+            .iter()
+            .map(|(skill, buckets)| {
+                let recent = buckets.iter().filter(|bucket| bucket.day >= oldest);
+                TrendingSkill {
+                    skill: skill.clone(),
+                    registrations: recent.clone().map(|bucket| bucket.registrations).sum(),
+                    assignments: recent.map(|bucket| bucket.assignments).sum(),
+                }
+            })
+            .filter(|skill| skill.registrations + skill.assignments > 0)
+            .collect();
+        trending.sort_by(|a, b| {
+            (b.registrations + b.assignments)
+                .cmp(&(a.registrations + a.assignments))
+                .then_with(|| a.skill.cmp(&b.skill))
+        });
+        trending.truncate(page_limit(limit));
+        trending
+    }
+}
+
+impl AgentRegistration {
+    /// Counts a registration listing each of `skills` in today's buckets.
+    pub(crate) fn record_skill_registrations(&mut self, skills: &[String]) {
+        for skill in skills.iter().collect::<BTreeSet<_>>() {
+            self.bump_skill_activity(skill, |bucket| bucket.registrations += 1);
+        }
+    }
+
+    /// Counts an assignment of a task requiring each of `skills`.
+    pub(crate) fn record_skill_assignments(&mut self, skills: &[String]) {
+        for skill in skills.iter().collect::<BTreeSet<_>>() {
+            self.bump_skill_activity(skill, |bucket| bucket.assignments += 1);
+        }
+    }
+
+    /// Applies `bump` to today's bucket for `skill`, dropping buckets too
+    /// old for the longest window.
+    fn bump_skill_activity(&mut self, skill: &String, bump: impl FnOnce(&mut SkillActivity)) {
+        let today = trending_day(env::block_timestamp());
+        let oldest = today.saturating_sub(TrendWindow::Month.days() - 1);
+        let mut buckets = self.skill_activity.get(skill).cloned().unwrap_or_default();
+        buckets.retain(|bucket| bucket.day >= oldest);
+        if buckets.last().is_none_or(|bucket| bucket.day != today) {
+            buckets.push(SkillActivity { day: today, registrations: 0, assignments: 0 });
+        }
+        bump(buckets.last_mut().unwrap());
+        self.skill_activity.insert(skill.clone(), buckets);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{get_context, register, sample_metadata};
+    use near_sdk::json_types::U128;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+    use near_sdk::NearToken;
+
+    #[test]
+    fn test_trending_counts_supply_and_demand_per_window() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.block_timestamp(0).build());
+        let mut contract = AgentRegistration::new(accounts(0), accounts(0));
+        let mut metadata = sample_metadata();
+        metadata.skills = vec!["Go".to_string(), "Go".to_string()];
+        register(&mut contract, &mut context, metadata);
+
+        // Ten days later: one more Rust agent, hired for a Rust task
+        let later = 10 * TRENDING_BUCKET_LENGTH;
+        testing_env!(context.predecessor_account_id(accounts(2)).block_timestamp(later).build());
+        register(&mut contract, &mut context, sample_metadata());
+        testing_env!(get_context(accounts(3)).block_timestamp(later).attached_deposit(NearToken::from_near(2)).build());
+        let task = contract.create_task("Port a crate".to_string(), vec!["Rust".to_string()], U128(10u128.pow(24)));
+        contract.assign_task(task.task_id, accounts(2));
+
+        let rust = TrendingSkill { skill: "Rust".to_string(), registrations: 1, assignments: 1 };
+        let go = TrendingSkill { skill: "Go".to_string(), registrations: 1, assignments: 0 };
+        assert_eq!(contract.get_trending_skills(TrendWindow::Week, None), vec![rust.clone()]);
+        assert_eq!(contract.get_trending_skills(TrendWindow::Month, None), vec![rust, go]);
+        assert_eq!(contract.get_trending_skills(TrendWindow::Month, Some(1)).len(), 1);
+    }
+}
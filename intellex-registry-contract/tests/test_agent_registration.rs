@@ -1,13 +1,13 @@
 use near_sdk::test_utils::{accounts, VMContextBuilder};
 use near_sdk::{testing_env, AccountId};
 use near_sdk::env;
-use near_sdk::test_utils::test_env::{alice, bob};
 use near_sdk::store::IterableSet;
 use near_sdk::collections::LookupMap;
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{near_bindgen, PanicOnDefault, require};
 
+#[allow(dead_code)]
 mod agent_registration {
     use super::*;
 
@@ -118,7 +118,7 @@ mod agent_registration {
         }
 
         pub fn get_agent(&self, agent_id: &AccountId) -> Option<Agent> {
-            self.agents.get(agent_id).map(|agent| agent.clone())
+            self.agents.get(agent_id)
         }
 
         pub fn get_agents_by_skill(&self, skill: &String) -> Vec<AccountId> {
@@ -304,7 +304,7 @@ fn test_get_agent_reputation_history() {
     let context = get_context(reputation_contract.clone());
     testing_env!(context.build());
     
-    let timestamps = vec![
+    let timestamps = [
         env::block_timestamp(),
         env::block_timestamp() + 1000,
         env::block_timestamp() + 2000,
@@ -0,0 +1,246 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_account_id::AccountId;
+use serde::{Deserialize, Serialize};
+
+use crate::reputation::AgentInfo;
+use crate::staking::u128_string;
+
+pub const MAX_ENDPOINT_URL_LENGTH: usize = 256;
+pub const MAX_METADATA_URI_LENGTH: usize = 256;
+/// Schemes `metadata_uri` can use.
+pub const METADATA_URI_SCHEMES: [&str; 3] = ["ipfs://", "ar://", "https://"];
+/// Protocols an agent can list, as stored; matched ignoring case.
+/// `near` means NEAR function calls on the agent's own account.
+pub const AGENT_PROTOCOLS: [&str; 3] = ["http", "grpc", "near"];
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, Default)]
+pub struct AgentMetadata {
+    pub name: String,
+    pub description: String,
+    pub skills: Vec<String>,
+    pub purpose: String,
+    /// Where the agent serves `http` and `grpc` requests; an `http://` or
+    /// `https://` URL.
+    #[serde(default)]
+    pub endpoint_url: Option<String>,
+    /// Entries of `AGENT_PROTOCOLS` the agent accepts requests over.
+    #[serde(default)]
+    pub protocols: Vec<String>,
+    #[serde(default)]
+    pub pricing: Option<PricingModel>,
+    /// Key the agent signs its messages with, as `ed25519:<base58>`.
+    #[serde(default)]
+    pub public_key: Option<String>,
+    /// Off-chain document with the agent's full description, e.g. on IPFS
+    /// or Arweave.
+    #[serde(default)]
+    pub metadata_uri: Option<String>,
+    /// SHA-256 of the document at `metadata_uri`.
+    #[serde(default)]
+    pub metadata_hash: Option<[u8; 32]>,
+}
+
+/// What an agent asks for its work, in yoctoNEAR.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum PricingModel {
+    Free,
+    PerTask {
+        #[serde(with = "u128_string")]
+        amount: u128,
+    },
+    PerCall {
+        #[serde(with = "u128_string")]
+        amount: u128,
+    },
+}
+
+impl AgentMetadata {
+    /// Checks the service descriptor: `endpoint_url`, `protocols`,
+    /// `pricing` and `public_key`. Returns a description of the first
+    /// problem found, if any. Protocols must already be lowercase.
+    pub fn validate_service(&self) -> Result<(), &'static str> {
+        if let Some(url) = &self.endpoint_url {
+            if url.len() > MAX_ENDPOINT_URL_LENGTH {
+                return Err("Endpoint URL must be at most 256 bytes");
+            }
+            let host = url.strip_prefix("https://").or_else(|| url.strip_prefix("http://")).unwrap_or_default();
+            if host.is_empty() || url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+                return Err("Endpoint URL must be an http:// or https:// URL");
+            }
+        }
+        for (i, protocol) in self.protocols.iter().enumerate() {
+            if !AGENT_PROTOCOLS.contains(&protocol.as_str()) {
+                return Err("Unknown protocol; expected http, grpc or near");
+            }
+            if self.protocols[..i].contains(protocol) {
+                return Err("Protocol listed twice");
+            }
+        }
+        if self.endpoint_url.is_none() && self.protocols.iter().any(|protocol| protocol != "near") {
+            return Err("http and grpc protocols need an endpoint URL");
+        }
+        if let Some(PricingModel::PerTask { amount: 0 } | PricingModel::PerCall { amount: 0 }) = self.pricing {
+            return Err("Price must be positive; use PricingModel::Free");
+        }
+        if let Some(key) = &self.public_key {
+            let is_key = key.strip_prefix("ed25519:").is_some_and(|data| {
+                (43..=44).contains(&data.len())
+                    && data.chars().all(|c| c.is_ascii_alphanumeric() && !"0OIl".contains(c))
+            });
+            if !is_key {
+                return Err("Public key must be an ed25519:<base58> key");
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks `metadata_uri` and `metadata_hash`. A hash needs a URI to
+    /// describe.
+    pub fn validate_reference(&self) -> Result<(), &'static str> {
+        match &self.metadata_uri {
+            Some(uri) => {
+                if uri.len() > MAX_METADATA_URI_LENGTH {
+                    return Err("Metadata URI must be at most 256 bytes");
+                }
+                let is_uri = METADATA_URI_SCHEMES.iter().any(|scheme| {
+                    uri.strip_prefix(scheme).is_some_and(|rest| !rest.is_empty())
+                });
+                if !is_uri || uri.chars().any(|c| c.is_whitespace() || c.is_control()) {
+                    return Err("Metadata URI must be an ipfs://, ar:// or https:// URI");
+                }
+            }
+            None if self.metadata_hash.is_some() => return Err("Metadata hash needs a metadata URI"),
+            None => {}
+        }
+        Ok(())
+    }
+}
+
+/// Metadata before the service descriptor fields.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+pub struct AgentMetadataV1 {
+    pub name: String,
+    pub description: String,
+    pub skills: Vec<String>,
+    pub purpose: String,
+}
+
+impl From<AgentMetadataV1> for AgentMetadata {
+    fn from(metadata: AgentMetadataV1) -> Self {
+        AgentMetadata {
+            name: metadata.name,
+            description: metadata.description,
+            skills: metadata.skills,
+            purpose: metadata.purpose,
+            ..Default::default()
+        }
+    }
+}
+
+/// Where an agent is in its lifecycle. Only `Active` agents should be
+/// routed new tasks.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AgentStatus {
+    #[default]
+    Active,
+    /// Dormant by the owner's choice; can return to `Active` at any time.
+    Inactive,
+    /// Set by the registry owner; only the registry owner can lift it.
+    Suspended,
+    /// Permanently out of service; the owner can only deregister.
+    Retired,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+pub struct Agent {
+    pub owner_id: AccountId,
+    pub metadata: AgentMetadata,
+    pub registered_at: u64,
+    pub updated_at: u64,
+    pub reputation_info: AgentInfo,  // Using AgentInfo from reputation contract
+    /// Bumped on every change to the agent's record, handle or availability,
+    /// so caches can tell when to refetch. Restarts at 0 on re-registration.
+    #[serde(default)]
+    pub version: u64,
+    #[serde(default)]
+    pub status: AgentStatus,
+}
+
+/// Agent record as first deployed, before `updated_at` and `version`.
+/// Still found in storage written by that release.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+pub struct AgentV1 {
+    pub owner_id: AccountId,
+    pub metadata: AgentMetadataV1,
+    pub registered_at: u64,
+    pub reputation_info: AgentInfo,
+}
+
+impl From<AgentV1> for Agent {
+    fn from(agent: AgentV1) -> Self {
+        Agent {
+            owner_id: agent.owner_id,
+            metadata: agent.metadata.into(),
+            registered_at: agent.registered_at,
+            updated_at: agent.registered_at,
+            reputation_info: agent.reputation_info,
+            version: 0,
+            status: AgentStatus::Active,
+        }
+    }
+}
+
+/// Storage envelope for agent records. Add a variant, and freeze the
+/// previous layout as its own struct, whenever `Agent` gains a field.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
+pub enum VersionedAgent {
+    V1(AgentV1),
+    /// The current `Agent` layout.
+    V2(Box<Agent>),
+}
+
+impl From<VersionedAgent> for Agent {
+    fn from(agent: VersionedAgent) -> Self {
+        match agent {
+            VersionedAgent::V1(agent) => agent.into(),
+            VersionedAgent::V2(agent) => *agent,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_service() {
+        let metadata = AgentMetadata {
+            endpoint_url: Some("https://agent.example.com".to_string()),
+            protocols: vec!["grpc".to_string()],
+            ..Default::default()
+        };
+        assert!(metadata.validate_service().is_ok());
+        let no_host = AgentMetadata { endpoint_url: Some("https://".to_string()), ..metadata.clone() };
+        assert!(no_host.validate_service().is_err());
+        let unknown = AgentMetadata { protocols: vec!["ftp".to_string()], ..metadata.clone() };
+        assert!(unknown.validate_service().is_err());
+        let free_priced = AgentMetadata { pricing: Some(PricingModel::PerCall { amount: 0 }), ..metadata.clone() };
+        assert!(free_priced.validate_service().is_err());
+        let bad_key = AgentMetadata { public_key: Some("ed25519:0OIl".to_string()), ..metadata };
+        assert!(bad_key.validate_service().is_err());
+    }
+
+    #[test]
+    fn test_validate_reference() {
+        let metadata = AgentMetadata {
+            metadata_uri: Some("ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string()),
+            metadata_hash: Some([7; 32]),
+            ..Default::default()
+        };
+        assert!(metadata.validate_reference().is_ok());
+        let unpinned = AgentMetadata { metadata_uri: Some("ftp://host/doc.json".to_string()), ..metadata.clone() };
+        assert!(unpinned.validate_reference().is_err());
+        let orphan_hash = AgentMetadata { metadata_uri: None, ..metadata };
+        assert!(orphan_hash.validate_reference().is_err());
+    }
+}
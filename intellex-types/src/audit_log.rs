@@ -0,0 +1,22 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+/// One period's commitment to an agent's off-chain action log.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+pub struct AuditAnchor {
+    pub period_id: u64,
+    /// Hex-encoded 32-byte merkle root of the period's actions.
+    pub merkle_root: String,
+    pub anchored_at: u64,
+    /// sha256(previous chain_hash || period_id || merkle_root), hex-encoded.
+    pub chain_hash: String,
+    /// True when one or more periods were skipped before this anchor.
+    pub follows_gap: bool,
+}
+
+/// An inclusive range of periods that were never anchored.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AuditGap {
+    pub from_period: u64,
+    pub to_period: u64,
+}
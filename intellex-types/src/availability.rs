@@ -0,0 +1,119 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+pub const MINUTES_PER_DAY: u16 = 24 * 60;
+pub const MAX_UTC_OFFSET_MINUTES: i16 = 14 * 60;
+pub const MAX_WORKING_WINDOWS: usize = 14;
+/// A ping older than this no longer counts towards being online.
+pub const ONLINE_PING_WINDOW_NS: u64 = 15 * 60 * 1_000_000_000; // 15 minutes
+
+const NS_PER_MINUTE: u64 = 60 * 1_000_000_000;
+
+/// A recurring local-time window, e.g. weekdays 09:00-17:00. Windows may not
+/// cross midnight; split overnight shifts into two windows.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct WorkingWindow {
+    /// Bitmask of weekdays, bit 0 = Monday through bit 6 = Sunday.
+    pub days: u8,
+    /// Minutes after local midnight, inclusive.
+    pub start_minute: u16,
+    /// Minutes after local midnight, exclusive.
+    pub end_minute: u16,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Availability {
+    /// Offset of the agent's timezone from UTC, e.g. -300 for UTC-5.
+    pub utc_offset_minutes: i16,
+    /// Empty means the agent declares itself available around the clock.
+    pub working_hours: Vec<WorkingWindow>,
+}
+
+impl Availability {
+    /// Returns a description of the first problem found, if any.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.utc_offset_minutes.abs() > MAX_UTC_OFFSET_MINUTES {
+            return Err("UTC offset must be within +/-14 hours");
+        }
+        if self.working_hours.len() > MAX_WORKING_WINDOWS {
+            return Err("Too many working-hour windows");
+        }
+        for window in &self.working_hours {
+            if window.days == 0 || window.days >= 1 << 7 {
+                return Err("Working-hour window must name at least one valid weekday");
+            }
+            if window.start_minute >= window.end_minute || window.end_minute > MINUTES_PER_DAY {
+                return Err("Working-hour window must start before it ends, within one day");
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `timestamp_ns` (UTC) falls inside a declared window.
+    pub fn is_working_at(&self, timestamp_ns: u64) -> bool {
+        if self.working_hours.is_empty() {
+            return true;
+        }
+        let local_minutes =
+            (timestamp_ns / NS_PER_MINUTE) as i64 + self.utc_offset_minutes as i64;
+        let minute_of_day = local_minutes.rem_euclid(MINUTES_PER_DAY as i64) as u16;
+        // 1970-01-01 was a Thursday, which is weekday 3 counting from Monday
+        let weekday = (local_minutes.div_euclid(MINUTES_PER_DAY as i64) + 3).rem_euclid(7) as u8;
+        self.working_hours.iter().any(|window| {
+            window.days & (1 << weekday) != 0
+                && (window.start_minute..window.end_minute).contains(&minute_of_day)
+        })
+    }
+}
+
+/// Best-effort estimate that an agent can respond right now: it pinged
+/// recently and `now` is inside its declared working hours.
+pub fn is_online(availability: Option<&Availability>, last_ping: Option<u64>, now: u64) -> bool {
+    let recently_pinged = last_ping.is_some_and(|ping| now.saturating_sub(ping) <= ONLINE_PING_WINDOW_NS);
+    recently_pinged && availability.is_none_or(|availability| availability.is_working_at(now))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE: u64 = NS_PER_MINUTE;
+    const DAY: u64 = 24 * 60 * MINUTE;
+
+    fn weekdays_nine_to_five(utc_offset_minutes: i16) -> Availability {
+        Availability {
+            utc_offset_minutes,
+            working_hours: vec![WorkingWindow { days: 0b001_1111, start_minute: 9 * 60, end_minute: 17 * 60 }],
+        }
+    }
+
+    #[test]
+    fn test_is_working_at_respects_weekday_and_offset() {
+        // Day 4 after the epoch is Monday 1970-01-05
+        let monday_noon_utc = 4 * DAY + 12 * 60 * MINUTE;
+        assert!(weekdays_nine_to_five(0).is_working_at(monday_noon_utc));
+        // Noon UTC is 04:00 in UTC-8
+        assert!(!weekdays_nine_to_five(-480).is_working_at(monday_noon_utc));
+        // Saturday 1970-01-03
+        assert!(!weekdays_nine_to_five(0).is_working_at(2 * DAY + 12 * 60 * MINUTE));
+    }
+
+    #[test]
+    fn test_is_online_needs_recent_ping() {
+        let now = 4 * DAY + 12 * 60 * MINUTE;
+        let availability = weekdays_nine_to_five(0);
+        assert!(is_online(Some(&availability), Some(now - 5 * MINUTE), now));
+        assert!(!is_online(Some(&availability), Some(now - 20 * MINUTE), now));
+        assert!(!is_online(Some(&availability), None, now));
+        assert!(is_online(None, Some(now), now));
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_windows() {
+        let mut availability = weekdays_nine_to_five(0);
+        assert!(availability.validate().is_ok());
+        availability.working_hours[0].start_minute = 17 * 60;
+        assert!(availability.validate().is_err());
+        assert!(weekdays_nine_to_five(15 * 60).validate().is_err());
+    }
+}
@@ -0,0 +1,51 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReputationBand {
+    New,
+    Bronze,
+    Silver,
+    Gold,
+    Elite,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BandThreshold {
+    pub band: ReputationBand,
+    /// Lowest reputation that earns this band.
+    pub min_reputation: u64,
+}
+
+/// Bands in ascending order of `min_reputation`; the first entry must start at 0.
+pub const DEFAULT_BAND_THRESHOLDS: [(ReputationBand, u64); 5] = [
+    (ReputationBand::New, 0),
+    (ReputationBand::Bronze, 10),
+    (ReputationBand::Silver, 40),
+    (ReputationBand::Gold, 70),
+    (ReputationBand::Elite, 90),
+];
+
+pub fn band_for_reputation(reputation: u64) -> ReputationBand {
+    DEFAULT_BAND_THRESHOLDS
+        .iter()
+        .rev()
+        .find(|(_, min_reputation)| reputation >= *min_reputation)
+        .map(|(band, _)| *band)
+        .unwrap_or(ReputationBand::New)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_band_for_reputation() {
+        assert_eq!(band_for_reputation(0), ReputationBand::New);
+        assert_eq!(band_for_reputation(9), ReputationBand::New);
+        assert_eq!(band_for_reputation(10), ReputationBand::Bronze);
+        assert_eq!(band_for_reputation(69), ReputationBand::Silver);
+        assert_eq!(band_for_reputation(70), ReputationBand::Gold);
+        assert_eq!(band_for_reputation(u64::MAX), ReputationBand::Elite);
+    }
+}
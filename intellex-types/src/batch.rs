@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+use crate::agent::AgentMetadata;
+use crate::availability::Availability;
+
+/// Most actions one `batch_execute` call can apply.
+pub const MAX_BATCH_ACTIONS: usize = 20;
+
+/// A change to the caller's own agent profile that `batch_execute` can
+/// combine with others. Each action sees the effects of the ones before it.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum RegistryAction {
+    /// Same as `update_agent_metadata`.
+    UpdateMetadata(Box<AgentMetadata>),
+    /// Lists one more skill; a no-op if already listed.
+    AddSkill(String),
+    /// Stops listing a skill; a no-op if not listed.
+    RemoveSkill(String),
+    /// Same as `set_availability`.
+    SetAvailability(Availability),
+    /// Same as `ping`.
+    Ping,
+}
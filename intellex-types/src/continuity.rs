@@ -0,0 +1,56 @@
+use near_account_id::AccountId;
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::reputation::{AgentInfo, ReputationBreakdown, TaskResult};
+
+/// A reputation push whose `prev_tip_hash` did not match the registry's
+/// tip, held back until a sync reconciles the two contracts.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+pub struct ReconciliationItem {
+    pub agent_id: AccountId,
+    /// Tip the registry holds; empty if no update has been applied yet.
+    pub expected_tip: String,
+    pub provided_tip: String,
+    /// Reputation carried by the rejected push.
+    pub proposed_reputation: u64,
+    pub flagged_at: u64,
+}
+
+/// Pushes held while reputation updates are paused. When the queue is full
+/// further pushes are rejected, so the reputation contract sees the failure
+/// and can retry instead of the update being dropped.
+pub const MAX_HELD_REPUTATION_UPDATES: u64 = 500;
+
+/// A reputation push received while updates were paused, applied in
+/// arrival order by `replay_held_reputation_updates`.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+pub struct HeldReputationUpdate {
+    pub agent_id: AccountId,
+    pub push: ReputationPush,
+    pub received_at: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+pub enum ReputationPush {
+    /// An `update_agent_reputation` push.
+    Full {
+        reputation_info: AgentInfo,
+        prev_tip_hash: String,
+        breakdown: Option<ReputationBreakdown>,
+    },
+    /// A `record_task_result` or `record_reputation_change` push. Its nonce
+    /// is checked when it is replayed.
+    Incremental {
+        change: IncrementalChange,
+        nonce: u64,
+        tip_hash: String,
+    },
+}
+
+/// What an incremental push changes.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+pub enum IncrementalChange {
+    TaskResult(TaskResult),
+    Reputation(u64),
+}
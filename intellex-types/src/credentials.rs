@@ -0,0 +1,26 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_account_id::AccountId;
+use serde::{Deserialize, Serialize};
+
+pub const MAX_CREDENTIAL_NAME_LENGTH: usize = 64;
+/// Most credentials, revoked ones included, an agent can hold.
+pub const MAX_AGENT_CREDENTIALS: u32 = 32;
+
+/// A non-transferable attestation about an agent, e.g. "Audited" or
+/// "KYC-verified", issued by the registry owner or an approved verifier.
+/// An agent holds at most one credential per name.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Credential {
+    pub name: String,
+    pub issuer: AccountId,
+    pub issued_at: u64,
+    pub expires_at: Option<u64>,
+    pub revoked_at: Option<u64>,
+}
+
+impl Credential {
+    /// Neither revoked nor expired at `now`.
+    pub fn is_valid(&self, now: u64) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|expires_at| now < expires_at)
+    }
+}
@@ -0,0 +1,48 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_account_id::AccountId;
+use serde::{Deserialize, Serialize};
+
+use crate::staking::u128_string;
+
+/// NEAR bond attached to a flag: 0.1 NEAR, refunded if the flag is
+/// confirmed and sent to the treasury if it is rejected.
+pub const FLAG_BOND: u128 = 100_000_000_000_000_000_000_000;
+pub const MAX_FLAG_REASON_LENGTH: usize = 512;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlagStatus {
+    /// Waiting for the arbiter or owner to review it.
+    Pending,
+    /// Upheld; the agent was suspended and the flagger paid the bounty.
+    Confirmed,
+    /// Dismissed; the bond went to the treasury.
+    Rejected,
+}
+
+/// A report that an agent listing is invalid, e.g. a dead endpoint or
+/// fraudulent claims.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ListingFlag {
+    pub flag_id: u64,
+    pub agent_id: AccountId,
+    pub flagger: AccountId,
+    pub reason: String,
+    #[serde(with = "u128_string")]
+    pub bond: u128,
+    pub status: FlagStatus,
+    /// ITLX paid to the flagger on confirmation; less than the configured
+    /// bounty if the pool ran short.
+    #[serde(with = "u128_string")]
+    pub bounty_paid: u128,
+    pub flagged_at: u64,
+    pub reviewed_at: Option<u64>,
+}
+
+/// The ITLX bounty per confirmed flag and the pool it is paid from.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CurationBounty {
+    #[serde(with = "u128_string")]
+    pub bounty: u128,
+    #[serde(with = "u128_string")]
+    pub pool: u128,
+}
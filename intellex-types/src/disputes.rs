@@ -0,0 +1,51 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_account_id::AccountId;
+use serde::{Deserialize, Serialize};
+
+use crate::tasks::TaskStatus;
+
+pub const MAX_DISPUTE_EVIDENCE_LENGTH: usize = 1024;
+/// Time the arbiter has to rule on a dispute: 7 days, in nanoseconds.
+pub const DISPUTE_RESOLUTION_PERIOD: u64 = 7 * 24 * 60 * 60 * 1_000_000_000;
+/// An agent share of the whole reward.
+pub const FULL_AGENT_SHARE_BPS: u16 = 10_000;
+
+/// How a disputed task's escrow is settled.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DisputeRuling {
+    /// Share of both rewards paid to the agent, in basis points; the rest
+    /// is refunded to the creator. The protocol fee comes out of the
+    /// agent's share only.
+    pub agent_share_bps: u16,
+    /// Reports a failed `TaskResult` to the reputation contract. Without
+    /// it, a full agent share reports a successful one and any other share
+    /// reports nothing.
+    pub report_failure: bool,
+}
+
+/// A challenge to an assigned or submitted task, raised by its creator or
+/// its agent.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Dispute {
+    pub task_id: u64,
+    pub raised_by: AccountId,
+    pub evidence: String,
+    /// Status of the task when it was disputed.
+    pub disputed_status: TaskStatus,
+    pub raised_at: u64,
+    /// Last timestamp the arbiter can rule at; afterwards anyone can settle
+    /// the dispute with its `fallback_ruling`.
+    pub deadline: u64,
+    pub ruling: Option<DisputeRuling>,
+    pub resolved_at: Option<u64>,
+}
+
+impl Dispute {
+    /// Ruling applied when the arbiter misses the deadline: a submitted
+    /// result is paid in full, an unsubmitted task is refunded, and no
+    /// failure is reported.
+    pub fn fallback_ruling(&self) -> DisputeRuling {
+        let agent_share_bps = if self.disputed_status == TaskStatus::Submitted { FULL_AGENT_SHARE_BPS } else { 0 };
+        DisputeRuling { agent_share_bps, report_failure: false }
+    }
+}
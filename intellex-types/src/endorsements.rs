@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// Peer endorsements of one of an agent's skills.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SkillEndorsement {
+    pub skill: String,
+    /// Agents that endorsed the skill, including since-deregistered ones.
+    pub endorsement_count: u64,
+    /// Sum of the current reputation of endorsers still registered, so an
+    /// endorsement counts for as much as its endorser's standing.
+    pub weighted_score: u64,
+}
@@ -0,0 +1,15 @@
+//! Cross-contract interface for contracts that read from the registry.
+//! Enabled by the `ext` feature, which pulls in `near-sdk`.
+
+use near_sdk::{ext_contract, AccountId};
+
+use crate::reputation::ReputationWithTtl;
+
+/// Views consumer contracts call on the registry, e.g.
+/// `ext_agent_registry::ext(registry_id).get_reputation_with_ttl(agent_id)`.
+/// Cache each answer for its `cache_ttl_ns` rather than inventing a local
+/// staleness rule.
+#[ext_contract(ext_agent_registry)]
+pub trait AgentRegistry {
+    fn get_reputation_with_ttl(&self, agent_id: AccountId) -> Option<ReputationWithTtl>;
+}
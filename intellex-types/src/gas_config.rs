@@ -0,0 +1,79 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Most TGas a single transaction can carry.
+pub const MAX_TRANSACTION_TGAS: u64 = 300;
+
+/// TGas left for the calling method itself when a call and its callback
+/// are scheduled together.
+pub const CALLER_RESERVED_TGAS: u64 = 20;
+
+/// Gas, in TGas, the registry attaches to each outbound call and callback.
+/// Tunable by the registry owner for reputation contracts that need more.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct GasConfig {
+    /// Each call to the reputation contract.
+    pub reputation_call_tgas: u64,
+    /// The registry's callback after a reputation contract call.
+    pub reputation_callback_tgas: u64,
+    /// The `sync_reputation_batch` callback, on top of its per-agent share.
+    pub batch_callback_tgas: u64,
+    pub batch_callback_per_agent_tgas: u64,
+    /// ITLX `ft_transfer` calls for payouts and withdrawals.
+    pub ft_transfer_tgas: u64,
+    pub ft_callback_tgas: u64,
+    /// The SocialDB `get` behind `link_social_profile`.
+    pub social_get_tgas: u64,
+    pub social_callback_tgas: u64,
+}
+
+impl Default for GasConfig {
+    fn default() -> Self {
+        Self {
+            reputation_call_tgas: 5,
+            reputation_callback_tgas: 5,
+            batch_callback_tgas: 10,
+            batch_callback_per_agent_tgas: 5,
+            ft_transfer_tgas: 10,
+            ft_callback_tgas: 10,
+            social_get_tgas: 10,
+            social_callback_tgas: 10,
+        }
+    }
+}
+
+impl GasConfig {
+    /// Each budget must be positive, and each call plus its callback must
+    /// leave `CALLER_RESERVED_TGAS` of a transaction for the caller.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        let budgets = [
+            self.reputation_call_tgas,
+            self.reputation_callback_tgas,
+            self.batch_callback_tgas,
+            self.batch_callback_per_agent_tgas,
+            self.ft_transfer_tgas,
+            self.ft_callback_tgas,
+            self.social_get_tgas,
+            self.social_callback_tgas,
+        ];
+        if budgets.contains(&0) {
+            return Err("Gas budgets must be positive");
+        }
+        if budgets.iter().any(|&tgas| tgas > MAX_TRANSACTION_TGAS) {
+            return Err("Gas budgets must be at most 300 TGas");
+        }
+        let pairs = [
+            self.reputation_call_tgas.saturating_add(self.reputation_callback_tgas),
+            self.ft_transfer_tgas.saturating_add(self.ft_callback_tgas),
+            self.social_get_tgas.saturating_add(self.social_callback_tgas),
+            // A one-agent `sync_reputation_batch`
+            self.reputation_call_tgas
+                .saturating_add(self.batch_callback_tgas)
+                .saturating_add(self.batch_callback_per_agent_tgas),
+        ];
+        if pairs.iter().any(|&tgas| tgas > MAX_TRANSACTION_TGAS - CALLER_RESERVED_TGAS) {
+            return Err("A call and its callback must leave 20 TGas of a transaction for the caller");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,28 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_account_id::AccountId;
+use serde::{Deserialize, Serialize};
+
+pub const MIN_HANDLE_LENGTH: usize = 3;
+pub const MAX_HANDLE_LENGTH: usize = 32;
+pub const HANDLE_RESERVATION_TTL_NS: u64 = 30 * 24 * 60 * 60 * 1_000_000_000; // 30 days
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum HandleStatus {
+    /// Held ahead of registration; lapses at `expires_at` unless claimed.
+    Reserved { expires_at: u64 },
+    /// Bound to a registered agent until released or transferred.
+    Claimed,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+pub struct HandleRecord {
+    pub holder: AccountId,
+    pub status: HandleStatus,
+    pub updated_at: u64,
+}
+
+impl HandleRecord {
+    pub fn is_expired(&self, now: u64) -> bool {
+        matches!(self.status, HandleStatus::Reserved { expires_at } if expires_at <= now)
+    }
+}
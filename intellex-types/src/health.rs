@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+use crate::staking::u128_string;
+
+/// Structural diagnostics returned by `health_check`, meant for alerting
+/// before the contract runs out of balance to pay for its storage.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct HealthReport {
+    /// Bytes of state the contract account uses.
+    pub storage_usage: u64,
+    /// Unlocked balance of the contract account, in yoctoNEAR.
+    #[serde(with = "u128_string")]
+    pub account_balance: u128,
+    /// Balance `storage_usage` requires at the current byte cost.
+    #[serde(with = "u128_string")]
+    pub storage_cost: u128,
+    /// Further bytes the balance can pay for; zero once storage is underfunded.
+    pub storage_headroom_bytes: u64,
+    pub counts: CollectionCounts,
+    pub backlog: PendingBacklog,
+    pub paused: PausedScopes,
+    /// Hex SHA-256 over the owner, pending owner, reputation contract,
+    /// treasury, protocol fee, arbiter and curation bounty; changes whenever
+    /// any of them does.
+    pub config_checksum: String,
+}
+
+/// Sizes of the collections the contract keeps a count for.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CollectionCounts {
+    pub agents: u64,
+    /// Tasks ever created, including settled ones.
+    pub tasks: u64,
+    /// Listing flags ever raised, including reviewed ones.
+    pub listing_flags: u64,
+}
+
+/// Work queued for later processing.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PendingBacklog {
+    /// Agents whose reputation pushes broke the hash chain.
+    pub reconciliation_queue: u64,
+    /// Reputation pushes held while updates were paused.
+    pub held_reputation_updates: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PausedScopes {
+    pub registry: bool,
+    pub registration: bool,
+    pub reputation_updates: bool,
+}
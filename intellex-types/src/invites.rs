@@ -0,0 +1,41 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_account_id::AccountId;
+use serde::{Deserialize, Serialize};
+
+/// Most codes one `mint_invite_codes` call can add.
+pub const MAX_INVITE_MINT_BATCH: usize = 100;
+pub const MAX_COHORT_LENGTH: usize = 64;
+/// Reputation an agent needs to invite others, the start of the Gold band.
+pub const MIN_REFERRER_REPUTATION: u64 = 70;
+/// Most unredeemed referral codes an agent can hold at once.
+pub const MAX_OPEN_REFERRAL_CODES: u32 = 5;
+/// Cohort recorded for agents that registered with a referral code.
+pub const REFERRAL_COHORT: &str = "referral";
+
+/// A single-use registration invite. Only the hex SHA-256 of the code is
+/// stored; the registrant reveals the code itself to `register_agent`.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct InviteCode {
+    pub code_hash: String,
+    /// Beta cohort the resulting agent is counted in.
+    pub cohort: String,
+    pub expires_at: Option<u64>,
+    /// Only this account may redeem the code. An unbound code can be taken
+    /// by anyone who sees it, e.g. in a pending transaction.
+    pub bound_to: Option<AccountId>,
+}
+
+/// The invite an agent registered with, kept for cohort analytics.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct InviteRedemption {
+    pub code_hash: String,
+    pub cohort: String,
+    pub redeemed_at: u64,
+}
+
+/// An agent registered with a code another agent created.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Referral {
+    pub agent_id: AccountId,
+    pub referred_at: u64,
+}
@@ -0,0 +1,60 @@
+//! Shared data types for the IAIP agent registry.
+//!
+//! These are the argument and return types of the registry contract's
+//! public methods, kept free of `near-sdk` so off-chain services can
+//! depend on them without pulling in contract machinery. The optional
+//! `ext` feature adds cross-contract bindings for consumer contracts.
+
+pub mod agent;
+pub mod audit_log;
+pub mod availability;
+pub mod bands;
+pub mod batch;
+pub mod continuity;
+pub mod credentials;
+pub mod curation;
+pub mod disputes;
+pub mod endorsements;
+#[cfg(feature = "ext")]
+pub mod ext;
+pub mod gas_config;
+pub mod handles;
+pub mod health;
+pub mod invites;
+pub mod matching;
+pub mod metrics;
+pub mod mirroring;
+pub mod nft;
+pub mod operators;
+pub mod pagination;
+pub mod protocol;
+pub mod rankings;
+pub mod registration_access;
+pub mod registration_limits;
+pub mod registry_config;
+pub mod registry_stats;
+pub mod reputation;
+pub mod reputation_engine;
+pub mod requester_lists;
+pub mod reviews;
+pub mod skill_history;
+pub mod skill_versions;
+pub mod skills_registry;
+pub mod slashing;
+pub mod social;
+pub mod staking;
+pub mod storage;
+pub mod summary;
+pub mod system_skills;
+pub mod task_history;
+pub mod tasks;
+pub mod teams;
+pub mod trending;
+
+pub use agent::{
+    Agent, AgentMetadata, AgentMetadataV1, AgentStatus, AgentV1, PricingModel, VersionedAgent,
+};
+pub use near_account_id::AccountId;
+pub use protocol::PROTOCOL_VERSION;
+pub use reputation::{AgentInfo, TaskResult};
+pub use summary::{AgentSummary, SortOrder};
@@ -0,0 +1,46 @@
+use near_account_id::AccountId;
+use serde::{Deserialize, Serialize};
+
+/// Share of a match score given to skill overlap, in percent; reputation
+/// makes up the rest.
+pub const MATCH_SKILL_WEIGHT_PCT: u64 = 70;
+/// Reputation at or above which an agent gets the full reputation share.
+pub const MATCH_REPUTATION_CAP: u64 = 100;
+
+/// A candidate for a task, as ranked by `match_agents`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AgentMatch {
+    pub agent_id: AccountId,
+    /// Required skills the agent lists, in the order they were required.
+    pub matched_skills: Vec<String>,
+    pub reputation: u64,
+    /// `match_score` of the agent, in basis points.
+    pub score: u32,
+}
+
+/// Blends skill overlap (`matched` of `required` skills) with reputation
+/// into a score in basis points. Off-chain routers can call this to rank
+/// agents the same way the registry does.
+pub fn match_score(matched: usize, required: usize, reputation: u64) -> u32 {
+    if required == 0 {
+        return 0;
+    }
+    let overlap_bps = matched.min(required) as u64 * 10_000 / required as u64;
+    let reputation_bps = reputation.min(MATCH_REPUTATION_CAP) * 10_000 / MATCH_REPUTATION_CAP;
+    ((overlap_bps * MATCH_SKILL_WEIGHT_PCT + reputation_bps * (100 - MATCH_SKILL_WEIGHT_PCT)) / 100) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_score_weighs_overlap_over_reputation() {
+        assert_eq!(match_score(2, 2, 100), 10_000);
+        assert_eq!(match_score(1, 2, 100), 6_500);
+        assert_eq!(match_score(2, 2, 0), 7_000);
+        assert_eq!(match_score(2, 2, 500), match_score(2, 2, 100));
+        assert!(match_score(2, 2, 0) > match_score(1, 2, 100));
+        assert_eq!(match_score(0, 0, 100), 0);
+    }
+}
@@ -0,0 +1,42 @@
+/// Fewer tasks than this and a success rate is not reported at all.
+pub const MIN_SUCCESS_RATE_SAMPLE: u64 = 10;
+
+/// z-score for a 95% confidence interval.
+const WILSON_Z: f64 = 1.96;
+
+/// Lower bound of the Wilson score interval for `successes` out of `total`,
+/// in basis points (0-10_000). Returns `None` below `min_sample` tasks so a
+/// handful of lucky results can't read as a perfect record.
+pub fn wilson_lower_bound_bps(successes: u64, total: u64, min_sample: u64) -> Option<u32> {
+    if total == 0 || total < min_sample {
+        return None;
+    }
+    let n = total as f64;
+    let p = successes.min(total) as f64 / n;
+    let z2 = WILSON_Z * WILSON_Z;
+    let centre = p + z2 / (2.0 * n);
+    let margin = WILSON_Z * ((p * (1.0 - p) + z2 / (4.0 * n)) / n).sqrt();
+    let lower = (centre - margin) / (1.0 + z2 / n);
+    Some((lower.clamp(0.0, 1.0) * 10_000.0).floor() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wilson_lower_bound() {
+        assert_eq!(wilson_lower_bound_bps(9, 9, MIN_SUCCESS_RATE_SAMPLE), None);
+        assert_eq!(wilson_lower_bound_bps(0, 0, 0), None);
+
+        let small = wilson_lower_bound_bps(10, 10, MIN_SUCCESS_RATE_SAMPLE).unwrap();
+        let large = wilson_lower_bound_bps(1_000, 1_000, MIN_SUCCESS_RATE_SAMPLE).unwrap();
+        assert!(small < 7_500, "10/10 should be heavily discounted, got {}", small);
+        assert!(large > 9_900);
+
+        // 95/100 has a lower bound of roughly 88.8%
+        let rate = wilson_lower_bound_bps(95, 100, MIN_SUCCESS_RATE_SAMPLE).unwrap();
+        assert!((8_860..=8_900).contains(&rate), "got {}", rate);
+        assert_eq!(wilson_lower_bound_bps(0, 50, MIN_SUCCESS_RATE_SAMPLE), Some(0));
+    }
+}
@@ -0,0 +1,26 @@
+/// Mutating methods whose calls can be mirrored for a canary deployment.
+/// Secrets never appear in mirrored arguments: `register_agent` drops its
+/// invite code, and methods that take private entries, such as blocklist
+/// hashes, are not mirrorable.
+pub const MIRRORABLE_METHODS: &[&str] = &[
+    "register_agent",
+    "update_agent_metadata",
+    "update_agent_metadata_as_operator",
+    "add_skill",
+    "remove_skill",
+    "set_service_descriptor",
+    "deregister_agent",
+    "create_task",
+    "assign_task",
+    "submit_result",
+    "approve_task",
+    "cancel_task",
+    "submit_review",
+    "endorse_skill",
+    "batch_execute",
+];
+
+/// Largest serialized arguments a mirrored event carries. Bigger ones are
+/// left out so the event stays within NEAR's log limit; the relayer can
+/// read them from the transaction instead.
+pub const MAX_MIRRORED_ARGS_LENGTH: usize = 8 * 1024;
@@ -0,0 +1,52 @@
+//! NEP-171 / NEP-177 shapes for the agent identity tokens the registry
+//! mints, one per registered agent.
+
+use std::collections::HashMap;
+
+use near_account_id::AccountId;
+use serde::{Deserialize, Serialize};
+
+pub const NFT_METADATA_SPEC: &str = "nft-1.0.0";
+pub const NFT_NAME: &str = "Intellex Agents";
+pub const NFT_SYMBOL: &str = "IAGENT";
+
+/// NEP-177 contract metadata.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct NFTContractMetadata {
+    pub spec: String,
+    pub name: String,
+    pub symbol: String,
+    pub icon: Option<String>,
+    pub base_uri: Option<String>,
+    pub reference: Option<String>,
+    pub reference_hash: Option<String>,
+}
+
+/// NEP-177 token metadata. For an agent, `title` and `description` are its
+/// name and description, and `extra` is JSON with its skills and purpose.
+/// Timestamps are milliseconds, as strings.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct TokenMetadata {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub media: Option<String>,
+    pub media_hash: Option<String>,
+    pub copies: Option<u64>,
+    pub issued_at: Option<String>,
+    pub expires_at: Option<String>,
+    pub starts_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub extra: Option<String>,
+    pub reference: Option<String>,
+    pub reference_hash: Option<String>,
+}
+
+/// NEP-171 token. Agent tokens have no approvals, so
+/// `approved_account_ids` is always empty.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Token {
+    pub token_id: String,
+    pub owner_id: AccountId,
+    pub metadata: Option<TokenMetadata>,
+    pub approved_account_ids: Option<HashMap<AccountId, u64>>,
+}
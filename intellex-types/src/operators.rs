@@ -0,0 +1,2 @@
+/// Most operators an agent can delegate to at once.
+pub const MAX_AGENT_OPERATORS: u32 = 10;
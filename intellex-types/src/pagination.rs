@@ -0,0 +1,43 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_account_id::AccountId;
+use serde::{Deserialize, Serialize};
+
+/// One page of a list view. Pass `next_cursor` back to get the following
+/// page; it is `None` on the last one.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+/// Where a list view resumes. Sent to clients as base64 Borsh and opaque to
+/// them; a new layout is added as a new variant, so older tokens still
+/// decode.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+pub enum Cursor {
+    V1 {
+        /// View that issued the cursor.
+        list: String,
+        after: CursorKey,
+    },
+}
+
+/// Key of the last item returned. Cursors hold keys rather than offsets, so
+/// items added or removed between pages do not shift the next page.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+pub enum CursorKey {
+    /// Position in an append-only list, task id or held update sequence.
+    Sequence(u64),
+    /// Account id, for lists ordered by account.
+    Account(AccountId),
+    /// Group, sort value and account id, for ranked agent listings.
+    Ranked { group: u8, rank: u64, agent_id: AccountId },
+    /// Timestamp and position among the points sharing it, for time series.
+    Point { timestamp: u64, index: u32 },
+}
+
+impl<T> Default for Page<T> {
+    fn default() -> Self {
+        Self { items: Vec::new(), next_cursor: None }
+    }
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// Version of the registry's method and argument formats. Bumped whenever a
+/// method's arguments or return shape change incompatibly.
+pub const PROTOCOL_VERSION: &str = "2.0.0";
+
+/// Oldest client protocol version that can call `method_name` correctly.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MethodVersionRequirement {
+    pub method_name: String,
+    pub min_client_version: String,
+}
@@ -0,0 +1,40 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_account_id::AccountId;
+use serde::{Deserialize, Serialize};
+
+/// Length of a ranking epoch: 7 days, in nanoseconds.
+pub const RANKING_EPOCH_LENGTH: u64 = 7 * 24 * 60 * 60 * 1_000_000_000;
+/// Agents kept per leaderboard in a snapshot.
+pub const RANKING_SNAPSHOT_SIZE: usize = 20;
+/// Most skills that get their own leaderboard.
+pub const MAX_RANKED_SKILLS: usize = 10;
+
+/// Epoch `timestamp` falls in, counted from the Unix epoch.
+pub fn ranking_epoch(timestamp: u64) -> u64 {
+    timestamp / RANKING_EPOCH_LENGTH
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RankedAgent {
+    pub agent_id: AccountId,
+    pub reputation: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SkillRanking {
+    pub skill: String,
+    pub agents: Vec<RankedAgent>,
+}
+
+/// Leaderboards as they stood when the snapshot was taken: active agents
+/// by reputation, highest first, ties broken by account id. Never changed
+/// once written.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RankingSnapshot {
+    pub epoch: u64,
+    pub taken_at: u64,
+    pub taken_by: AccountId,
+    pub overall: Vec<RankedAgent>,
+    /// One leaderboard per ranked skill, in the order the skills were set.
+    pub by_skill: Vec<SkillRanking>,
+}
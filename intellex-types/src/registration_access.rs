@@ -0,0 +1,23 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_account_id::AccountId;
+use serde::{Deserialize, Serialize};
+
+pub const MAX_BAN_REASON_LENGTH: usize = 256;
+/// Most accounts one `add_to_allowlist` call can add.
+pub const MAX_ALLOWLIST_BATCH: usize = 100;
+
+/// Who may call `register_agent`. Banned accounts never can.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RegistrationMode {
+    #[default]
+    Open,
+    /// Only accounts on the owner's allowlist.
+    Allowlist,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Ban {
+    pub account_id: AccountId,
+    pub reason: String,
+    pub banned_at: u64,
+}
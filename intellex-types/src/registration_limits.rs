@@ -0,0 +1,28 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::staking::u128_string;
+
+/// Anti-sybil limits on registration, tunable by the registry owner. Each
+/// is off at 0.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct RegistrationLimits {
+    /// NEAR, in yoctoNEAR, each registration locks on top of storage.
+    #[serde(with = "u128_string")]
+    pub registration_bond: u128,
+    /// How long the bond stays locked after registering, in nanoseconds.
+    pub bond_lock_ns: u64,
+    /// Most agents registered in a single block.
+    pub max_registrations_per_block: u32,
+    /// How long an account waits after deregistering before it can register
+    /// again, in nanoseconds.
+    pub reregistration_cooldown_ns: u64,
+}
+
+/// NEAR locked by a registration until `unlocks_at`.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RegistrationBond {
+    #[serde(with = "u128_string")]
+    pub amount: u128,
+    pub unlocks_at: u64,
+}
@@ -0,0 +1,109 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::agent::AgentMetadata;
+
+/// Limits on agent metadata, tunable by the registry owner. Lengths are in
+/// bytes of UTF-8.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct RegistryConfig {
+    pub max_name_length: u32,
+    pub max_description_length: u32,
+    pub max_purpose_length: u32,
+    /// Most skills an agent can list, counted after deduplication.
+    pub max_skills: u32,
+    pub max_skill_length: u32,
+}
+
+impl Default for RegistryConfig {
+    fn default() -> Self {
+        Self {
+            max_name_length: 64,
+            max_description_length: 2048,
+            max_purpose_length: 512,
+            max_skills: 32,
+            max_skill_length: 64,
+        }
+    }
+}
+
+impl RegistryConfig {
+    /// Checks the limits themselves: each must be positive.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        let limits = [
+            self.max_name_length,
+            self.max_description_length,
+            self.max_purpose_length,
+            self.max_skills,
+            self.max_skill_length,
+        ];
+        if limits.contains(&0) {
+            return Err("Metadata limits must be positive");
+        }
+        Ok(())
+    }
+
+    /// Checks `metadata` against the limits: a non-empty name, field
+    /// lengths, skill count and no control characters, except line breaks
+    /// and tabs in the description. Returns a description of the first
+    /// problem found, if any.
+    pub fn validate_metadata(&self, metadata: &AgentMetadata) -> Result<(), String> {
+        if metadata.name.trim().is_empty() {
+            return Err("Agent name must not be empty".to_string());
+        }
+        check_text("Agent name", &metadata.name, self.max_name_length, false)?;
+        check_text("Agent description", &metadata.description, self.max_description_length, true)?;
+        check_text("Agent purpose", &metadata.purpose, self.max_purpose_length, false)?;
+        if metadata.skills.len() > self.max_skills as usize {
+            return Err(format!("An agent can list at most {} skills", self.max_skills));
+        }
+        metadata.skills.iter().try_for_each(|skill| self.validate_skill(skill))
+    }
+
+    pub fn validate_skill(&self, skill: &str) -> Result<(), String> {
+        check_text("Skill", skill, self.max_skill_length, false)
+    }
+}
+
+fn check_text(field: &str, text: &str, max_length: u32, multiline: bool) -> Result<(), String> {
+    if text.len() > max_length as usize {
+        return Err(format!("{} must be at most {} bytes, got {}", field, max_length, text.len()));
+    }
+    let allowed = |c: char| multiline && (c == '\n' || c == '\r' || c == '\t');
+    if text.chars().any(|c| c.is_control() && !allowed(c)) {
+        return Err(format!("{} must not contain control characters", field));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata() -> AgentMetadata {
+        AgentMetadata {
+            name: "Agent".to_string(),
+            description: "Line one\nLine two".to_string(),
+            skills: vec!["Rust".to_string()],
+            purpose: "Testing".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_validate_metadata() {
+        let config = RegistryConfig::default();
+        assert_eq!(config.validate_metadata(&metadata()), Ok(()));
+        let blank = AgentMetadata { name: " ".to_string(), ..metadata() };
+        assert_eq!(config.validate_metadata(&blank), Err("Agent name must not be empty".to_string()));
+        let long = AgentMetadata { purpose: "x".repeat(513), ..metadata() };
+        assert_eq!(
+            config.validate_metadata(&long),
+            Err("Agent purpose must be at most 512 bytes, got 513".to_string())
+        );
+        let control = AgentMetadata { name: "Agent\u{7}".to_string(), ..metadata() };
+        assert!(config.validate_metadata(&control).is_err());
+        let many = AgentMetadata { skills: vec!["Rust".to_string(); 2], ..metadata() };
+        assert!(RegistryConfig { max_skills: 1, ..config }.validate_metadata(&many).is_err());
+    }
+}
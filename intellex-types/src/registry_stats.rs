@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// Length of a registration bucket: one hour, in nanoseconds.
+pub const REGISTRATION_BUCKET_LENGTH: u64 = 60 * 60 * 1_000_000_000;
+/// Hourly registration buckets kept: one week's worth.
+pub const REGISTRATION_BUCKETS: u64 = 7 * 24;
+
+/// Hour `timestamp` falls in, counted from the Unix epoch.
+pub fn registration_hour(timestamp: u64) -> u64 {
+    timestamp / REGISTRATION_BUCKET_LENGTH
+}
+
+/// Registry-wide counts for dashboards. Recent registrations are counted in
+/// hourly buckets, so "the last day" spans the current hour and the 23
+/// before it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RegistryStats {
+    pub total_agents: u64,
+    /// Distinct skills at least one agent lists, compared by lookup key.
+    pub total_skills: u64,
+    pub registered_last_day: u64,
+    pub registered_last_week: u64,
+}
@@ -0,0 +1,148 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+pub struct TaskResult {
+    pub task_id: String,
+    pub success: bool,
+    pub timestamp: u64,
+    pub details: String,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+pub struct AgentInfo {
+    pub reputation: u64,
+    pub task_history: Vec<TaskResult>,
+    pub reputation_history: Vec<(u64, u64)>, // (timestamp, reputation)
+}
+
+/// Per-dimension scores behind an agent's overall reputation, on the same
+/// scale. Pushed by the reputation contract alongside `AgentInfo`.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReputationBreakdown {
+    pub quality: u64,
+    pub timeliness: u64,
+    pub communication: u64,
+}
+
+/// A `transfer_agent` or `remove_agent` call the reputation contract
+/// failed, kept until `retry_reputation_call` gets it through.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum PendingReputationCall {
+    /// Re-key the record kept under `from` to the agent.
+    Transfer { from: near_account_id::AccountId },
+    /// Drop the account's record.
+    Remove,
+}
+
+/// Outcome for one agent of `sync_reputation_batch`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ReputationSyncResult {
+    pub agent_id: near_account_id::AccountId,
+    pub synced: bool,
+    /// Why the agent was left untouched, when `synced` is false.
+    pub error: Option<String>,
+}
+
+/// An agent whose locally cached reputation was last refreshed at
+/// `last_synced`, longer ago than the age asked for.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StaleReputation {
+    pub agent_id: near_account_id::AccountId,
+    pub last_synced: u64,
+}
+
+/// How long consumers should cache a reputation read when nothing is
+/// pending: 10 minutes, in nanoseconds.
+pub const REPUTATION_CACHE_TTL: u64 = 10 * 60 * 1_000_000_000;
+
+/// An agent's reputation with caching guidance, as returned by
+/// `get_reputation_with_ttl`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ReputationWithTtl {
+    pub reputation: u64,
+    /// When the registry last received this agent's reputation.
+    pub synced_at: u64,
+    /// How long the answer may be cached, in nanoseconds. Zero while an
+    /// update is known to be on its way, so the next read picks it up.
+    pub cache_ttl_ns: u64,
+}
+
+/// Cache TTL for a reputation read: `REPUTATION_CACHE_TTL`, or zero when
+/// `update_pending`, e.g. while the registry holds or reconciles pushes.
+pub fn reputation_cache_ttl(update_pending: bool) -> u64 {
+    if update_pending {
+        0
+    } else {
+        REPUTATION_CACHE_TTL
+    }
+}
+
+/// One day, in nanoseconds: the granularity reputation history is compacted
+/// to once it is older than `REPUTATION_HISTORY_DETAIL_WINDOW`.
+pub const REPUTATION_SNAPSHOT_INTERVAL: u64 = 24 * 60 * 60 * 1_000_000_000;
+/// How far back every reputation change is kept: 30 days, in nanoseconds.
+pub const REPUTATION_HISTORY_DETAIL_WINDOW: u64 = 30 * REPUTATION_SNAPSHOT_INTERVAL;
+
+/// Thins `history`, sorted by timestamp, to the last point of each UTC day
+/// for points older than `REPUTATION_HISTORY_DETAIL_WINDOW` at `now`.
+/// Newer points are all kept.
+pub fn compact_reputation_history(history: &mut Vec<(u64, u64)>, now: u64) {
+    let cutoff = now.saturating_sub(REPUTATION_HISTORY_DETAIL_WINDOW);
+    let day = |timestamp: u64| timestamp / REPUTATION_SNAPSHOT_INTERVAL;
+    let keep: Vec<bool> = history
+        .iter()
+        .enumerate()
+        .map(|(i, (timestamp, _))| {
+            *timestamp >= cutoff || history.get(i + 1).is_none_or(|(next, _)| day(*next) != day(*timestamp))
+        })
+        .collect();
+    let mut keep = keep.into_iter();
+    history.retain(|_| keep.next().unwrap());
+}
+
+/// Points of `history`, sorted by timestamp, from `from` to `to` inclusive.
+/// Each is keyed by its timestamp and its position among the points
+/// sharing that timestamp; points up to and including `after` are skipped.
+pub fn reputation_history_range(
+    history: &[(u64, u64)],
+    from: u64,
+    to: u64,
+    after: Option<(u64, u32)>,
+) -> impl Iterator<Item = ((u64, u32), (u64, u64))> + '_ {
+    history
+        .iter()
+        .scan(None, |previous: &mut Option<(u64, u32)>, &(timestamp, reputation)| {
+            let index = match *previous {
+                Some((last, index)) if last == timestamp => index + 1,
+                _ => 0,
+            };
+            *previous = Some((timestamp, index));
+            Some(((timestamp, index), (timestamp, reputation)))
+        })
+        .filter(move |((timestamp, _), _)| (from..=to).contains(timestamp))
+        .filter(move |(key, _)| after.is_none_or(|after| *key > after))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: u64 = REPUTATION_SNAPSHOT_INTERVAL;
+
+    #[test]
+    fn test_old_history_keeps_one_point_per_day() {
+        let mut history = vec![(DAY, 1), (DAY + 5, 2), (3 * DAY, 3), (40 * DAY, 4), (40 * DAY + 5, 5)];
+        compact_reputation_history(&mut history, 45 * DAY);
+        assert_eq!(history, vec![(DAY + 5, 2), (3 * DAY, 3), (40 * DAY, 4), (40 * DAY + 5, 5)]);
+    }
+
+    #[test]
+    fn test_range_resumes_inside_a_timestamp() {
+        let history = [(1, 10), (2, 20), (2, 21), (3, 30), (9, 90)];
+        let keyed: Vec<_> = reputation_history_range(&history, 0, 8, None).collect();
+        assert_eq!(keyed.iter().map(|(key, _)| *key).collect::<Vec<_>>(), vec![(1, 0), (2, 0), (2, 1), (3, 0)]);
+        let rest: Vec<_> = reputation_history_range(&history, 0, 8, Some((2, 0))).map(|(_, point)| point).collect();
+        assert_eq!(rest, vec![(2, 21), (3, 30)]);
+    }
+}
@@ -0,0 +1,67 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_account_id::AccountId;
+use serde::{Deserialize, Serialize};
+
+/// Where agent reputation comes from, chosen at init.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum ReputationMode {
+    /// Computed by the registry itself from the results of its tasks.
+    /// Needs a contract built with the `reputation-engine` feature.
+    Local,
+    /// Pushed and served by a separate reputation contract.
+    External(AccountId),
+}
+
+/// How `ReputationMode::Local` scores task results.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct ReputationEngineConfig {
+    /// Points added for a successful task.
+    pub success_weight: u64,
+    /// Points taken away for a failed task.
+    pub failure_weight: u64,
+    /// Share of the current score lost before each new result, in basis
+    /// points, so recent tasks weigh more than old ones.
+    pub decay_bps: u16,
+    /// Ceiling on the score.
+    pub max_reputation: u64,
+}
+
+impl Default for ReputationEngineConfig {
+    fn default() -> Self {
+        Self { success_weight: 10, failure_weight: 20, decay_bps: 500, max_reputation: 100 }
+    }
+}
+
+impl ReputationEngineConfig {
+    /// Score after one more task result: the decayed score, plus
+    /// `success_weight` or minus `failure_weight`, kept within
+    /// `0..=max_reputation`.
+    pub fn apply(&self, reputation: u64, success: bool) -> u64 {
+        let kept_bps = 10_000 - u64::from(self.decay_bps.min(10_000));
+        let decayed = (u128::from(reputation) * u128::from(kept_bps) / 10_000) as u64;
+        let score = if success {
+            decayed.saturating_add(self.success_weight)
+        } else {
+            decayed.saturating_sub(self.failure_weight)
+        };
+        score.min(self.max_reputation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_decays_then_weighs_result() {
+        let config = ReputationEngineConfig::default();
+        assert_eq!(config.apply(0, true), 10);
+        assert_eq!(config.apply(60, true), 67);
+        assert_eq!(config.apply(60, false), 37);
+        assert_eq!(config.apply(10, false), 0);
+        assert_eq!(config.apply(100, true), 100);
+
+        let no_decay = ReputationEngineConfig { decay_bps: 0, ..config };
+        assert_eq!(no_decay.apply(60, true), 70);
+    }
+}
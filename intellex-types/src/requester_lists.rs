@@ -0,0 +1,11 @@
+/// Most hashed entries a requester may keep on their blocklist.
+pub const MAX_BLOCKLIST_SIZE: usize = 100;
+/// Most agents a requester may keep on their watchlist.
+pub const MAX_WATCHLIST_SIZE: usize = 100;
+
+/// Bytes hashed to form a blocklist entry: `"{requester}:{agent_id}"`.
+/// Salting with the requester keeps entries from being compared across
+/// requesters; the registry stores `hex(sha256(preimage))`.
+pub fn blocklist_entry_preimage(requester: &str, agent_id: &str) -> Vec<u8> {
+    format!("{}:{}", requester, agent_id).into_bytes()
+}
@@ -0,0 +1,32 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_account_id::AccountId;
+use serde::{Deserialize, Serialize};
+
+pub const MIN_REVIEW_RATING: u8 = 1;
+pub const MAX_REVIEW_RATING: u8 = 5;
+pub const MAX_REVIEW_COMMENT_LENGTH: usize = 512;
+
+/// A requester's rating of an agent for one completed task.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Review {
+    pub task_id: u64,
+    pub reviewer: AccountId,
+    /// 1 to 5 stars.
+    pub rating: u8,
+    pub comment: String,
+    pub submitted_at: u64,
+}
+
+/// Running totals of an agent's reviews.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Default)]
+pub struct ReviewStats {
+    pub review_count: u64,
+    pub rating_total: u64,
+}
+
+impl ReviewStats {
+    /// Mean rating times 100, e.g. 450 for 4.5 stars; `None` without reviews.
+    pub fn average_rating_x100(&self) -> Option<u64> {
+        (self.review_count > 0).then(|| self.rating_total * 100 / self.review_count)
+    }
+}
@@ -0,0 +1,33 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum SkillChangeKind {
+    Added,
+    Removed,
+}
+
+/// A skill an agent started or stopped listing, and when.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SkillChange {
+    pub skill: String,
+    pub kind: SkillChangeKind,
+    pub changed_at: u64,
+}
+
+/// Skills listed at `timestamp`, replayed from a chronological history.
+/// Changes made at exactly `timestamp` are included.
+pub fn skills_at(history: impl IntoIterator<Item = SkillChange>, timestamp: u64) -> Vec<String> {
+    let mut skills: Vec<String> = Vec::new();
+    for change in history.into_iter().take_while(|change| change.changed_at <= timestamp) {
+        match change.kind {
+            SkillChangeKind::Added => {
+                if !skills.contains(&change.skill) {
+                    skills.push(change.skill);
+                }
+            }
+            SkillChangeKind::Removed => skills.retain(|skill| *skill != change.skill),
+        }
+    }
+    skills
+}
@@ -0,0 +1,187 @@
+use std::fmt;
+
+/// Separator between a skill name and its version, e.g. `near-sdk@5.1`.
+pub const SKILL_VERSION_SEPARATOR: char = '@';
+
+/// A `MAJOR[.MINOR[.PATCH]]` version. Omitted components are treated as 0,
+/// so a skill listed as `near-sdk@5` compares as `5.0.0`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Op {
+    Exact,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    Caret,
+    Tilde,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Comparator {
+    op: Op,
+    version: Version,
+    /// Number of components written in the requirement (1-3).
+    precision: u8,
+}
+
+/// A comma-separated list of comparators (`>=1.2, <2`), all of which must
+/// match. Bare versions behave like `^`, as in Cargo; `*` matches anything.
+#[derive(Clone, Debug)]
+pub struct VersionReq {
+    comparators: Vec<Comparator>,
+}
+
+/// Splits `name@version` into its parts. Unversioned skills return `None`.
+pub fn split_versioned_skill(skill: &str) -> Option<(&str, Version)> {
+    let (name, version) = skill.split_once(SKILL_VERSION_SEPARATOR)?;
+    if name.is_empty() {
+        return None;
+    }
+    parse_version(version).map(|(version, _)| (name, version))
+}
+
+pub fn parse_version(input: &str) -> Option<(Version, u8)> {
+    let mut parts = [0u64; 3];
+    let mut precision = 0u8;
+    for (i, part) in input.trim().split('.').enumerate() {
+        if i >= 3 || part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        parts[i] = part.parse().ok()?;
+        precision += 1;
+    }
+    Some((
+        Version {
+            major: parts[0],
+            minor: parts[1],
+            patch: parts[2],
+        },
+        precision,
+    ))
+}
+
+impl Version {
+    pub fn parse(input: &str) -> Option<Self> {
+        parse_version(input).map(|(version, _)| version)
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl VersionReq {
+    pub fn parse(input: &str) -> Option<Self> {
+        let mut comparators = Vec::new();
+        for raw in input.split(',') {
+            let raw = raw.trim();
+            if raw == "*" {
+                continue;
+            }
+            let (op, rest) = [
+                (">=", Op::GreaterEq),
+                ("<=", Op::LessEq),
+                (">", Op::Greater),
+                ("<", Op::Less),
+                ("=", Op::Exact),
+                ("^", Op::Caret),
+                ("~", Op::Tilde),
+            ]
+            .iter()
+            .find_map(|(prefix, op)| raw.strip_prefix(prefix).map(|rest| (*op, rest)))
+            .unwrap_or((Op::Caret, raw));
+            let (version, precision) = parse_version(rest)?;
+            comparators.push(Comparator {
+                op,
+                version,
+                precision,
+            });
+        }
+        Some(Self { comparators })
+    }
+
+    pub fn matches(&self, version: &Version) -> bool {
+        self.comparators.iter().all(|c| c.matches(version))
+    }
+}
+
+impl Comparator {
+    fn matches(&self, v: &Version) -> bool {
+        let req = self.version;
+        match self.op {
+            Op::Exact => match self.precision {
+                1 => v.major == req.major,
+                2 => v.major == req.major && v.minor == req.minor,
+                _ => *v == req,
+            },
+            Op::Greater => *v > req,
+            Op::GreaterEq => *v >= req,
+            Op::Less => *v < req,
+            Op::LessEq => *v <= req,
+            Op::Caret => *v >= req && *v < self.caret_upper_bound(),
+            Op::Tilde => {
+                let upper = if self.precision == 1 {
+                    Version { major: req.major + 1, minor: 0, patch: 0 }
+                } else {
+                    Version { major: req.major, minor: req.minor + 1, patch: 0 }
+                };
+                *v >= req && *v < upper
+            }
+        }
+    }
+
+    /// `^1.2.3` allows `<2.0.0`; `^0.2.3` allows `<0.3.0`; `^0.0.3` allows `<0.0.4`.
+    fn caret_upper_bound(&self) -> Version {
+        let req = self.version;
+        if req.major > 0 || self.precision == 1 {
+            Version { major: req.major + 1, minor: 0, patch: 0 }
+        } else if req.minor > 0 || self.precision == 2 {
+            Version { major: 0, minor: req.minor + 1, patch: 0 }
+        } else {
+            Version { major: 0, minor: 0, patch: req.patch + 1 }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(input: &str) -> Version {
+        parse_version(input).unwrap().0
+    }
+
+    #[test]
+    fn test_version_req_matching() {
+        let caret = VersionReq::parse("^5.1").unwrap();
+        assert!(caret.matches(&v("5.1.0")));
+        assert!(caret.matches(&v("5.9")));
+        assert!(!caret.matches(&v("6.0.0")));
+        assert!(!caret.matches(&v("5.0.9")));
+
+        let zero = VersionReq::parse("0.3").unwrap();
+        assert!(zero.matches(&v("0.3.7")));
+        assert!(!zero.matches(&v("0.4.0")));
+
+        let tilde = VersionReq::parse("~1.2").unwrap();
+        assert!(tilde.matches(&v("1.2.9")));
+        assert!(!tilde.matches(&v("1.3.0")));
+
+        let range = VersionReq::parse(">=1.2, <2").unwrap();
+        assert!(range.matches(&v("1.9.9")));
+        assert!(!range.matches(&v("2.0.0")));
+
+        assert!(VersionReq::parse("*").unwrap().matches(&v("42")));
+        assert!(VersionReq::parse("=1").unwrap().matches(&v("1.4.2")));
+        assert!(VersionReq::parse("1.x").is_none());
+    }
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+pub const MAX_SKILL_ALIASES: usize = 16;
+
+/// Key skills are indexed and looked up by: trimmed, lowercased and with
+/// runs of whitespace collapsed, so `rust`, `Rust` and ` RUST` share one
+/// index entry, as do `machine learning` and `Machine  Learning`.
+pub fn skill_lookup_key(skill: &str) -> String {
+    skill.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// A canonical skill in the admin-curated taxonomy, with how many agents
+/// list it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SkillInfo {
+    pub skill: String,
+    /// Other spellings registrations are rewritten from.
+    pub aliases: Vec<String>,
+    pub agent_count: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skill_lookup_key_ignores_case_and_spacing() {
+        assert_eq!(skill_lookup_key(" RUST "), "rust");
+        assert_eq!(skill_lookup_key("Machine \t Learning"), "machine learning");
+        assert_eq!(skill_lookup_key("near-sdk@5.1"), "near-sdk@5.1");
+    }
+}
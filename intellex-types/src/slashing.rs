@@ -0,0 +1,19 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_account_id::AccountId;
+use serde::{Deserialize, Serialize};
+
+use crate::staking::u128_string;
+
+pub const MAX_SLASH_REASON_LENGTH: usize = 256;
+
+/// A penalty taken from an agent's stake.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SlashEvent {
+    /// Amount actually taken, which is less than requested if the agent's
+    /// stake ran out.
+    #[serde(with = "u128_string")]
+    pub amount: u128,
+    pub reason: String,
+    pub slashed_by: AccountId,
+    pub slashed_at: u64,
+}
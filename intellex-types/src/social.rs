@@ -0,0 +1,23 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_account_id::AccountId;
+use serde::{Deserialize, Serialize};
+
+/// SocialDB key, under the social account, where the link nonce is written:
+/// `{social_account_id}/intellex/link/{agent_id}`.
+pub fn social_link_key(social_account_id: &str, agent_id: &str) -> String {
+    format!("{}/intellex/link/{}", social_account_id, agent_id)
+}
+
+/// Claim that `social_account_id` has written `nonce` at `social_link_key`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SocialLinkProof {
+    pub social_account_id: AccountId,
+    pub nonce: String,
+}
+
+/// A near.social profile verified as controlled by the agent's operator.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SocialLink {
+    pub social_account_id: AccountId,
+    pub linked_at: u64,
+}
@@ -0,0 +1,41 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Stake an account must hold to register an agent: 100 ITLX (24 decimals).
+pub const MIN_REGISTRATION_STAKE: u128 = 100_000_000_000_000_000_000_000;
+/// Time between `unstake()` and the unstaked amount becoming withdrawable.
+pub const UNSTAKE_COOLDOWN_NS: u64 = 7 * 24 * 60 * 60 * 1_000_000_000; // 7 days
+
+/// ITLX an account has deposited with the registry through `ft_transfer_call`.
+/// Amounts are yocto-ITLX, serialized as decimal strings like near-sdk's `U128`.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct StakeInfo {
+    /// Counts towards `MIN_REGISTRATION_STAKE`.
+    #[serde(with = "u128_string")]
+    pub staked_balance: u128,
+    /// Unstaked but still in cooldown; no longer counts as stake.
+    #[serde(with = "u128_string")]
+    pub unstaking_balance: u128,
+    /// When `unstaking_balance` can be withdrawn. Each `unstake()` restarts it.
+    pub unstake_available_at: Option<u64>,
+}
+
+impl StakeInfo {
+    pub fn is_empty(&self) -> bool {
+        self.staked_balance == 0 && self.unstaking_balance == 0
+    }
+}
+
+/// Serde helper for `u128` amounts as decimal strings, matching near-sdk's
+/// `U128` on the wire.
+pub mod u128_string {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &u128, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(de::Error::custom)
+    }
+}
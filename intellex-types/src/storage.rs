@@ -0,0 +1,58 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::staking::u128_string;
+
+/// NEAR an account has deposited to pay for the storage its agent uses.
+/// `locked_bytes` are the bytes its agent record and skill index entries
+/// take up; they are priced at the current byte cost when read.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default, PartialEq)]
+pub struct StorageAccount {
+    pub total: u128,
+    pub locked_bytes: u64,
+}
+
+/// NEP-145 storage balance, in yoctoNEAR. `total - available` is locked to
+/// pay for the account's agent record and skill index entries.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StorageBalance {
+    #[serde(with = "u128_string")]
+    pub total: u128,
+    #[serde(with = "u128_string")]
+    pub available: u128,
+}
+
+/// NEP-145 bounds. No minimum is needed to open a storage account; the
+/// cost of registering is charged by bytes written.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StorageBalanceBounds {
+    #[serde(with = "u128_string")]
+    pub min: u128,
+    #[serde(with = "option_u128_string")]
+    pub max: Option<u128>,
+}
+
+/// What `update_agent_metadata` would write and free for a proposed
+/// metadata change, and how much more to attach so it does not fail on
+/// storage (zero when the available balance covers it).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct UpdateCostEstimate {
+    pub bytes_added: u64,
+    pub bytes_freed: u64,
+    #[serde(with = "u128_string")]
+    pub required_deposit: u128,
+}
+
+mod option_u128_string {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &Option<u128>, serializer: S) -> Result<S::Ok, S::Error> {
+        value.map(|value| value.to_string()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u128>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|value| value.parse().map_err(serde::de::Error::custom))
+            .transpose()
+    }
+}
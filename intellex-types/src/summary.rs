@@ -0,0 +1,36 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_account_id::AccountId;
+use serde::{Deserialize, Serialize};
+
+use crate::agent::AgentStatus;
+use crate::bands::ReputationBand;
+
+/// Ordering for list views that return agents.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Default)]
+pub enum SortOrder {
+    #[default]
+    ReputationDesc,
+    ReputationAsc,
+    NewestFirst,
+    OldestFirst,
+}
+
+/// Compact view of an agent for listings, without histories.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AgentSummary {
+    pub agent_id: AccountId,
+    pub name: String,
+    pub skills: Vec<String>,
+    pub reputation: u64,
+    pub status: AgentStatus,
+    pub band: ReputationBand,
+    pub handle: Option<String>,
+    /// Linked near.social account, if any.
+    pub social_profile: Option<AccountId>,
+    pub online_now: bool,
+    pub review_count: u64,
+    /// Mean review rating times 100, e.g. 450 for 4.5 stars.
+    pub average_rating_x100: Option<u64>,
+    pub registered_at: u64,
+    pub version: u64,
+}
@@ -0,0 +1,8 @@
+/// Skills under this prefix mark protocol roles, such as
+/// `system:verified-oracle`. Only the registry owner can grant or revoke
+/// them; agents cannot declare them in their own metadata.
+pub const SYSTEM_SKILL_PREFIX: &str = "system:";
+
+pub fn is_system_skill(skill: &str) -> bool {
+    skill.starts_with(SYSTEM_SKILL_PREFIX)
+}
@@ -0,0 +1,58 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::reputation::TaskResult;
+
+/// Latest task results kept on-chain per agent unless the owner changes it.
+pub const DEFAULT_TASK_HISTORY_WINDOW: u32 = 50;
+/// Largest window the owner can set, so clearing or moving an agent's
+/// history fits in one call.
+pub const MAX_TASK_HISTORY_WINDOW: u32 = 100;
+
+/// Counts over an agent's whole task history, including results that have
+/// since left the on-chain window. Kept up to date as results arrive, so
+/// reading it costs the same however long the history is.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TaskStats {
+    pub total: u64,
+    pub succeeded: u64,
+    pub failed: u64,
+    /// `succeeded` out of `total`, in basis points; 0 before any result.
+    pub success_rate_bps: u32,
+    /// Latest `TaskResult::timestamp` recorded; 0 before any result.
+    pub last_task_at: u64,
+}
+
+impl TaskStats {
+    pub fn record(&mut self, result: &TaskResult) {
+        self.total += 1;
+        if result.success {
+            self.succeeded += 1;
+        } else {
+            self.failed += 1;
+        }
+        self.success_rate_bps = (u128::from(self.succeeded) * 10_000 / u128::from(self.total)) as u32;
+        self.last_task_at = self.last_task_at.max(result.timestamp);
+    }
+}
+
+impl<'a> FromIterator<&'a TaskResult> for TaskStats {
+    fn from_iter<I: IntoIterator<Item = &'a TaskResult>>(results: I) -> Self {
+        let mut stats = Self::default();
+        results.into_iter().for_each(|result| stats.record(result));
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_track_rate_and_latest_task() {
+        let result = |success, timestamp| TaskResult { task_id: String::new(), success, timestamp, details: String::new() };
+        let stats: TaskStats = [result(true, 30), result(false, 10), result(true, 20)].iter().collect();
+        assert_eq!(stats, TaskStats { total: 3, succeeded: 2, failed: 1, success_rate_bps: 6_666, last_task_at: 30 });
+        assert_eq!(TaskStats::default().success_rate_bps, 0);
+    }
+}
@@ -0,0 +1,92 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_account_id::AccountId;
+use serde::{Deserialize, Serialize};
+
+use crate::staking::u128_string;
+
+pub const MAX_TASK_DESCRIPTION_LENGTH: usize = 1024;
+pub const MAX_TASK_RESULT_LENGTH: usize = 1024;
+pub const MAX_TASK_SKILLS: usize = 16;
+/// Ceiling on the protocol fee: 10% of a task's rewards.
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 1_000;
+/// How long a settled task keeps its full record before it can be compacted
+/// into an `ArchivedTask`: 90 days, in nanoseconds.
+pub const TASK_RETENTION_PERIOD: u64 = 90 * 24 * 60 * 60 * 1_000_000_000;
+pub const MAX_TASK_COMPACTION_BATCH: u64 = 100;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TaskStatus {
+    /// Waiting for the creator to assign an agent.
+    Open,
+    /// Assigned; the agent works on it and submits a result.
+    Assigned,
+    /// Result submitted, awaiting the creator's approval.
+    Submitted,
+    /// Approved; the reward was paid and the result sent to the reputation
+    /// contract.
+    Completed,
+    /// Withdrawn by the creator before assignment; the reward was refunded.
+    Cancelled,
+    /// Escrow frozen by a dispute until the arbiter rules.
+    Disputed,
+    /// Dispute settled; the rewards were split as ruled.
+    Resolved,
+}
+
+impl TaskStatus {
+    /// True once escrow has been paid out or refunded and the task can no
+    /// longer change.
+    pub fn is_settled(&self) -> bool {
+        matches!(self, TaskStatus::Completed | TaskStatus::Cancelled | TaskStatus::Resolved)
+    }
+}
+
+/// A unit of work posted by a requester, with its reward held in escrow by
+/// the registry until approval.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Task {
+    pub task_id: u64,
+    pub creator: AccountId,
+    pub description: String,
+    /// Skills the assigned agent must list, matched exactly.
+    pub required_skills: Vec<String>,
+    /// Reward in yoctoNEAR.
+    #[serde(with = "u128_string")]
+    pub reward: u128,
+    /// Reward in yocto-ITLX, escrowed through `ft_transfer_call`.
+    #[serde(with = "u128_string")]
+    pub itlx_reward: u128,
+    /// Protocol fee in basis points, fixed when the task was created.
+    pub fee_bps: u16,
+    pub status: TaskStatus,
+    pub agent_id: Option<AccountId>,
+    pub result: Option<String>,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+/// What is kept of a settled task once its retention period has passed.
+/// `record_hash` is hex(sha256(borsh(Task))) of the full record, so a copy
+/// kept off-chain can still be checked against the registry.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ArchivedTask {
+    pub task_id: u64,
+    pub record_hash: String,
+    pub creator: AccountId,
+    pub agent_id: Option<AccountId>,
+    pub status: TaskStatus,
+    #[serde(with = "u128_string")]
+    pub reward: u128,
+    #[serde(with = "u128_string")]
+    pub itlx_reward: u128,
+    pub fee_bps: u16,
+    pub created_at: u64,
+    pub settled_at: u64,
+}
+
+/// Share of task rewards sent to the treasury on approval.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ProtocolFee {
+    pub fee_bps: u16,
+    pub treasury_id: AccountId,
+}
@@ -0,0 +1,63 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use near_account_id::AccountId;
+use serde::{Deserialize, Serialize};
+
+pub const MAX_TEAM_NAME_LENGTH: usize = 64;
+pub const MAX_TEAM_DESCRIPTION_LENGTH: usize = 512;
+/// Most members a team can have, its lead included. Pending invites count
+/// towards the limit.
+pub const MAX_TEAM_MEMBERS: u32 = 20;
+/// Most teams an agent can belong to, led ones included.
+pub const MAX_AGENT_TEAMS: u32 = 10;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct TeamMetadata {
+    pub description: String,
+    /// Skills the team offers as a whole, for display only; tasks are
+    /// still matched against each member's own skills.
+    #[serde(default)]
+    pub skills: Vec<String>,
+}
+
+/// A group of agents that task creators can hire as a unit. The lead, the
+/// agent that created it, invites the others.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Team {
+    pub team_id: u64,
+    pub name: String,
+    pub metadata: TeamMetadata,
+    pub lead: AccountId,
+    pub created_at: u64,
+}
+
+/// Reputation of a team's members, read at query time.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct TeamReputation {
+    pub member_count: u64,
+    pub total_reputation: u64,
+    /// Rounded down; 0 for a team without members.
+    pub average_reputation: u64,
+    pub max_reputation: u64,
+}
+
+impl TeamReputation {
+    pub fn from_scores(scores: impl IntoIterator<Item = u64>) -> Self {
+        let mut reputation = Self::default();
+        for score in scores {
+            reputation.member_count += 1;
+            reputation.total_reputation = reputation.total_reputation.saturating_add(score);
+            reputation.max_reputation = reputation.max_reputation.max(score);
+        }
+        reputation.average_reputation = reputation
+            .total_reputation
+            .checked_div(reputation.member_count)
+            .unwrap_or(0);
+        reputation
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TeamView {
+    pub team: Team,
+    pub reputation: TeamReputation,
+}
@@ -0,0 +1,46 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+/// Length of an activity bucket: one day, in nanoseconds.
+pub const TRENDING_BUCKET_LENGTH: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+/// Day `timestamp` falls in, counted from the Unix epoch.
+pub fn trending_day(timestamp: u64) -> u64 {
+    timestamp / TRENDING_BUCKET_LENGTH
+}
+
+/// Rolling window `get_trending_skills` sums activity over.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrendWindow {
+    /// The current day and the 6 before it.
+    Week,
+    /// The current day and the 29 before it.
+    Month,
+}
+
+impl TrendWindow {
+    pub fn days(self) -> u64 {
+        match self {
+            TrendWindow::Week => 7,
+            TrendWindow::Month => 30,
+        }
+    }
+}
+
+/// One day of a skill's activity.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct SkillActivity {
+    pub day: u64,
+    /// Agents registered listing the skill: supply.
+    pub registrations: u64,
+    /// Tasks requiring the skill that were assigned: demand.
+    pub assignments: u64,
+}
+
+/// A skill's activity summed over a window.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TrendingSkill {
+    pub skill: String,
+    pub registrations: u64,
+    pub assignments: u64,
+}
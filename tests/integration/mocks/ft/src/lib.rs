@@ -0,0 +1,97 @@
+//! Minimal NEP-141 token standing in for ITLX in integration tests. Anyone
+//! can mint, and transfers can be told to fail.
+
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::serde_json::json;
+use near_sdk::store::LookupMap;
+use near_sdk::{env, near_bindgen, require, AccountId, Gas, NearToken, PanicOnDefault, Promise, PromiseResult};
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct MockFt {
+    balances: LookupMap<AccountId, u128>,
+    failing: bool,
+}
+
+#[near_bindgen]
+impl MockFt {
+    #[init]
+    pub fn new() -> Self {
+        Self { balances: LookupMap::new(b"b"), failing: false }
+    }
+
+    pub fn mint(&mut self, account_id: AccountId, amount: U128) {
+        let balance = self.balance(&account_id);
+        self.balances.insert(account_id, balance + amount.0);
+    }
+
+    /// While set, `ft_transfer` panics.
+    pub fn set_failing(&mut self, failing: bool) {
+        self.failing = failing;
+    }
+
+    #[payable]
+    pub fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>) {
+        let _ = memo;
+        require!(!self.failing, "Token transfers are failing on purpose");
+        self.move_tokens(&env::predecessor_account_id(), &receiver_id, amount.0);
+    }
+
+    /// Moves the tokens, then calls the receiver's `ft_on_transfer` and
+    /// refunds whatever it hands back.
+    #[payable]
+    pub fn ft_transfer_call(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>, msg: String) -> Promise {
+        let _ = memo;
+        let sender_id = env::predecessor_account_id();
+        self.move_tokens(&sender_id, &receiver_id, amount.0);
+        Promise::new(receiver_id.clone())
+            .function_call(
+                "ft_on_transfer".to_string(),
+                json!({ "sender_id": sender_id, "amount": amount, "msg": msg }).to_string().into_bytes(),
+                NearToken::from_yoctonear(0),
+                Gas::from_tgas(30),
+            )
+            .then(Promise::new(env::current_account_id()).function_call(
+                "ft_resolve_transfer".to_string(),
+                json!({ "sender_id": sender_id, "receiver_id": receiver_id, "amount": amount }).to_string().into_bytes(),
+                NearToken::from_yoctonear(0),
+                Gas::from_tgas(10),
+            ))
+    }
+
+    /// Returns the amount the receiver kept.
+    #[private]
+    pub fn ft_resolve_transfer(&mut self, sender_id: AccountId, receiver_id: AccountId, amount: U128) -> U128 {
+        let unused = match env::promise_result(0) {
+            PromiseResult::Successful(value) => {
+                near_sdk::serde_json::from_slice::<U128>(&value).map_or(amount.0, |unused| unused.0.min(amount.0))
+            }
+            PromiseResult::Failed => amount.0,
+        };
+        let refund = unused.min(self.balance(&receiver_id));
+        if refund > 0 {
+            self.move_tokens(&receiver_id, &sender_id, refund);
+        }
+        U128(amount.0 - refund)
+    }
+
+    pub fn ft_balance_of(&self, account_id: AccountId) -> U128 {
+        U128(self.balance(&account_id))
+    }
+}
+
+impl MockFt {
+    fn balance(&self, account_id: &AccountId) -> u128 {
+        self.balances.get(account_id).copied().unwrap_or_default()
+    }
+
+    fn move_tokens(&mut self, sender_id: &AccountId, receiver_id: &AccountId, amount: u128) {
+        let balance = self.balance(sender_id);
+        require!(balance >= amount, "Not enough tokens");
+        self.balances.insert(sender_id.clone(), balance - amount);
+        let received = self.balance(receiver_id);
+        self.balances.insert(receiver_id.clone(), received + amount);
+    }
+}
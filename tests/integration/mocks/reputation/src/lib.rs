@@ -0,0 +1,94 @@
+//! Stand-in for the reputation contract in integration tests. It keeps an
+//! `AgentInfo` per agent, answers the calls the registry makes, and can be
+//! told to fail them.
+
+use intellex_types::{AgentInfo, TaskResult};
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::serde_json::json;
+use near_sdk::store::LookupMap;
+use near_sdk::{env, near_bindgen, AccountId, Gas, NearToken, PanicOnDefault, Promise};
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
+#[borsh(crate = "near_sdk::borsh")]
+pub struct MockReputation {
+    agents: LookupMap<AccountId, AgentInfo>,
+    failing: bool,
+}
+
+#[near_bindgen]
+impl MockReputation {
+    #[init]
+    pub fn new() -> Self {
+        Self { agents: LookupMap::new(b"a"), failing: false }
+    }
+
+    /// While set, every call from the registry panics.
+    pub fn set_failing(&mut self, failing: bool) {
+        self.failing = failing;
+    }
+
+    pub fn initialize_agent(&mut self, agent_id: AccountId) {
+        self.assert_not_failing();
+        let info = AgentInfo {
+            reputation: 0,
+            task_history: Vec::new(),
+            reputation_history: vec![(env::block_timestamp(), 0)],
+        };
+        self.agents.insert(agent_id, info);
+    }
+
+    pub fn get_agent_info(&self, agent_id: AccountId) -> Option<AgentInfo> {
+        self.assert_not_failing();
+        self.agents.get(&agent_id).cloned()
+    }
+
+    pub fn add_task_result(&mut self, agent_id: AccountId, task_result: TaskResult) {
+        self.assert_not_failing();
+        if let Some(info) = self.agents.get_mut(&agent_id) {
+            info.task_history.push(task_result);
+        }
+    }
+
+    pub fn remove_agent(&mut self, agent_id: AccountId) {
+        self.assert_not_failing();
+        self.agents.remove(&agent_id);
+    }
+
+    pub fn transfer_agent(&mut self, agent_id: AccountId, new_agent_id: AccountId) {
+        self.assert_not_failing();
+        if let Some(info) = self.agents.remove(&agent_id) {
+            self.agents.insert(new_agent_id, info);
+        }
+    }
+
+    /// Changes an agent's score here only, for the registry to pull with
+    /// `sync_reputation`.
+    pub fn set_reputation(&mut self, agent_id: AccountId, reputation: u64) {
+        let info = self.agents.get_mut(&agent_id).unwrap_or_else(|| env::panic_str("Unknown agent"));
+        info.reputation = reputation;
+        info.reputation_history.push((env::block_timestamp(), reputation));
+    }
+
+    /// Sets a score here and pushes it to the registry with
+    /// `record_reputation_change`, along with the hash of the new state.
+    pub fn push_reputation_change(&mut self, registry_id: AccountId, agent_id: AccountId, new_value: u64, nonce: u64) -> Promise {
+        self.set_reputation(agent_id.clone(), new_value);
+        let info = self.agents.get(&agent_id).unwrap();
+        let tip_hash: String = env::sha256(&near_sdk::borsh::to_vec(info).unwrap()).iter().map(|b| format!("{:02x}", b)).collect();
+        Promise::new(registry_id).function_call(
+            "record_reputation_change".to_string(),
+            json!({ "agent_id": agent_id, "new_value": new_value, "nonce": nonce, "tip_hash": tip_hash }).to_string().into_bytes(),
+            NearToken::from_yoctonear(0),
+            Gas::from_tgas(20),
+        )
+    }
+}
+
+impl MockReputation {
+    fn assert_not_failing(&self) {
+        if self.failing {
+            env::panic_str("Reputation contract is failing on purpose");
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! Sandbox fixtures for the registry's end-to-end tests: the registry, the
+//! mock reputation contract and the mock ITLX token, deployed from source.
+
+use intellex_types::staking::MIN_REGISTRATION_STAKE;
+use intellex_types::AgentMetadata;
+use near_workspaces::network::Sandbox;
+use near_workspaces::result::ExecutionFinalResult;
+use near_workspaces::types::{AccountDetailsPatch, NearToken};
+use near_workspaces::{Account, AccountId, Contract, Worker};
+use serde_json::json;
+
+/// The registry only accepts stake from this account, so the mock token is
+/// patched in under it.
+pub const ITLX_TOKEN_CONTRACT: &str = "itlx.token.near";
+
+pub struct Env {
+    pub worker: Worker<Sandbox>,
+    pub registry: Contract,
+    pub reputation: Contract,
+    pub itlx: AccountId,
+    pub owner: Account,
+}
+
+impl Env {
+    /// Deploys the three contracts and initializes them, with `owner`
+    /// owning the registry.
+    pub async fn new() -> anyhow::Result<Self> {
+        let worker = near_workspaces::sandbox().await?;
+        let registry_wasm = near_workspaces::compile_project("../../intellex-registry-contract").await?;
+        let reputation_wasm = near_workspaces::compile_project("./mocks/reputation").await?;
+        let ft_wasm = near_workspaces::compile_project("./mocks/ft").await?;
+
+        let owner = worker.dev_create_account().await?;
+        let reputation = worker.dev_deploy(&reputation_wasm).await?;
+        reputation.call("new").transact().await?.into_result()?;
+
+        let itlx: AccountId = ITLX_TOKEN_CONTRACT.parse()?;
+        worker
+            .patch(&itlx)
+            .account(AccountDetailsPatch::default().balance(NearToken::from_near(100)))
+            .code(&ft_wasm)
+            .transact()
+            .await?;
+        owner.call(&itlx, "new").transact().await?.into_result()?;
+
+        let registry = worker.dev_deploy(&registry_wasm).await?;
+        registry
+            .call("new")
+            .args_json(json!({ "owner_id": owner.id(), "reputation_contract_id": reputation.id() }))
+            .transact()
+            .await?
+            .into_result()?;
+
+        Ok(Self { worker, registry, reputation, itlx, owner })
+    }
+
+    /// A fresh account holding `MIN_REGISTRATION_STAKE` ITLX, not staked.
+    pub async fn funded_account(&self) -> anyhow::Result<Account> {
+        let account = self.worker.dev_create_account().await?;
+        self.owner
+            .call(&self.itlx, "mint")
+            .args_json(json!({ "account_id": account.id(), "amount": MIN_REGISTRATION_STAKE.to_string() }))
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(account)
+    }
+
+    /// Stakes `amount` ITLX with the registry through `ft_transfer_call`.
+    pub async fn stake(&self, account: &Account, amount: u128) -> anyhow::Result<()> {
+        self.transfer_call(account, amount, "stake").await
+    }
+
+    /// Sends `amount` ITLX to the registry with `ft_transfer_call` and `msg`.
+    pub async fn transfer_call(&self, account: &Account, amount: u128, msg: &str) -> anyhow::Result<()> {
+        account
+            .call(&self.itlx, "ft_transfer_call")
+            .args_json(json!({ "receiver_id": self.registry.id(), "amount": amount.to_string(), "msg": msg }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+
+    pub async fn itlx_balance(&self, account: &Account) -> anyhow::Result<u128> {
+        let balance: String = self
+            .worker
+            .view(&self.itlx, "ft_balance_of")
+            .args_json(json!({ "account_id": account.id() }))
+            .await?
+            .json()?;
+        Ok(balance.parse()?)
+    }
+
+    /// Calls `register_agent` with one NEAR for storage.
+    pub async fn register(&self, account: &Account) -> anyhow::Result<ExecutionFinalResult> {
+        let metadata = AgentMetadata {
+            name: "Integration Agent".to_string(),
+            description: "Registered from the sandbox".to_string(),
+            skills: vec!["Rust".to_string()],
+            purpose: "Testing".to_string(),
+            ..Default::default()
+        };
+        Ok(account
+            .call(self.registry.id(), "register_agent")
+            .args_json(json!({ "metadata": metadata, "invite_code": null }))
+            .deposit(NearToken::from_near(1))
+            .max_gas()
+            .transact()
+            .await?)
+    }
+
+    /// A funded, staked and registered agent.
+    pub async fn agent(&self) -> anyhow::Result<Account> {
+        let account = self.funded_account().await?;
+        self.stake(&account, MIN_REGISTRATION_STAKE).await?;
+        self.register(&account).await?.into_result()?;
+        Ok(account)
+    }
+
+    /// Makes every call into the mock reputation contract fail, or succeed
+    /// again.
+    pub async fn set_reputation_failing(&self, failing: bool) -> anyhow::Result<()> {
+        self.reputation
+            .call("set_failing")
+            .args_json(json!({ "failing": failing }))
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+
+    /// Makes every `ft_transfer` on the mock token fail, or succeed again.
+    pub async fn set_token_failing(&self, failing: bool) -> anyhow::Result<()> {
+        self.owner
+            .call(&self.itlx, "set_failing")
+            .args_json(json!({ "failing": failing }))
+            .transact()
+            .await?
+            .into_result()?;
+        Ok(())
+    }
+
+    pub async fn view<T: serde::de::DeserializeOwned>(&self, method: &str, args: serde_json::Value) -> anyhow::Result<T> {
+        Ok(self.registry.view(method).args_json(args).await?.json()?)
+    }
+}
@@ -0,0 +1,161 @@
+use intellex_integration_tests::Env;
+use intellex_types::staking::MIN_REGISTRATION_STAKE;
+use near_workspaces::types::NearToken;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn test_register_checks_stake_and_initializes_reputation() -> anyhow::Result<()> {
+    let env = Env::new().await?;
+
+    // Registration is refused until the stake is in
+    let agent = env.funded_account().await?;
+    let outcome = env.register(&agent).await?;
+    assert!(outcome.is_failure());
+    assert!(format!("{:?}", outcome.into_result().unwrap_err()).contains("Insufficient ITLX stake to register"));
+
+    env.stake(&agent, MIN_REGISTRATION_STAKE).await?;
+    let staked: String = env.view("get_staked_balance", json!({ "account_id": agent.id() })).await?;
+    assert_eq!(staked, MIN_REGISTRATION_STAKE.to_string());
+
+    env.register(&agent).await?.into_result()?;
+    let storage: Option<Value> = env.view("storage_balance_of", json!({ "account_id": agent.id() })).await?;
+    assert!(storage.is_some());
+    let pending: bool = env.view("is_initialization_pending", json!({ "agent_id": agent.id() })).await?;
+    assert!(!pending);
+    let info: Option<Value> = env.reputation.view("get_agent_info").args_json(json!({ "agent_id": agent.id() })).await?.json()?;
+    assert!(info.is_some());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failed_initialization_can_be_retried() -> anyhow::Result<()> {
+    let env = Env::new().await?;
+    env.set_reputation_failing(true).await?;
+
+    // The agent is registered; only its reputation record is missing
+    let agent = env.agent().await?;
+    let reputation: Option<u64> = env.view("get_agent_reputation", json!({ "agent_id": agent.id() })).await?;
+    assert_eq!(reputation, Some(0));
+    let pending: bool = env.view("is_initialization_pending", json!({ "agent_id": agent.id() })).await?;
+    assert!(pending);
+
+    env.set_reputation_failing(false).await?;
+    env.owner
+        .call(env.registry.id(), "retry_initialization")
+        .args_json(json!({ "agent_id": agent.id() }))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+    let pending: bool = env.view("is_initialization_pending", json!({ "agent_id": agent.id() })).await?;
+    assert!(!pending);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sync_pulls_reputation_and_survives_failures() -> anyhow::Result<()> {
+    let env = Env::new().await?;
+    let agent = env.agent().await?;
+    env.reputation
+        .call("set_reputation")
+        .args_json(json!({ "agent_id": agent.id(), "reputation": 42 }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let synced: bool = env
+        .owner
+        .call(env.registry.id(), "sync_reputation")
+        .args_json(json!({ "agent_id": agent.id() }))
+        .max_gas()
+        .transact()
+        .await?
+        .json()?;
+    assert!(synced);
+    let reputation: Option<u64> = env.view("get_agent_reputation", json!({ "agent_id": agent.id() })).await?;
+    assert_eq!(reputation, Some(42));
+
+    // A failing reputation contract leaves the synced score in place
+    env.reputation
+        .call("set_reputation")
+        .args_json(json!({ "agent_id": agent.id(), "reputation": 7 }))
+        .transact()
+        .await?
+        .into_result()?;
+    env.set_reputation_failing(true).await?;
+    let synced: bool = env
+        .owner
+        .call(env.registry.id(), "sync_reputation")
+        .args_json(json!({ "agent_id": agent.id() }))
+        .max_gas()
+        .transact()
+        .await?
+        .json()?;
+    assert!(!synced);
+    let reputation: Option<u64> = env.view("get_agent_reputation", json!({ "agent_id": agent.id() })).await?;
+    assert_eq!(reputation, Some(42));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_pushed_reputation_rejects_replayed_nonces() -> anyhow::Result<()> {
+    let env = Env::new().await?;
+    let agent = env.agent().await?;
+    let push = |new_value: u64, nonce: u64| {
+        env.reputation
+            .call("push_reputation_change")
+            .args_json(json!({ "registry_id": env.registry.id(), "agent_id": agent.id(), "new_value": new_value, "nonce": nonce }))
+            .max_gas()
+            .transact()
+    };
+
+    push(30, 1).await?.into_result()?;
+    let reputation: Option<u64> = env.view("get_agent_reputation", json!({ "agent_id": agent.id() })).await?;
+    assert_eq!(reputation, Some(30));
+
+    let replay = push(90, 1).await?;
+    assert!(replay.is_failure());
+    assert!(format!("{:?}", replay.into_result().unwrap_err()).contains("Stale reputation push: nonce must exceed 1"));
+    let reputation: Option<u64> = env.view("get_agent_reputation", json!({ "agent_id": agent.id() })).await?;
+    assert_eq!(reputation, Some(30));
+    let nonce: u64 = env.view("get_reputation_push_nonce", json!({ "agent_id": agent.id() })).await?;
+    assert_eq!(nonce, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failed_itlx_payout_is_owed_until_claimed() -> anyhow::Result<()> {
+    let env = Env::new().await?;
+    let creator = env.funded_account().await?;
+    let task: Value = creator
+        .call(env.registry.id(), "create_task")
+        .args_json(json!({ "description": "Summarize a paper", "required_skills": ["Rust"], "reward": "0" }))
+        .deposit(NearToken::from_near(1))
+        .max_gas()
+        .transact()
+        .await?
+        .json()?;
+    let task_id = task["task_id"].as_u64().unwrap();
+    env.transfer_call(&creator, 1_000, &format!("fund_task:{}", task_id)).await?;
+    assert_eq!(env.itlx_balance(&creator).await?, MIN_REGISTRATION_STAKE - 1_000);
+
+    // The refund's ft_transfer fails, so the registry keeps it owed
+    env.set_token_failing(true).await?;
+    creator
+        .call(env.registry.id(), "cancel_task")
+        .args_json(json!({ "task_id": task_id }))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+    let owed: String = env.view("get_itlx_payout_owed", json!({ "account_id": creator.id() })).await?;
+    assert_eq!(owed, "1000");
+    assert_eq!(env.itlx_balance(&creator).await?, MIN_REGISTRATION_STAKE - 1_000);
+
+    env.set_token_failing(false).await?;
+    creator.call(env.registry.id(), "claim_itlx_payout").max_gas().transact().await?.into_result()?;
+    let owed: String = env.view("get_itlx_payout_owed", json!({ "account_id": creator.id() })).await?;
+    assert_eq!(owed, "0");
+    assert_eq!(env.itlx_balance(&creator).await?, MIN_REGISTRATION_STAKE);
+    Ok(())
+}